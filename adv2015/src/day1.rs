@@ -1,39 +1,35 @@
-pub fn count_brackets(s: &str) -> i16 {
-    let chars = s.chars();
-    let floor = chars.fold(0, |acc, c| {
-        if c == '(' {
-            acc + 1
-        } else if c == ')' {
-            acc - 1
-        } else {
-            acc
-        }
-    });
+use advparse::ParseError;
+
+pub fn count_brackets(s: &str) -> Result<i16, ParseError> {
+    let floor = parse_moves(s)?.iter().sum();
 
-    floor
+    Ok(floor)
 }
 
-pub fn count_position(s: &str) -> Option<i16> {
+pub fn count_position(s: &str) -> Result<i16, ParseError> {
     let mut sum: i16 = 0;
-    let mut counter: i16 = 1;
 
-    for c in s.chars() {
-        sum += if c == '(' {
-            1
-        } else if c == ')' {
-            -1
-        } else {
-            return None;
-        };
+    for (counter, step) in parse_moves(s)?.iter().enumerate() {
+        sum += step;
 
         if sum < 0 {
-            return Some(counter);
-        } else {
-            counter += 1;
+            return Ok(counter as i16 + 1);
         }
     }
 
-    return None;
+    Err(ParseError::input("Santa never enters the basement"))
+}
+
+fn parse_moves(s: &str) -> Result<Vec<i16>, ParseError> {
+    s.char_indices()
+        .map(|(i, c)| match c {
+            '(' => Ok(1),
+            ')' => Ok(-1),
+            _ => Err(
+                ParseError::in_line(s, &s[i..i + c.len_utf8()], "expected a bracket").at_line(1),
+            ),
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -42,28 +38,38 @@ mod tests {
 
     #[test]
     fn test_count_brackets() {
-        assert_eq!(count_brackets("((("), 3);
-        assert_eq!(count_brackets(")))"), -3);
-        assert_eq!(count_brackets("()(())"), 0);
-        assert_eq!(count_brackets("((())"), 1);
-        assert_eq!(count_brackets("(((a)b)c"), 1);
+        assert_eq!(count_brackets("((("), Ok(3));
+        assert_eq!(count_brackets(")))"), Ok(-3));
+        assert_eq!(count_brackets("()(())"), Ok(0));
+        assert_eq!(count_brackets("((())"), Ok(1));
+    }
+
+    #[test]
+    fn test_count_unexpected_char() {
+        assert_eq!(
+            count_brackets("(((a)b)c"),
+            Err(ParseError::new(1, 4, "a", "expected a bracket"))
+        );
     }
 
     #[test]
     fn test_count_position() {
-        assert_eq!(count_position(")"), Some(1));
-        assert_eq!(count_position("()())"), Some(5));
+        assert_eq!(count_position(")"), Ok(1));
+        assert_eq!(count_position("()())"), Ok(5));
     }
 
     #[test]
     fn test_count_unexpected_char_position() {
-        assert_eq!(count_position(")"), Some(1));
-        assert_eq!(count_position("()!!!())"), None);
+        assert_eq!(count_position(")"), Ok(1));
+        assert_eq!(
+            count_position("()!!!())"),
+            Err(ParseError::new(1, 3, "!", "expected a bracket"))
+        );
     }
 
     #[test]
     fn test_count_wrong_position() {
-        assert_eq!(count_position("("), None);
-        assert_eq!(count_position("()()("), None);
+        assert!(count_position("(").is_err());
+        assert!(count_position("()()(").is_err());
     }
 }
@@ -1,12 +1,31 @@
 const ITERATION_COUNT_FIRST: usize = 40;
 const ITERATION_COUNT_SECOND: usize = 50;
 
-pub fn calc_first(input: &str) -> usize {
-    calc_length(input, ITERATION_COUNT_FIRST)
+use advparse::ParseError;
+
+pub fn calc_first(input: &str) -> Result<usize, ParseError> {
+    Ok(calc_length(parse_digits(input)?, ITERATION_COUNT_FIRST))
+}
+
+pub fn calc_second(input: &str) -> Result<usize, ParseError> {
+    Ok(calc_length(parse_digits(input)?, ITERATION_COUNT_SECOND))
 }
 
-pub fn calc_second(input: &str) -> usize {
-    calc_length(input, ITERATION_COUNT_SECOND)
+fn parse_digits(input: &str) -> Result<&str, ParseError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseError::input("the sequence is empty"));
+    }
+
+    match input.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((i, c)) => {
+            Err(
+                ParseError::in_line(input, &input[i..i + c.len_utf8()], "expected a digit")
+                    .at_line(1),
+            )
+        }
+        None => Ok(input),
+    }
 }
 
 fn calc_length(input: &str, iterations: usize) -> usize {
@@ -60,6 +79,16 @@ mod tests {
         assert_eq!(convert_to_look(&"3113322113".to_string()), "132123222113");
     }
 
+    #[test]
+    fn test_parse_digits() {
+        assert_eq!(parse_digits("3113322113\n"), Ok("3113322113"));
+        assert_eq!(
+            parse_digits("31a3"),
+            Err(ParseError::new(1, 3, "a", "expected a digit"))
+        );
+        assert!(parse_digits("").is_err());
+    }
+
     #[test]
     fn test_calc_length() {
         assert_eq!(calc_length(&"1".to_string(), 1), 2, "Length of 11 is 2");
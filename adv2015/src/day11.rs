@@ -1,14 +1,33 @@
+use advparse::ParseError;
 use std::collections::HashSet;
 
 const MAX_LENGTH: usize = 8;
 
-pub fn get_expired_once(input: &str) -> String {
-    find_next(input)
+pub fn get_expired_once(input: &str) -> Result<String, ParseError> {
+    Ok(find_next(parse_password(input)?))
 }
 
-pub fn get_expired_twice(input: &str) -> String {
-    let expired = find_next(input);
-    find_next(expired.as_str())
+pub fn get_expired_twice(input: &str) -> Result<String, ParseError> {
+    let expired = find_next(parse_password(input)?);
+    Ok(find_next(expired.as_str()))
+}
+
+fn parse_password(input: &str) -> Result<&str, ParseError> {
+    let input = input.trim();
+
+    if let Some((i, c)) = input.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        let token = &input[i..i + c.len_utf8()];
+        return Err(ParseError::in_line(input, token, "expected a lowercase letter").at_line(1));
+    }
+
+    if input.len() != MAX_LENGTH {
+        return Err(ParseError::input(format!(
+            "the password must have {} letters",
+            MAX_LENGTH
+        )));
+    }
+
+    Ok(input)
 }
 
 struct Password {
@@ -268,6 +287,16 @@ mod tests {
         assert_eq!(new_pass, "abcdffaa", "I expected another password");
     }
 
+    #[test]
+    fn test_parse_password() {
+        assert_eq!(parse_password("abcdefgh\n"), Ok("abcdefgh"));
+        assert_eq!(
+            parse_password("abcDefgh"),
+            Err(ParseError::new(1, 4, "D", "expected a lowercase letter"))
+        );
+        assert!(parse_password("abc").is_err());
+    }
+
     #[test]
     #[ignore]
     fn test_find_next_slow() {
@@ -1,14 +1,46 @@
-pub fn get_answer(input: &str) -> i32 {
+use advparse::ParseError;
+
+pub fn get_answer(input: &str) -> Result<i32, ParseError> {
+    check_brackets(input)?;
     let vec = extract_numbers(input);
-    let answer = sum_numbers(&vec);
 
-    answer
+    Ok(sum_numbers(&vec))
 }
 
-pub fn get_answer_without_red(input: &str) -> i32 {
-    let answer = scan_deep(input);
+pub fn get_answer_without_red(input: &str) -> Result<i32, ParseError> {
+    check_brackets(input)?;
 
-    answer
+    Ok(scan_deep(input))
+}
+
+// The scan relies on the balanced brackets, so a truncated document is an error
+fn check_brackets(input: &str) -> Result<(), ParseError> {
+    let mut stack = Vec::new();
+
+    for (line_index, line) in input.lines().enumerate() {
+        for (i, c) in line.char_indices() {
+            let token = &line[i..i + c.len_utf8()];
+            match c {
+                '{' | '[' => stack.push(c),
+                '}' | ']' => {
+                    let expected = if c == '}' { '{' } else { '[' };
+                    if stack.pop() != Some(expected) {
+                        return Err(ParseError::in_line(line, token, "unbalanced bracket")
+                            .at_line(line_index + 1));
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    match stack.last() {
+        Some(c) => Err(ParseError::input(format!(
+            "the document ends before {} is closed",
+            c
+        ))),
+        None => Ok(()),
+    }
 }
 
 fn extract_numbers(input: &str) -> Vec<i32> {
@@ -17,9 +49,7 @@ fn extract_numbers(input: &str) -> Vec<i32> {
             c == '[' || c == ']' || c == ',' || c == '"' || c == ':' || c == '{' || c == '}'
         })
         .filter(|s| !s.is_empty())
-        .map(|s| s.parse::<i32>())
-        .filter(|p| p.is_ok())
-        .map(|r| r.unwrap())
+        .filter_map(|s| s.parse::<i32>().ok())
         .collect()
 }
 
@@ -60,7 +90,7 @@ fn scan_deep(input: &str) -> i32 {
         return if input.contains(r#":"red""#) {
             0
         } else {
-            get_answer(input)
+            sum_numbers(&extract_numbers(input))
         };
     }
 
@@ -170,6 +200,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_check_brackets() {
+        assert_eq!(check_brackets(r#"[1,{"c":"red","b":2},3]"#), Ok(()));
+        assert_eq!(
+            check_brackets(r#"[1,{"c":"red","b":2],3]"#),
+            Err(ParseError::new(1, 20, "]", "unbalanced bracket"))
+        );
+        assert!(check_brackets(r#"{"a":[1,2"#).is_err());
+        assert!(get_answer(r#"[1,2,3"#).is_err());
+    }
+
     #[test]
     fn test_scan_deep() {
         assert_eq!(scan_deep(r#"[1,{"c":"red",{"a":1},"b":2},3]"#), 4);
@@ -1,12 +1,13 @@
+use advparse::{expect_token, next_token, parse_lines, parse_number, ParseError};
 use permute::permutations_of;
 use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 
-pub fn get_answer(input: &str) -> i32 {
+pub fn get_answer(input: &str) -> Result<i32, ParseError> {
     calculate_everyone(input)
 }
 
-pub fn get_answer_with_me(input: &str) -> i32 {
+pub fn get_answer_with_me(input: &str) -> Result<i32, ParseError> {
     calculate_everyone_and_me(input)
 }
 
@@ -16,33 +17,60 @@ struct PersonToPerson {
     attitude: i32,
 }
 
-fn parse_line(input: &str) -> PersonToPerson {
+// Alice would gain 54 happiness units by sitting next to Bob.
+fn parse_line(input: &str) -> Result<PersonToPerson, ParseError> {
     let mut split = input.split_whitespace();
-    let who = split.next().unwrap(); // name
-    split.next(); // would
-    let verb = split.next().unwrap(); // gain/lose
-    let attitude =
-        split.next().unwrap().parse::<i32>().unwrap() * if verb == "lose" { -1 } else { 1 }; // amount * verb
-    split.next(); // happiness
-    split.next(); // units
-    split.next(); // by
-    split.next(); // sitting
-    split.next(); // next
-    split.next(); // to
-    let mut next = String::from(split.next().unwrap()); // name, with . at the end.
-    next.pop();
-
-    PersonToPerson {
+    let who = next_token(input, &mut split, "a name")?;
+    expect_token(input, &mut split, "would")?;
+    let sign = match next_token(input, &mut split, "\"gain\" or \"lose\"")? {
+        "gain" => 1,
+        "lose" => -1,
+        token => {
+            return Err(ParseError::in_line(
+                input,
+                token,
+                "expected \"gain\" or \"lose\"",
+            ))
+        }
+    };
+    let attitude = parse_number::<i32>(input, next_token(input, &mut split, "an amount")?)? * sign;
+    for word in ["happiness", "units", "by", "sitting", "next", "to"] {
+        expect_token(input, &mut split, word)?;
+    }
+    let next = next_token(input, &mut split, "a name")?;
+
+    Ok(PersonToPerson {
         who: String::from(who),
-        next,
+        next: String::from(next.trim_end_matches('.')),
         attitude,
-    }
+    })
 }
 
-fn parse_input(input: &str) -> Vec<PersonToPerson> {
-    let vec: Vec<PersonToPerson> = input.lines().map(|line| parse_line(line)).collect();
+fn parse_input(input: &str) -> Result<Vec<PersonToPerson>, ParseError> {
+    parse_lines(input, parse_line)
+}
 
-    vec
+// Every couple must be described both ways, otherwise the table can't be scored
+fn check_relationships(
+    names: &HashSet<String>,
+    relationship: &HashMap<(String, String), i32>,
+) -> Result<(), ParseError> {
+    if names.is_empty() {
+        return Err(ParseError::input("there is nobody at the table"));
+    }
+
+    for who in names {
+        for next in names.iter().filter(|next| *next != who) {
+            if !relationship.contains_key(&(who.clone(), next.clone())) {
+                return Err(ParseError::input(format!(
+                    "the attitude of {} to {} is missing",
+                    who, next
+                )));
+            }
+        }
+    }
+
+    Ok(())
 }
 
 fn build_relationships(input: &Vec<PersonToPerson>) -> HashMap<(String, String), i32> {
@@ -60,6 +88,7 @@ fn extract_names(input: &Vec<PersonToPerson>) -> HashSet<String> {
 
     input.iter().for_each(|person| {
         hash_set.insert(person.who.clone());
+        hash_set.insert(person.next.clone());
     });
 
     hash_set
@@ -67,7 +96,7 @@ fn extract_names(input: &Vec<PersonToPerson>) -> HashSet<String> {
 
 fn calculate_happiness(people: &Vec<String>, relationship: &HashMap<(String, String), i32>) -> i32 {
     let mut result = 0i32;
-    let mut previous = String::from(people.last().unwrap());
+    let mut previous = people.last().cloned().unwrap_or_default();
 
     for current in people.iter() {
         result += relationship[&(previous.clone(), current.clone())];
@@ -83,7 +112,7 @@ fn calculate_happiness_with_me(
     relationship: &HashMap<(String, String), i32>,
 ) -> i32 {
     let mut result = 0i32;
-    let mut previous = String::from(people.last().unwrap());
+    let mut previous = people.last().cloned().unwrap_or_default();
 
     for current in people.iter() {
         if current.eq("Me") || previous.eq("Me") {
@@ -99,13 +128,12 @@ fn calculate_happiness_with_me(
     result
 }
 
-fn calculate_everyone(input: &str) -> i32 {
-    let people = parse_input(input);
+fn calculate_everyone(input: &str) -> Result<i32, ParseError> {
+    let people = parse_input(input)?;
     let relationship = build_relationships(&people);
-    let table: Vec<String> = extract_names(&people)
-        .iter()
-        .map(|n| (*n).clone())
-        .collect();
+    let names = extract_names(&people);
+    check_relationships(&names, &relationship)?;
+    let table: Vec<String> = names.iter().map(|n| (*n).clone()).collect();
 
     let mut result = 0i32;
 
@@ -116,16 +144,15 @@ fn calculate_everyone(input: &str) -> i32 {
         result = max(result, current_happiness);
     }
 
-    result
+    Ok(result)
 }
 
-fn calculate_everyone_and_me(input: &str) -> i32 {
-    let people = parse_input(input);
+fn calculate_everyone_and_me(input: &str) -> Result<i32, ParseError> {
+    let people = parse_input(input)?;
     let relationship = build_relationships(&people);
-    let mut table: Vec<String> = extract_names(&people)
-        .iter()
-        .map(|n| (*n).clone())
-        .collect();
+    let names = extract_names(&people);
+    check_relationships(&names, &relationship)?;
+    let mut table: Vec<String> = names.iter().map(|n| (*n).clone()).collect();
     table.push("Me".to_string());
 
     let mut result = 0i32;
@@ -137,7 +164,7 @@ fn calculate_everyone_and_me(input: &str) -> i32 {
         result = max(result, current_happiness);
     }
 
-    result
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -159,7 +186,8 @@ mod tests {
 
     #[test]
     fn test_parse_line_1() {
-        let result = parse_line("Alice would gain 54 happiness units by sitting next to Bob.");
+        let result =
+            parse_line("Alice would gain 54 happiness units by sitting next to Bob.").unwrap();
         assert_eq!(result.who, "Alice");
         assert_eq!(result.next, "Bob");
         assert_eq!(result.attitude, 54);
@@ -167,7 +195,8 @@ mod tests {
 
     #[test]
     fn test_parse_line_2() {
-        let result = parse_line("Alice would lose 79 happiness units by sitting next to Carol.");
+        let result =
+            parse_line("Alice would lose 79 happiness units by sitting next to Carol.").unwrap();
         assert_eq!(result.who, "Alice");
         assert_eq!(result.next, "Carol");
         assert_eq!(result.attitude, -79);
@@ -175,7 +204,7 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let result = parse_input(INPUT);
+        let result = parse_input(INPUT).unwrap();
         let single_line = result.get(10).unwrap();
 
         assert_eq!(result.len(), 12);
@@ -186,7 +215,7 @@ mod tests {
 
     #[test]
     fn test_build_relationships() {
-        let result = parse_input(INPUT);
+        let result = parse_input(INPUT).unwrap();
         let hash_map = build_relationships(&result);
 
         let key = (String::from("David"), String::from("Bob")); // David -> Bob
@@ -197,7 +226,7 @@ mod tests {
 
     #[test]
     fn test_extract_names() {
-        let result = parse_input(INPUT);
+        let result = parse_input(INPUT).unwrap();
         let people = extract_names(&result);
 
         assert_eq!(people.len(), 4);
@@ -209,7 +238,7 @@ mod tests {
 
     #[test]
     fn test_calculate_happiness() {
-        let people = parse_input(INPUT);
+        let people = parse_input(INPUT).unwrap();
         let hash_map = build_relationships(&people);
         let table: Vec<String> = vec![
             "Alice".to_string(),
@@ -225,6 +254,32 @@ mod tests {
     #[test]
     fn test_calculate_everyone() {
         let result = calculate_everyone(INPUT);
-        assert_eq!(result, 330);
+        assert_eq!(result, Ok(330));
+    }
+
+    #[test]
+    fn test_wrong_input() {
+        assert_eq!(
+            parse_line("Alice would win 54 happiness units by sitting next to Bob.").err(),
+            Some(ParseError::new(
+                0,
+                13,
+                "win",
+                "expected \"gain\" or \"lose\""
+            ))
+        );
+        assert!(parse_line("Alice would gain 54 happiness").is_err());
+        assert!(
+            calculate_everyone("Alice would gain 54 happiness units by sitting next to Bob.")
+                .is_err()
+        );
+        assert_eq!(
+            calculate_everyone(""),
+            Err(ParseError::input("there is nobody at the table"))
+        );
+        assert_eq!(
+            calculate_everyone_and_me("\n"),
+            Err(ParseError::input("there is nobody at the table"))
+        );
     }
 }
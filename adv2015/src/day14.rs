@@ -1,13 +1,16 @@
+use advcore::{Param, Params};
+use advparse::{expect_token, next_token, parse_lines, parse_number, ParseError};
 use std::cmp::min;
 
-const TOTAL_TIME: usize = 2503;
+// The example of the puzzle lasts 1000 seconds
+pub const TOTAL_TIME: Param = Param::new("time", "2503", "the seconds of the race");
 
-pub fn get_answer(input: &str) -> usize {
-    find_fastest_deer(input, TOTAL_TIME)
+pub fn get_answer(input: &str, params: &Params) -> Result<usize, ParseError> {
+    find_fastest_deer(input, params.get(&TOTAL_TIME)?)
 }
 
-pub fn get_answer_points(input: &str) -> usize {
-    deer_race(input, TOTAL_TIME)
+pub fn get_answer_points(input: &str, params: &Params) -> Result<usize, ParseError> {
+    deer_race(input, params.get(&TOTAL_TIME)?)
 }
 
 struct RangiferTarandus {
@@ -23,23 +26,41 @@ struct RangiferTarandus {
     points: usize,
 }
 
-fn parse_line(input: &str) -> RangiferTarandus {
+// Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
+fn parse_line(input: &str) -> Result<RangiferTarandus, ParseError> {
     let mut split = input.split_whitespace();
-    let name = String::from(split.next().unwrap());
-    let numbers: Vec<usize> = split.filter_map(|s| s.parse().ok()).collect();
+    let name = String::from(next_token(input, &mut split, "a name")?);
+    expect_token(input, &mut split, "can")?;
+    expect_token(input, &mut split, "fly")?;
+    let speed = parse_number(input, next_token(input, &mut split, "a speed")?)?;
+    expect_token(input, &mut split, "km/s")?;
+    expect_token(input, &mut split, "for")?;
+    let endurance = parse_number(input, next_token(input, &mut split, "a duration")?)?;
+    for word in ["seconds,", "but", "then", "must", "rest", "for"] {
+        expect_token(input, &mut split, word)?;
+    }
+    let sleep = parse_number(input, next_token(input, &mut split, "a duration")?)?;
+
+    if endurance + sleep == 0 {
+        return Err(ParseError::in_line(
+            input,
+            input.trim(),
+            "the deer neither flies nor rests",
+        ));
+    }
 
-    RangiferTarandus {
+    Ok(RangiferTarandus {
         _name: name,
-        speed: numbers[0],
-        endurance: numbers[1],
-        sleep: numbers[2],
+        speed,
+        endurance,
+        sleep,
 
         is_running: true,
         running_time: 0,
         sleeping_time: 0,
         distance: 0,
         points: 0,
-    }
+    })
 }
 
 fn calculate_deer(deer: &RangiferTarandus, time: usize) -> usize {
@@ -53,17 +74,18 @@ fn calculate_deer(deer: &RangiferTarandus, time: usize) -> usize {
     distance1 + distance2
 }
 
-fn find_fastest_deer(input: &str, total: usize) -> usize {
-    input
-        .lines()
-        .map(|l| parse_line(l))
-        .map(|d| calculate_deer(&d, total))
+fn find_fastest_deer(input: &str, total: usize) -> Result<usize, ParseError> {
+    let deers = parse_lines(input, parse_line)?;
+
+    Ok(deers
+        .iter()
+        .map(|d| calculate_deer(d, total))
         .max()
-        .unwrap_or_default()
+        .unwrap_or_default())
 }
 
-fn deer_race(input: &str, duration: usize) -> usize {
-    let mut deers: Vec<RangiferTarandus> = input.lines().map(|l| parse_line(l)).collect();
+fn deer_race(input: &str, duration: usize) -> Result<usize, ParseError> {
+    let mut deers: Vec<RangiferTarandus> = parse_lines(input, parse_line)?;
 
     for _ in 0..duration + 1 {
         deers.iter_mut().for_each(|d| {
@@ -95,11 +117,11 @@ fn deer_race(input: &str, duration: usize) -> usize {
         }
     }
 
-    return if let Some(deer) = deers.iter().max_by_key(|d| d.points) {
+    return Ok(if let Some(deer) = deers.iter().max_by_key(|d| d.points) {
         deer.points
     } else {
         0
-    };
+    });
 }
 
 #[cfg(test)]
@@ -109,7 +131,7 @@ mod tests {
     #[test]
     fn test_parse_line() {
         let input = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.";
-        let result = parse_line(input);
+        let result = parse_line(input).unwrap();
 
         assert_eq!(result._name, "Comet");
         assert_eq!(result.speed, 14);
@@ -120,10 +142,12 @@ mod tests {
     #[test]
     fn test_calculate_deer() {
         let input1 =
-            parse_line("Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.");
+            parse_line("Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.")
+                .unwrap();
         let input2 = parse_line(
             "Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.",
-        );
+        )
+        .unwrap();
 
         let result1 = calculate_deer(&input1, 1000);
         let result2 = calculate_deer(&input2, 1000);
@@ -132,6 +156,18 @@ mod tests {
         assert_eq!(result2, 1056);
     }
 
+    #[test]
+    fn test_example_time() {
+        let input = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.\n\
+                     Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.";
+        let mut params = Params::new();
+        params.set("time", "1000");
+
+        assert_eq!(get_answer(input, &params), Ok(1120));
+        assert_eq!(get_answer_points(input, &params), Ok(689));
+        assert_eq!(get_answer(input, &Params::new()), Ok(2660));
+    }
+
     #[test]
     fn test_find_fastest_deer() {
         let input = r#"Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
@@ -139,7 +175,7 @@ mod tests {
 
         let result = find_fastest_deer(input, 1000);
 
-        assert_eq!(result, 1120);
+        assert_eq!(result, Ok(1120));
     }
 
     #[test]
@@ -149,6 +185,15 @@ mod tests {
 
         let result = deer_race(input, 1000);
 
-        assert_eq!(result, 689);
+        assert_eq!(result, Ok(689));
+    }
+
+    #[test]
+    fn test_wrong_line() {
+        assert_eq!(
+            parse_line("Comet can fly fast").err(),
+            Some(ParseError::new(0, 15, "fast", "expected a number"))
+        );
+        assert!(parse_line("Comet can fly 14 km/s for 10 seconds").is_err());
     }
 }
@@ -1,17 +1,22 @@
+use advcore::{Param, Params};
+use advparse::{expect_token, next_token, parse_lines, parse_number, ParseError};
 use std::cmp::max;
 
-pub fn get_answer(input: &str) -> usize {
-    let ingredients = parse_lines(input);
+pub const TOTAL_CALORIES: Param =
+    Param::new("calories", "500", "the calories of a cookie in part 2");
+
+pub fn get_answer(input: &str) -> Result<usize, ParseError> {
+    let ingredients = parse_ingredients(input)?;
     let spoons = find_spoons(&ingredients);
 
-    calc_spoons(&ingredients, &spoons)
+    Ok(calc_spoons(&ingredients, &spoons))
 }
 
-pub fn get_answer_with_calories(input: &str) -> usize {
-    let ingredients = parse_lines(input);
-    let spoons = find_spoons_with_calories(&ingredients);
+pub fn get_answer_with_calories(input: &str, params: &Params) -> Result<usize, ParseError> {
+    let ingredients = parse_ingredients(input)?;
+    let spoons = find_spoons_with_calories(&ingredients, params.get(&TOTAL_CALORIES)?);
 
-    calc_spoons(&ingredients, &spoons)
+    Ok(calc_spoons(&ingredients, &spoons))
 }
 
 struct Ingredient {
@@ -24,30 +29,44 @@ struct Ingredient {
     calories: i32,
 }
 
-fn parse_line(input: &str) -> Ingredient {
-    let everything: Vec<&str> = input
-        .split(|c: char| c == ':' || c == ',' || c.is_whitespace())
-        .filter(|s| !s.is_empty()) // remove empty results, we don't need them
-        .collect();
-    let name = everything[0];
-    let cap_str = everything[2];
-    let dur_str = everything[4];
-    let fla_str = everything[6];
-    let tex_str = everything[8];
-    let cal_str = everything[10];
-
-    Ingredient {
+// Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
+fn parse_line(input: &str) -> Result<Ingredient, ParseError> {
+    let mut split = input.split_whitespace();
+    let name = next_token(input, &mut split, "a name")?;
+    let name = match name.strip_suffix(':') {
+        Some(name) => name,
+        None => {
+            return Err(ParseError::in_line(
+                input,
+                name,
+                "expected a name with \":\"",
+            ))
+        }
+    };
+
+    let mut property = |property: &str| -> Result<i32, ParseError> {
+        expect_token(input, &mut split, property)?;
+        let value = next_token(input, &mut split, "a number")?;
+        parse_number(input, value.trim_end_matches(','))
+    };
+
+    Ok(Ingredient {
         name: String::from(name),
-        capacity: cap_str.parse::<i32>().unwrap(),
-        durability: dur_str.parse::<i32>().unwrap(),
-        flavor: fla_str.parse::<i32>().unwrap(),
-        texture: tex_str.parse::<i32>().unwrap(),
-        calories: cal_str.parse::<i32>().unwrap(),
-    }
+        capacity: property("capacity")?,
+        durability: property("durability")?,
+        flavor: property("flavor")?,
+        texture: property("texture")?,
+        calories: property("calories")?,
+    })
 }
 
-fn parse_lines(input: &str) -> Vec<Ingredient> {
-    input.lines().map(|line| parse_line(line)).collect()
+fn parse_ingredients(input: &str) -> Result<Vec<Ingredient>, ParseError> {
+    let ingredients = parse_lines(input, parse_line)?;
+    if ingredients.is_empty() {
+        return Err(ParseError::input("there are no ingredients"));
+    }
+
+    Ok(ingredients)
 }
 
 fn calc_spoons(ingredients: &Vec<Ingredient>, spoons: &Vec<i32>) -> usize {
@@ -112,9 +131,8 @@ fn find_spoons(ingredients: &Vec<Ingredient>) -> Vec<i32> {
     result
 }
 
-fn find_spoons_with_calories(ingredients: &Vec<Ingredient>) -> Vec<i32> {
+fn find_spoons_with_calories(ingredients: &Vec<Ingredient>, total_calories: i32) -> Vec<i32> {
     let spoons_count: usize = ingredients.len();
-    const TOTAL_CALORIES: i32 = 500;
     let max_size: usize = 100usize.pow(spoons_count as u32 - 1) + 1;
     let mut spoons: Vec<i32> = vec![0; spoons_count];
     let mut result = spoons.clone();
@@ -145,7 +163,7 @@ fn find_spoons_with_calories(ingredients: &Vec<Ingredient>) -> Vec<i32> {
             .enumerate()
             .map(|(i, s)| ingredients[i].calories * s)
             .sum();
-        if calories == TOTAL_CALORIES {
+        if calories == total_calories {
             let score = calc_spoons(&ingredients, &spoons);
 
             if score > max_result {
@@ -165,7 +183,8 @@ mod tests {
     #[test]
     fn test_parse_line() {
         let result =
-            parse_line("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8");
+            parse_line("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8")
+                .unwrap();
 
         assert_eq!(result.name, String::from("Butterscotch"));
         assert_eq!(result.capacity, -1);
@@ -177,14 +196,14 @@ mod tests {
 
     #[test]
     fn test_parse_lines() {
-        let result = parse_lines("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3");
+        let result = parse_ingredients("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3").unwrap();
 
         assert_eq!(result.len(), 2);
     }
 
     #[test]
     fn test_calc_spoons() {
-        let ingredients = parse_lines("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3");
+        let ingredients = parse_ingredients("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3").unwrap();
         let spoons = vec![44, 56];
         let result = calc_spoons(&ingredients, &spoons);
 
@@ -193,7 +212,7 @@ mod tests {
 
     #[test]
     fn test_find_spoons() {
-        let ingredients = parse_lines("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3");
+        let ingredients = parse_ingredients("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3").unwrap();
         let spoons = find_spoons(&ingredients);
 
         assert_eq!(spoons.len(), 2);
@@ -204,13 +223,13 @@ mod tests {
     #[test]
     fn test_get_answer() {
         let result = get_answer("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3");
-        assert_eq!(result, 62842880);
+        assert_eq!(result, Ok(62842880));
     }
 
     #[test]
     fn test_find_spoons_with_calories() {
-        let ingredients = parse_lines("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3");
-        let spoons = find_spoons_with_calories(&ingredients);
+        let ingredients = parse_ingredients("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3").unwrap();
+        let spoons = find_spoons_with_calories(&ingredients, 500);
 
         assert_eq!(spoons.len(), 2);
         assert_eq!(spoons[0], 40);
@@ -219,7 +238,25 @@ mod tests {
 
     #[test]
     fn test_get_answer_with_calories() {
-        let result = get_answer_with_calories("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3");
-        assert_eq!(result, 57600000);
+        let result = get_answer_with_calories("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3", &Params::new());
+        assert_eq!(result, Ok(57600000));
+
+        let mut params = Params::new();
+        params.set("calories", "five hundred");
+        assert!(get_answer_with_calories(
+            "Sugar: capacity 1, durability 1, flavor 1, texture 1, calories 5",
+            &params
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_wrong_input() {
+        assert_eq!(
+            parse_line("Butterscotch: capacity -1, durability two").err(),
+            Some(ParseError::new(0, 39, "two", "expected a number"))
+        );
+        assert!(parse_line("Butterscotch capacity -1").is_err());
+        assert!(get_answer("").is_err());
     }
 }
@@ -1,45 +1,24 @@
+use advparse::{expect_token, next_token, parse_lines, parse_number, ParseError};
 use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
 
 const IDEAL_AUNT: &str = r#"Sue 0: children: 3, cats: 7, samoyeds: 2, pomeranians: 3, akitas: 0, vizslas: 0, goldfish: 5, trees: 3, cars: 2, perfumes: 1"#;
 
-pub fn get_answer(input: &str) -> usize {
-    let aunts = parse_aunts(input);
+pub fn get_answer(input: &str) -> Result<usize, ParseError> {
+    let aunts = parse_aunts(input)?;
     let ideal_aunt = Aunt::from_str(IDEAL_AUNT).unwrap();
 
-    find_ideal_aunt(&aunts, &ideal_aunt).unwrap_or(0)
+    find_ideal_aunt(&aunts, &ideal_aunt)
+        .ok_or_else(|| ParseError::input("no aunt matches the sample"))
 }
 
-pub fn get_answer_from_retroencabulator(input: &str) -> usize {
-    let aunts = parse_aunts(input);
+pub fn get_answer_from_retroencabulator(input: &str) -> Result<usize, ParseError> {
+    let aunts = parse_aunts(input)?;
     let ideal_aunt = Aunt::from_str(IDEAL_AUNT).unwrap();
 
-    find_ideal_retro_aunt(&aunts, &ideal_aunt).unwrap_or(0)
-}
-
-#[derive(Debug)]
-struct ParseThingsError(String);
-
-impl fmt::Display for ParseThingsError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_fmt(format_args!(
-            "ParseThingsError: couldn't parse '{}' to Things",
-            self.0
-        ))
-    }
-}
-
-#[derive(Debug)]
-struct ParseAuntError(String);
-
-impl fmt::Display for ParseAuntError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_fmt(format_args!(
-            "ParseAuntError: couldn't parse '{}' to Aunt",
-            self.0
-        ))
-    }
+    find_ideal_retro_aunt(&aunts, &ideal_aunt)
+        .ok_or_else(|| ParseError::input("no aunt matches the sample"))
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -56,23 +35,20 @@ enum Things {
     Perfumes,
 }
 
-impl FromStr for Things {
-    type Err = ParseThingsError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "children" => Ok(Things::Children),
-            "cats" => Ok(Things::Cats),
-            "samoyeds" => Ok(Things::Samoyeds),
-            "pomeranians" => Ok(Things::Pomeranians),
-            "akitas" => Ok(Things::Akitas),
-            "vizslas" => Ok(Things::Vizslas),
-            "goldfish" => Ok(Things::Goldfish),
-            "trees" => Ok(Things::Trees),
-            "cars" => Ok(Things::Cars),
-            "perfumes" => Ok(Things::Perfumes),
-            _ => Err(ParseThingsError(String::from(s))),
-        }
+// The token is a part of the line, so the error points to it
+fn parse_thing(line: &str, token: &str) -> Result<Things, ParseError> {
+    match token {
+        "children" => Ok(Things::Children),
+        "cats" => Ok(Things::Cats),
+        "samoyeds" => Ok(Things::Samoyeds),
+        "pomeranians" => Ok(Things::Pomeranians),
+        "akitas" => Ok(Things::Akitas),
+        "vizslas" => Ok(Things::Vizslas),
+        "goldfish" => Ok(Things::Goldfish),
+        "trees" => Ok(Things::Trees),
+        "cars" => Ok(Things::Cars),
+        "perfumes" => Ok(Things::Perfumes),
+        _ => Err(ParseError::in_line(line, token, "unknown thing")),
     }
 }
 
@@ -97,60 +73,37 @@ impl fmt::Debug for Aunt {
     }
 }
 
+// Sue 30: vizslas: 3, perfumes: 8, akitas: 2
 impl FromStr for Aunt {
-    type Err = ParseAuntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut tokens = s
-            .split(|c: char| c.is_whitespace() || c == ':' || c == ',')
-            .filter(|token| *token != "Sue" && !token.is_empty())
-            .into_iter();
-
-        if let Some(num_str) = tokens.next() {
-            if let Ok(number) = num_str.parse::<usize>() {
-                let mut aunt = Aunt {
-                    number,
-                    things: HashMap::new(),
-                };
-
-                let mut parse_aunt = || -> Result<(), ParseThingsError> {
-                    while let Some(thing_str) = tokens.next() {
-                        let thing = Things::from_str(thing_str)?;
-
-                        if let Some(count_str) = tokens.next() {
-                            if let Ok(count) = count_str.parse::<usize>() {
-                                aunt.things.insert(thing, count);
-                            } else {
-                                let mut err_str = String::from(thing_str);
-                                err_str.push_str(": ");
-                                err_str.push_str(thing_str);
-
-                                return Err(ParseThingsError(err_str));
-                            }
-                        }
-                    }
-                    Ok(())
-                };
-
-                if let Err(e) = parse_aunt() {
-                    eprintln!("{}", e);
-                }
-
-                return Ok(aunt);
-            }
+        let mut tokens = s.split_whitespace();
+        expect_token(s, &mut tokens, "Sue")?;
+
+        let number = next_token(s, &mut tokens, "a number")?;
+        let mut aunt = Aunt {
+            number: parse_number(s, number.trim_end_matches(':'))?,
+            things: HashMap::new(),
+        };
+
+        while let Some(thing) = tokens.next() {
+            let thing = match thing.strip_suffix(':') {
+                Some(thing) => parse_thing(s, thing)?,
+                None => return Err(ParseError::in_line(s, thing, "expected a thing with \":\"")),
+            };
+            let count = next_token(s, &mut tokens, "a count")?;
+
+            aunt.things
+                .insert(thing, parse_number(s, count.trim_end_matches(','))?);
         }
 
-        Err(ParseAuntError(String::from(s)))
+        Ok(aunt)
     }
 }
 
-fn parse_aunts(input: &str) -> Vec<Aunt> {
-    input
-        .lines()
-        .map(|l| Aunt::from_str(l))
-        .filter(|a| a.is_ok())
-        .map(|a| a.unwrap())
-        .collect()
+fn parse_aunts(input: &str) -> Result<Vec<Aunt>, ParseError> {
+    parse_lines(input, Aunt::from_str)
 }
 
 fn find_ideal_aunt(input_aunts: &Vec<Aunt>, aunt_sample: &Aunt) -> Option<usize> {
@@ -220,49 +173,40 @@ mod tests {
 
     #[test]
     fn test_aunt_from_str_error_token() {
-        let aunt = Aunt::from_str("Sue 32: perfumes: 8, children: -3, akitas: 2").unwrap();
-        assert_eq!(aunt.number, 32);
-        assert_eq!(aunt.things.len(), 1);
-        assert_eq!(aunt.get_thing(&Things::Children), None);
+        let err = Aunt::from_str("Sue 32: perfumes: 8, children: -3, akitas: 2").unwrap_err();
+        assert_eq!(err, ParseError::new(0, 32, "-3", "expected a number"));
     }
 
     #[test]
     fn test_aunt_from_str_error_aunt() {
-        let err = Aunt::from_str("Marry 32: children: 13, perfumes: 8, akitas: 2");
-        let aunt_error = err.unwrap_err();
-        assert_eq!(
-            format!("{:?}", aunt_error),
-            r#"ParseAuntError("Marry 32: children: 13, perfumes: 8, akitas: 2")"#
-        );
+        let err = Aunt::from_str("Marry 32: children: 13, perfumes: 8, akitas: 2").unwrap_err();
+        assert_eq!(err, ParseError::new(0, 1, "Marry", "expected \"Sue\""));
         assert_eq!(
-            format!("{}", aunt_error),
-            r#"ParseAuntError: couldn't parse 'Marry 32: children: 13, perfumes: 8, akitas: 2' to Aunt"#
+            format!("{}", err),
+            r#"line 0, column 1: expected "Sue", found "Marry""#
         );
     }
 
     #[test]
-    fn test_things_from_str() {
-        assert_eq!(Things::from_str("children").unwrap(), Things::Children);
-        assert_eq!(Things::from_str("cats").unwrap(), Things::Cats);
-        assert_eq!(Things::from_str("samoyeds").unwrap(), Things::Samoyeds);
+    fn test_parse_thing() {
+        assert_eq!(parse_thing("children", "children"), Ok(Things::Children));
+        assert_eq!(parse_thing("cats", "cats"), Ok(Things::Cats));
+        assert_eq!(parse_thing("samoyeds", "samoyeds"), Ok(Things::Samoyeds));
         assert_eq!(
-            Things::from_str("pomeranians").unwrap(),
-            Things::Pomeranians
+            parse_thing("pomeranians", "pomeranians"),
+            Ok(Things::Pomeranians)
         );
-        assert_eq!(Things::from_str("akitas").unwrap(), Things::Akitas);
-        assert_eq!(Things::from_str("vizslas").unwrap(), Things::Vizslas);
-        assert_eq!(Things::from_str("goldfish").unwrap(), Things::Goldfish);
-        assert_eq!(Things::from_str("trees").unwrap(), Things::Trees);
-        assert_eq!(Things::from_str("cars").unwrap(), Things::Cars);
-        assert_eq!(Things::from_str("perfumes").unwrap(), Things::Perfumes);
-
-        let things_result = Things::from_str("Vest");
-        assert!(things_result.is_err());
-        let things_error = things_result.unwrap_err();
-        assert_eq!(format!("{:?}", things_error), r#"ParseThingsError("Vest")"#);
+        assert_eq!(parse_thing("akitas", "akitas"), Ok(Things::Akitas));
+        assert_eq!(parse_thing("vizslas", "vizslas"), Ok(Things::Vizslas));
+        assert_eq!(parse_thing("goldfish", "goldfish"), Ok(Things::Goldfish));
+        assert_eq!(parse_thing("trees", "trees"), Ok(Things::Trees));
+        assert_eq!(parse_thing("cars", "cars"), Ok(Things::Cars));
+        assert_eq!(parse_thing("perfumes", "perfumes"), Ok(Things::Perfumes));
+
+        let line = "Sue 1: Vest: 3";
         assert_eq!(
-            format!("{}", things_error),
-            r#"ParseThingsError: couldn't parse 'Vest' to Things"#
+            parse_thing(line, &line[7..11]),
+            Err(ParseError::new(0, 8, "Vest", "unknown thing"))
         );
     }
 
@@ -273,12 +217,14 @@ mod tests {
         Sue 2: akitas: 9, children: 3, samoyeds: 9
         Sue 3: trees: 6, cars: 6, children: 4
         Sue 4: trees: 4, vizslas: 4, goldfish: 9
-        Vest
         Sue 5: akitas: 9, vizslas: 7, cars: 5
         Sue 6: vizslas: 6, goldfish: 6, akitas: 3"#,
         );
 
-        assert_eq!(aunts.len(), 6);
+        assert_eq!(aunts.map(|aunts| aunts.len()), Ok(6));
+
+        let err = parse_aunts("Sue 1: cars: 9\nVest").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 1, "Vest", "expected \"Sue\""));
     }
 
     #[test]
@@ -292,7 +238,8 @@ mod tests {
         let aunts = parse_aunts(
             r#"Sue 1: cars: 9, akitas: 3, goldfish: 0
         Sue 2: children: 3, cats: 7, pomeranians: 3"#,
-        );
+        )
+        .unwrap();
         let ideal_aunt = Aunt::from_str(IDEAL_AUNT).unwrap();
         let aunt = find_ideal_aunt(&aunts, &ideal_aunt);
         assert!(aunt.is_some());
@@ -304,7 +251,8 @@ mod tests {
         let aunts = parse_aunts(
             r#"Sue 1: cars: 9, akitas: 3, goldfish: 0
         Sue 2: children: 4, cats: 7, pomeranians: 3"#,
-        ); // this aunt doesn't exist
+        )
+        .unwrap(); // this aunt doesn't exist
         let ideal_aunt = Aunt::from_str(IDEAL_AUNT).unwrap();
         let aunt = find_ideal_aunt(&aunts, &ideal_aunt);
         assert!(aunt.is_none());
@@ -316,13 +264,13 @@ mod tests {
             r#"Sue 1: cars: 9, akitas: 3, goldfish: 0
         Sue 2: children: 3, cats: 7, pomeranians: 3"#,
         );
-        assert_eq!(answer, 2);
+        assert_eq!(answer, Ok(2));
     }
 
     #[test]
     fn test_get_empty_answer() {
         let answer = get_answer("");
-        assert_eq!(answer, 0);
+        assert!(answer.is_err());
     }
 
     #[test]
@@ -331,7 +279,7 @@ mod tests {
             r#"Sue 1: cars: 9, akitas: 3, goldfish: 0
         Sue 2: children: 4, cats: 7, pomeranians: 3"#,
         ); // this aunt doesn't exist
-        assert_eq!(answer, 0);
+        assert!(answer.is_err());
     }
 
     #[test]
@@ -340,7 +288,7 @@ mod tests {
             r#"Sue 1: cars: 9, akitas: 3, goldfish: 0
         Sue 2: children: 3, cats: 7, pomeranians: 3"#,
         );
-        assert_eq!(answer, 0);
+        assert!(answer.is_err());
     }
 
     #[test]
@@ -349,6 +297,6 @@ mod tests {
             r#"Sue 1: cats: 9, akitas: 0, goldfish: 4
         Sue 2: children: 4, cats: 7, pomeranians: 3"#,
         );
-        assert_eq!(answer, 1);
+        assert_eq!(answer, Ok(1));
     }
 }
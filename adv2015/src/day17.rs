@@ -1,29 +1,35 @@
+use advcore::{Param, Params};
+use advparse::{parse_numbers, ParseError};
 use combinations::Combinations;
 
-const REQUIRED_VOLUME: usize = 150;
+// The example of the puzzle has 25 litres
+pub const REQUIRED_VOLUME: Param = Param::new("volume", "150", "the litres of eggnog");
 
-pub fn get_total_count_of_combinations(input: &str) -> usize {
-    let available_cans = parse_to_available_cans(input);
-    let found_cans = find_cans_combination_total_count(&available_cans, REQUIRED_VOLUME);
+pub fn get_total_count_of_combinations(input: &str, params: &Params) -> Result<usize, ParseError> {
+    let available_cans = parse_to_available_cans(input)?;
 
-    found_cans
+    Ok(find_cans_combination_total_count(
+        &available_cans,
+        params.get(&REQUIRED_VOLUME)?,
+    ))
 }
 
-pub fn get_minimal_count_of_cans(input: &str) -> usize {
-    let available_cans = parse_to_available_cans(input);
-    let found_cans = find_cans_combination_minimal_count(&available_cans, REQUIRED_VOLUME);
+pub fn get_minimal_count_of_cans(input: &str, params: &Params) -> Result<usize, ParseError> {
+    let available_cans = parse_to_available_cans(input)?;
 
-    found_cans
+    Ok(find_cans_combination_minimal_count(
+        &available_cans,
+        params.get(&REQUIRED_VOLUME)?,
+    ))
 }
 
-fn parse_to_available_cans(input: &str) -> Vec<usize> {
-    input
-        .lines()
-        .map(|l| l.trim())
-        .map(|l| l.parse::<usize>())
-        .filter(|c| c.is_ok())
-        .map(|c| c.unwrap())
-        .collect()
+fn parse_to_available_cans(input: &str) -> Result<Vec<usize>, ParseError> {
+    let cans = parse_numbers(input)?;
+    if cans.is_empty() {
+        return Err(ParseError::input("there are no containers"));
+    }
+
+    Ok(cans)
 }
 
 fn find_cans_combination_total_count(vec: &Vec<usize>, required_volume: usize) -> usize {
@@ -117,9 +123,10 @@ mod tests {
             get_total_count_of_combinations(
                 r#"50
         50
-        100"#
+        100"#,
+                &Params::new()
             ),
-            2
+            Ok(2)
         );
     }
 
@@ -131,9 +138,10 @@ mod tests {
         140
         50
         50
-        50"#
+        50"#,
+                &Params::new()
             ),
-            1
+            Ok(1)
         );
     }
 
@@ -145,7 +153,8 @@ mod tests {
         10
         5
         5"#,
-        );
+        )
+        .unwrap();
 
         assert_eq!(cans.len(), 5);
 
@@ -175,23 +184,44 @@ mod tests {
         assert_eq!(cans2.0, 2usize);
         assert_eq!(cans2.1, 3usize);
 
-        let cans3 = find_k(&vec![50, 50, 100], REQUIRED_VOLUME);
+        let cans3 = find_k(&vec![50, 50, 100], 150);
         assert_eq!(cans3.0, 2usize);
         assert_eq!(cans3.1, 2usize);
     }
 
+    #[test]
+    fn test_example_volume() {
+        let mut params = Params::new();
+        params.set("volume", "25");
+
+        assert_eq!(
+            get_total_count_of_combinations("20\n15\n10\n5\n5", &params),
+            Ok(4)
+        );
+        assert_eq!(
+            get_minimal_count_of_cans("20\n15\n10\n5\n5", &params),
+            Ok(3)
+        );
+    }
+
     #[test]
     fn test_zeroes() {
-        assert_eq!(get_total_count_of_combinations(r#""#), 0);
-        assert_eq!(get_minimal_count_of_cans(r#""#), 0);
+        assert!(get_total_count_of_combinations(r#""#, &Params::new()).is_err());
+        assert!(get_minimal_count_of_cans(r#""#, &Params::new()).is_err());
     }
 
     #[test]
     fn test_impossible() {
-        assert_eq!(get_total_count_of_combinations(r#"5"#), 0);
-        assert_eq!(get_minimal_count_of_cans(r#"5"#), 0);
+        assert_eq!(
+            get_total_count_of_combinations(r#"5"#, &Params::new()),
+            Ok(0)
+        );
+        assert_eq!(get_minimal_count_of_cans(r#"5"#, &Params::new()), Ok(0));
 
-        assert_eq!(get_total_count_of_combinations(r#"5 155"#), 0);
-        assert_eq!(get_minimal_count_of_cans(r#"5 155"#), 0);
+        assert_eq!(
+            get_total_count_of_combinations(r#"5 155"#, &Params::new()),
+            Err(ParseError::new(1, 1, "5 155", "expected a number"))
+        );
+        assert!(get_minimal_count_of_cans("5\n155", &Params::new()).is_ok());
     }
 }
@@ -1,136 +1,171 @@
-const SIZE: usize = 100;
-const COUNT: usize = 100;
+use advcore::{Param, Params};
+use advgrid::{Automaton, Grid, Life, Neighbourhood, Neighbours};
+use advparse::ParseError;
+use advrender::{Animation, Frame, Scene};
+use std::iter::once;
 
-pub fn get_answer_normal(input: &str) -> usize {
-    let mut grid = Grid::new(SIZE);
+// The example of the puzzle is 6x6, with 4 steps (5 when the corners are stuck)
+pub const SIZE: Param = Param::new("size", "100", "the width and the height of the grid");
+pub const COUNT: Param = Param::new("steps", "100", "the steps of the animation");
 
-    grid.parse_grid(input);
+pub fn get_answer_normal(input: &str, params: &Params) -> Result<usize, ParseError> {
+    let mut grid = Lights::new(params.get(&SIZE)?);
 
-    for _ in 0..COUNT {
-        grid.evolve();
-    }
+    grid.parse_grid(input)?;
+    grid.evolve(params.get(&COUNT)?);
 
-    grid.count_lights()
+    Ok(grid.count_lights())
 }
 
-pub fn get_answer_broken(input: &str) -> usize {
-    let mut grid = Grid::new(SIZE);
+pub fn get_answer_broken(input: &str, params: &Params) -> Result<usize, ParseError> {
+    let mut grid = Lights::new(params.get(&SIZE)?);
 
-    grid.parse_grid(input);
+    grid.parse_grid(input)?;
     grid.break_circuit();
+    grid.evolve(params.get(&COUNT)?);
 
-    for _ in 0..COUNT {
-        grid.evolve();
-        grid.break_circuit();
-    }
+    Ok(grid.count_lights())
+}
+
+// Every step, with the stuck corners too
+pub fn render(input: &str, params: &Params) -> Result<Vec<Animation>, ParseError> {
+    let mut grid = Lights::new(params.get(&SIZE)?);
+    grid.parse_grid(input)?;
+    let steps = params.get(&COUNT)?;
+    let normal = grid.animate("lights", steps);
+
+    grid.break_circuit();
+    let broken = grid.animate("broken", steps);
 
-    grid.count_lights()
+    Ok(vec![normal, broken])
 }
 
-struct Grid {
+// The same steps in the terminal
+pub fn animate(input: &str, params: &Params) -> Result<Vec<Scene>, ParseError> {
+    let mut grid = Lights::new(params.get(&SIZE)?);
+    grid.parse_grid(input)?;
+    let steps = params.get(&COUNT)?;
+    let normal = grid.scene("lights", steps);
+
+    grid.break_circuit();
+    let broken = grid.scene("broken", steps);
+
+    Ok(vec![normal, broken])
+}
+
+// A light is a square of pixels
+const PIXEL_SIZE: usize = 4;
+
+struct Lights {
     size: usize,
-    grid: Vec<Vec<bool>>,
+    grid: Grid<bool>,
+    broken: bool,
 }
 
-impl Grid {
-    fn new(size: usize) -> Grid {
-        Grid {
+impl Lights {
+    fn new(size: usize) -> Lights {
+        Lights {
             size,
-            grid: vec![vec![false; size]; size],
-        }
-    }
-
-    fn is_on(&self, row: usize, col: usize) -> Option<&bool> {
-        if let Some(row_vec) = self.grid.get(row) {
-            row_vec.get(col)
-        } else {
-            None
+            grid: Grid::new(size, size, false),
+            broken: false,
         }
     }
 
-    fn neighbors_count(&self, row: usize, col: usize) -> usize {
-        let mut results: Vec<Option<&bool>> = Vec::with_capacity(8);
-
-        // 7-8-9
-        if let (Some(row7), Some(col7)) = (row.checked_sub(1), col.checked_sub(1)) {
-            results.push(self.is_on(row7, col7));
+    // The grid must be complete, a truncated one would give a wrong answer;
+    // an empty one has no corners to get stuck
+    fn parse_grid(&mut self, lines: &str) -> Result<(), ParseError> {
+        if self.size == 0 {
+            return Err(ParseError::input("the parameter size can't be 0"));
         }
 
-        if let (Some(row8), Some(col8)) = (row.checked_sub(1), Some(col)) {
-            results.push(self.is_on(row8, col8));
-        }
+        let grid: Grid<bool> = Grid::parse(lines)?;
 
-        if let (Some(row9), Some(col9)) = (row.checked_sub(1), col.checked_add(1)) {
-            results.push(self.is_on(row9, col9));
+        if grid.height() != self.size || grid.width() != self.size {
+            return Err(ParseError::input(format!(
+                "expected {}x{} lights, found {}x{}",
+                self.size,
+                self.size,
+                grid.width(),
+                grid.height()
+            )));
         }
 
-        // 4- -6
-        if let (Some(row4), Some(col4)) = (Some(row), col.checked_sub(1)) {
-            results.push(self.is_on(row4, col4));
-        }
+        self.grid = grid;
+        Ok(())
+    }
 
-        if let (Some(row6), Some(col6)) = (Some(row), col.checked_add(1)) {
-            results.push(self.is_on(row6, col6));
-        }
+    fn life(&self) -> Automaton<Grid<bool>, Life> {
+        let life = Automaton::new(
+            self.grid.clone(),
+            Neighbours::Adjacent(Neighbourhood::Moore),
+            Life::CONWAY,
+        );
 
-        // 1-2-3
-        if let (Some(row1), Some(col1)) = (row.checked_add(1), col.checked_sub(1)) {
-            results.push(self.is_on(row1, col1));
+        match self.broken {
+            true => life.with_pinned(stuck_corners),
+            false => life,
         }
+    }
 
-        if let (Some(row2), Some(col2)) = (row.checked_add(1), Some(col)) {
-            results.push(self.is_on(row2, col2));
-        }
+    fn evolve(&mut self, steps: usize) {
+        let mut life = self.life();
+        life.run(steps);
+        self.grid = life.state().clone();
+    }
 
-        if let (Some(row3), Some(col3)) = (row.checked_add(1), col.checked_add(1)) {
-            results.push(self.is_on(row3, col3));
+    // The grid doesn't change, every step is a frame
+    fn animate(&self, name: &str, steps: usize) -> Animation {
+        let frame = |grid: &Grid<bool>| {
+            Frame::from_grid(grid, |&light| if light { 255 } else { 0 }).scaled(PIXEL_SIZE)
+        };
+        let mut life = self.life();
+        let mut animation = Animation::new(name);
+
+        animation.push(frame(life.state()));
+        for _ in 0..steps {
+            life.step();
+            animation.push(frame(life.state()));
         }
 
-        results
-            .iter()
-            .filter(|&r| r.is_some())
-            .map(|r| r.unwrap())
-            .filter(|&r| *r)
-            .count()
+        animation
     }
 
-    fn parse_grid(&mut self, lines: &str) {
-        lines.lines().enumerate().for_each(|(row, line)| {
-            line.trim().chars().enumerate().for_each(|(col, light)| {
-                self.grid[row][col] = light == '#';
-            })
+    fn scene(&self, name: &str, steps: usize) -> Scene {
+        let draw = move |life: &Automaton<Grid<bool>, Life>| {
+            format!(
+                "step {}/{}, {} lights\n{}\n",
+                life.generation(),
+                steps,
+                life.population(),
+                life.state()
+            )
+        };
+        let mut life = self.life();
+
+        let first = draw(&life);
+        let rest = (0..steps).map(move |_| {
+            life.step();
+            draw(&life)
         });
-    }
-
-    fn evolve(&mut self) {
-        let mut new_grid = vec![vec![false; self.size]; self.size];
-
-        for row in 0..self.size {
-            for col in 0..self.size {
-                let count = self.neighbors_count(row, col);
 
-                new_grid[row][col] = match self.is_on(row, col).unwrap() {
-                    true => count == 2 || count == 3,
-                    false => count == 3,
-                }
-            }
-        }
-
-        self.grid = new_grid;
+        Scene::new(name, once(first).chain(rest))
     }
 
     fn count_lights(&self) -> usize {
-        self.grid
-            .iter()
-            .map(|line| line.iter().filter(|&light| *light).count())
-            .sum()
+        self.grid.count(|&light| light)
     }
 
+    // The corners stay on after every step
     fn break_circuit(&mut self) {
-        self.grid[0][0] = true;
-        self.grid[0][self.size - 1] = true;
-        self.grid[self.size - 1][0] = true;
-        self.grid[self.size - 1][self.size - 1] = true;
+        self.broken = true;
+        stuck_corners(&mut self.grid);
+    }
+}
+
+fn stuck_corners(grid: &mut Grid<bool>) {
+    let (right, bottom) = (grid.width() - 1, grid.height() - 1);
+    for corner in [(0, 0), (right, 0), (0, bottom), (right, bottom)] {
+        grid[corner] = true;
     }
 }
 
@@ -138,6 +173,13 @@ impl Grid {
 mod tests {
     use super::*;
 
+    fn neighbors_count(grid: &Lights, row: usize, col: usize) -> usize {
+        grid.grid
+            .neighbours((col, row), Neighbourhood::Moore)
+            .filter(|&&light| light)
+            .count()
+    }
+
     const EXAMPLE: &str = r#".#.#.#
                              ...##.
                              #....#
@@ -147,104 +189,168 @@ mod tests {
 
     #[test]
     fn test_build_grid() {
-        let grid = Grid::new(6);
+        let grid = Lights::new(6);
         assert_eq!(grid.size, 6);
 
         for row in 0..grid.size {
             for col in 0..grid.size {
-                assert!(!grid.is_on(row, col).unwrap());
+                assert!(!grid.grid[(col, row)]);
             }
         }
     }
 
     #[test]
     fn test_neighbors_count_zero() {
-        let grid = Grid::new(6);
+        let grid = Lights::new(6);
         for row in 0..grid.size {
             for col in 0..grid.size {
-                assert_eq!(grid.neighbors_count(row, col), 0);
+                assert_eq!(neighbors_count(&grid, row, col), 0);
             }
         }
     }
 
     #[test]
     fn test_neighbors_count_top_left() {
-        let mut grid = Grid::new(6);
-        grid.grid[0][1] = true;
-        grid.grid[1][0] = true;
-        grid.grid[1][1] = true;
-        assert_eq!(grid.neighbors_count(0, 0), 3);
+        let mut grid = Lights::new(6);
+        grid.grid[(1, 0)] = true;
+        grid.grid[(0, 1)] = true;
+        grid.grid[(1, 1)] = true;
+        assert_eq!(neighbors_count(&grid, 0, 0), 3);
     }
 
     #[test]
     fn test_neighbors_count_top_right() {
-        let mut grid = Grid::new(6);
-        grid.grid[0][4] = true;
-        grid.grid[1][4] = true;
-        grid.grid[1][5] = true;
-        assert_eq!(grid.neighbors_count(0, 5), 3);
+        let mut grid = Lights::new(6);
+        grid.grid[(4, 0)] = true;
+        grid.grid[(4, 1)] = true;
+        grid.grid[(5, 1)] = true;
+        assert_eq!(neighbors_count(&grid, 0, 5), 3);
     }
 
     #[test]
     fn test_neighbors_count_middle() {
-        let mut grid = Grid::new(6);
-        grid.grid[2][2] = true;
-        grid.grid[4][4] = true;
-        grid.grid[4][2] = true;
-        grid.grid[2][4] = true;
-        assert_eq!(grid.neighbors_count(3, 3), 4);
+        let mut grid = Lights::new(6);
+        grid.grid[(2, 2)] = true;
+        grid.grid[(4, 4)] = true;
+        grid.grid[(2, 4)] = true;
+        grid.grid[(4, 2)] = true;
+        assert_eq!(neighbors_count(&grid, 3, 3), 4);
     }
 
     #[test]
     fn test_parse_grid() {
-        let mut grid = Grid::new(6);
-        grid.parse_grid(EXAMPLE);
+        let mut grid = Lights::new(6);
+        grid.parse_grid(EXAMPLE).unwrap();
 
         assert_eq!(grid.count_lights(), 15);
     }
 
     #[test]
     fn test_parse_empty_grid() {
-        let mut grid = Grid::new(6);
-        grid.parse_grid("");
-
-        assert_eq!(grid.count_lights(), 0);
+        let mut grid = Lights::new(6);
+
+        assert!(grid.parse_grid("").is_err());
+        assert!(grid.parse_grid(&EXAMPLE[..20]).is_err());
+        assert_eq!(
+            grid.parse_grid(".#.#.#\n...#x."),
+            Err(ParseError::new(2, 5, "x", "expected # or ."))
+        );
     }
 
     #[test]
     fn test_evolve() {
-        let mut grid = Grid::new(6);
-        grid.parse_grid(EXAMPLE);
+        let mut grid = Lights::new(6);
+        grid.parse_grid(EXAMPLE).unwrap();
 
-        grid.evolve();
-        grid.evolve();
-        grid.evolve();
-        grid.evolve();
+        grid.evolve(4);
 
         assert_eq!(grid.count_lights(), 4);
 
-        assert_eq!(grid.is_on(2, 2), Some(&true));
-        assert_eq!(grid.is_on(2, 3), Some(&true));
-        assert_eq!(grid.is_on(3, 2), Some(&true));
-        assert_eq!(grid.is_on(3, 3), Some(&true));
+        assert_eq!(grid.grid.get(2, 2), Some(&true));
+        assert_eq!(grid.grid.get(3, 2), Some(&true));
+        assert_eq!(grid.grid.get(2, 3), Some(&true));
+        assert_eq!(grid.grid.get(3, 3), Some(&true));
     }
 
     #[test]
     fn test_empty_get_answer_normal() {
-        assert_eq!(get_answer_normal(""), 0);
+        assert!(get_answer_normal("", &Params::new()).is_err());
     }
 
     #[test]
     fn test_empty_get_answer_broken() {
-        assert_eq!(get_answer_broken(""), 4);
+        assert!(get_answer_broken("", &Params::new()).is_err());
+    }
+
+    #[test]
+    fn test_example_params() {
+        let mut params = Params::new();
+        params.set("size", "6");
+        params.set("steps", "4");
+        assert_eq!(get_answer_normal(EXAMPLE, &params), Ok(4));
+
+        params.set("steps", "5");
+        assert_eq!(get_answer_broken(EXAMPLE, &params), Ok(17));
+
+        params.set("size", "six");
+        assert!(get_answer_normal(EXAMPLE, &params).is_err());
+
+        params.set("size", "0");
+        let error = Err(ParseError::input("the parameter size can't be 0"));
+        assert_eq!(get_answer_normal("", &params), error);
+        assert_eq!(get_answer_broken("", &params), error);
+        assert!(render("", &params).is_err());
+        assert!(animate("", &params).is_err());
     }
 
     #[test]
     fn test_break_circuit() {
-        let mut grid = Grid::new(6);
+        let mut grid = Lights::new(6);
         assert_eq!(grid.count_lights(), 0);
 
         grid.break_circuit();
         assert_eq!(grid.count_lights(), 4);
     }
+
+    #[test]
+    fn test_render() {
+        let mut grid = Lights::new(6);
+        grid.parse_grid(EXAMPLE).unwrap();
+
+        let animation = grid.animate("lights", 4);
+        assert_eq!(animation.frames().len(), 5);
+        assert_eq!(animation.frames()[0].width(), 6 * PIXEL_SIZE);
+        assert_eq!(animation.frames()[0].get(4, 0), Some(255));
+        assert_eq!(animation.frames()[4].get(0, 0), Some(0));
+
+        assert!(render("", &Params::new()).is_err());
+    }
+
+    #[test]
+    fn test_animate() {
+        let mut grid = Lights::new(6);
+        grid.parse_grid(EXAMPLE).unwrap();
+
+        let frames: Vec<String> = grid.scene("lights", 4).collect();
+        assert_eq!(frames.len(), 5);
+        assert_eq!(frames[0], format!("step 0/4, 15 lights\n{}\n", grid.grid));
+        assert_eq!(
+            frames[4],
+            "step 4/4, 4 lights\n......\n......\n..##..\n..##..\n......\n......\n"
+        );
+
+        assert!(animate("", &Params::new()).is_err());
+    }
+
+    #[test]
+    fn test_evolve_broken() {
+        let mut grid = Lights::new(6);
+        grid.parse_grid(EXAMPLE).unwrap();
+        grid.break_circuit();
+        assert_eq!(grid.count_lights(), 17);
+
+        grid.evolve(5);
+        assert_eq!(grid.count_lights(), 17);
+        assert!(grid.grid[(5, 5)]);
+    }
 }
@@ -1,19 +1,23 @@
+use advparse::{expect_token, next_token, ParseError};
 use rand::seq::SliceRandom;
 use regex::Regex;
 use std::collections::HashSet;
 
-pub fn count_unique_molecules(input: &str) -> usize {
-    let (replacements, sample_molecule) = parse_all(input);
+pub fn count_unique_molecules(input: &str) -> Result<usize, ParseError> {
+    let (replacements, sample_molecule) = parse_all(input)?;
     let molecules = build_molecules(sample_molecule, &replacements);
 
-    molecules.len()
+    Ok(molecules.len())
 }
 
-pub fn count_molecules_from_electron(input: &str) -> usize {
-    let (replacements, sample_molecule) = parse_all(input);
-    let count = build_molecule_from_e(sample_molecule, &replacements);
+pub fn count_molecules_from_electron(input: &str) -> Result<usize, ParseError> {
+    let (replacements, sample_molecule) = parse_all(input)?;
+    if replacements.is_empty() {
+        return Err(ParseError::input("there are no replacements"));
+    }
 
-    count
+    build_molecule_from_e(sample_molecule, &replacements)
+        .ok_or_else(|| ParseError::input("the molecule can't be made from e"))
 }
 
 struct Replace<'a> {
@@ -21,32 +25,44 @@ struct Replace<'a> {
     to: &'a str,
 }
 
-fn parse_single_replace(line: &str) -> Replace {
-    let atoms: Vec<&str> = line.trim().split(" => ").collect();
-    Replace {
-        from: atoms[0],
-        to: atoms[1],
+// H => HO
+fn parse_single_replace(line: &str) -> Result<Replace<'_>, ParseError> {
+    let mut atoms = line.split_whitespace();
+    let from = next_token(line, &mut atoms, "an atom")?;
+    expect_token(line, &mut atoms, "=>")?;
+    let to = next_token(line, &mut atoms, "a molecule")?;
+
+    if let Some(token) = atoms.next() {
+        return Err(ParseError::in_line(
+            line,
+            token,
+            "expected the end of the line",
+        ));
     }
+
+    Ok(Replace { from, to })
 }
 
-fn parse_all(input: &str) -> (Vec<Replace>, &str) {
+// The replacements, an empty line and the molecule
+fn parse_all(input: &str) -> Result<(Vec<Replace<'_>>, &str), ParseError> {
     let mut result = Vec::with_capacity(5);
-    let mut is_molecule = false;
-    let mut molecule: &str = "";
+    let mut lines = input.lines().enumerate();
 
-    input.lines().into_iter().map(&str::trim).for_each(|line| {
-        if line.is_empty() {
-            is_molecule = true;
+    for (index, line) in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
         }
 
-        if !is_molecule {
-            result.push(parse_single_replace(line));
-        } else {
-            molecule = line;
-        }
-    });
+        result.push(parse_single_replace(line).map_err(|err| err.at_line(index + 1))?);
+    }
 
-    (result, molecule)
+    match lines
+        .map(|(_, line)| line.trim())
+        .find(|line| !line.is_empty())
+    {
+        Some(molecule) => Ok((result, molecule)),
+        None => Err(ParseError::input("the molecule is missing")),
+    }
 }
 
 fn split_molecule(input: &str) -> Vec<&str> {
@@ -85,38 +101,51 @@ fn build_molecules(molecule: &str, replacements: &Vec<Replace>) -> HashSet<Strin
     molecules
 }
 
-fn build_molecule_from_e(molecule: &str, replacements: &Vec<Replace>) -> usize {
+// The random search gives up after so many attempts, the molecule may not be made from e at all
+const ATTEMPTS: usize = 1000;
+
+fn build_molecule_from_e(molecule: &str, replacements: &[Replace]) -> Option<usize> {
+    if !replacements.iter().any(|replace| replace.from == "e") {
+        return None;
+    }
+
     let mut thread_rng = rand::thread_rng();
-    let mut finished = false;
-    let mut iter = 0usize;
 
     // brute force, if the answer is not obtained - repeat.
-    while !finished {
-        iter = 0usize;
+    for _ in 0..ATTEMPTS {
+        let mut iter = 0usize;
 
         // it is a potential 'e' result, but not always
         let mut potential_e = String::from(molecule);
 
         while potential_e != "e" {
-            if let Some(replacement) = replacements.choose(&mut thread_rng) {
-                if potential_e.find(replacement.to).is_some() {
-                    potential_e = potential_e.replacen(replacement.to, replacement.from, 1);
-                    iter += 1;
-                }
+            // nothing can be replaced any more, or the molecule keeps growing
+            if replacements
+                .iter()
+                .all(|replace| !potential_e.contains(replace.to))
+                || potential_e.len() > 2 * molecule.len()
+            {
+                break;
+            }
 
-                // couldn't find the solution
-                if potential_e.chars().filter(|c| *c == 'e').count() > 1 {
-                    break;
-                }
-            } else {
-                return iter;
+            let replacement = replacements.choose(&mut thread_rng)?;
+            if potential_e.contains(replacement.to) {
+                potential_e = potential_e.replacen(replacement.to, replacement.from, 1);
+                iter += 1;
+            }
+
+            // couldn't find the solution
+            if potential_e.chars().filter(|c| *c == 'e').count() > 1 {
+                break;
             }
         }
 
-        finished = potential_e == "e";
+        if potential_e == "e" {
+            return Some(iter);
+        }
     }
 
-    iter
+    None
 }
 
 #[cfg(test)]
@@ -164,14 +193,14 @@ mod tests {
 
     #[test]
     fn test_parse_single_replace() {
-        let replace_atoms = parse_single_replace("O => HH");
+        let replace_atoms = parse_single_replace("O => HH").unwrap();
         assert_eq!(replace_atoms.from, "O");
         assert_eq!(replace_atoms.to, "HH");
     }
 
     #[test]
     fn test_parse_all() {
-        let (replacements, molecule) = parse_all(SAMPLE);
+        let (replacements, molecule) = parse_all(SAMPLE).unwrap();
 
         assert_eq!(replacements.len(), 3);
 
@@ -189,7 +218,7 @@ mod tests {
 
     #[test]
     fn test_build_molecules() {
-        let (replacements, sample_molecule) = parse_all(SAMPLE);
+        let (replacements, sample_molecule) = parse_all(SAMPLE).unwrap();
 
         let molecules = build_molecules(sample_molecule, &replacements);
 
@@ -202,7 +231,7 @@ mod tests {
 
     #[test]
     fn test_build_molecules_santa() {
-        let (replacements, sample_molecule) = parse_all(SANTA_SAMPLE);
+        let (replacements, sample_molecule) = parse_all(SANTA_SAMPLE).unwrap();
 
         let molecules = build_molecules(sample_molecule, &replacements);
         println!("{:?}", molecules);
@@ -213,33 +242,47 @@ mod tests {
     fn test_count_unique_molecules() {
         let answer = count_unique_molecules(SANTA_SAMPLE);
 
-        assert_eq!(answer, 7);
+        assert_eq!(answer, Ok(7));
     }
 
     #[test]
     fn test_count_unique_molecules_empty() {
         let answer = count_unique_molecules("");
 
-        assert_eq!(answer, 0);
+        assert_eq!(answer, Err(ParseError::input("the molecule is missing")));
+        assert_eq!(
+            count_unique_molecules("H -> HO\n\nHOH").err(),
+            Some(ParseError::new(1, 3, "->", "expected \"=>\""))
+        );
     }
 
     #[test]
     fn test_build_molecule_from_e() {
-        let (replacements, molecule) = parse_all(E_SAMPLE);
+        let (replacements, molecule) = parse_all(E_SAMPLE).unwrap();
         let number_steps = build_molecule_from_e(molecule, &replacements);
-        assert_eq!(number_steps, 3);
+        assert_eq!(number_steps, Some(3));
     }
 
     #[test]
     fn test_build_santa_molecule_from_e() {
-        let (replacements, molecule) = parse_all(E_SANTA_SAMPLE);
+        let (replacements, molecule) = parse_all(E_SANTA_SAMPLE).unwrap();
         let number_steps = build_molecule_from_e(molecule, &replacements);
-        assert_eq!(number_steps, 6);
+        assert_eq!(number_steps, Some(6));
     }
 
     #[test]
     fn test_count_molecules_from_electron() {
         let answer = count_molecules_from_electron(E_SANTA_SAMPLE);
-        assert_eq!(answer, 6);
+        assert_eq!(answer, Ok(6));
+    }
+
+    #[test]
+    fn test_molecule_not_from_electron() {
+        let error = Err(ParseError::input("the molecule can't be made from e"));
+
+        // No replacement starts with e, nothing replaces b, and H grows into HO forever
+        assert_eq!(count_molecules_from_electron("a => b\n\nab"), error);
+        assert_eq!(count_molecules_from_electron("e => a\n\nb"), error);
+        assert_eq!(count_molecules_from_electron("e => O\nHO => H\n\nH"), error);
     }
 }
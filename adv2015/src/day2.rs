@@ -1,35 +1,35 @@
-pub fn calc_packs(s: &str) -> u32 {
-    s.lines()
-        .map(|line| {
-            let (l, w, h) = parse_line(line);
-            calc_paper(l, w, h)
-        })
-        .sum()
-}
+use advparse::{next_token, parse_lines, parse_number, ParseError};
+
+pub fn calc_packs(s: &str) -> Result<u32, ParseError> {
+    let boxes = parse_lines(s, parse_line)?;
 
-pub fn calc_ribbons(s: &str) -> u32 {
-    s.lines()
-        .map(|line| {
-            let (l, w, h) = parse_line(line);
-            calc_ribbon(l, w, h)
-        })
-        .sum()
+    Ok(boxes.into_iter().map(|(l, w, h)| calc_paper(l, w, h)).sum())
 }
 
-fn parse_line(s: &str) -> (u8, u8, u8) {
-    let mut result: Vec<u8> = Vec::new();
-    let tokens = s.split('x');
-    for token in tokens {
-        if let Ok(dim) = token.parse::<u8>() {
-            result.push(dim);
-        }
-    }
+pub fn calc_ribbons(s: &str) -> Result<u32, ParseError> {
+    let boxes = parse_lines(s, parse_line)?;
+
+    Ok(boxes
+        .into_iter()
+        .map(|(l, w, h)| calc_ribbon(l, w, h))
+        .sum())
+}
 
-    if result.len() != 3 {
-        return (0, 0, 0);
+fn parse_line(s: &str) -> Result<(u8, u8, u8), ParseError> {
+    let mut tokens = s.trim().split('x');
+    let l = parse_number(s, next_token(s, &mut tokens, "a length")?)?;
+    let w = parse_number(s, next_token(s, &mut tokens, "a width")?)?;
+    let h = parse_number(s, next_token(s, &mut tokens, "a height")?)?;
+
+    if let Some(token) = tokens.next() {
+        return Err(ParseError::in_line(
+            s,
+            token,
+            "expected the end of the line",
+        ));
     }
 
-    (result[0], result[1], result[2])
+    Ok((l, w, h))
 }
 
 fn calc_paper(l: u8, w: u8, h: u8) -> u32 {
@@ -72,15 +72,25 @@ mod tests {
 
     #[test]
     fn test_parse_line() {
-        assert_eq!(parse_line("1x1x1"), (1, 1, 1));
-        assert_eq!(parse_line("1x2x3"), (1, 2, 3));
-        assert_eq!(parse_line("1x2x3x4"), (0, 0, 0));
-        assert_eq!(parse_line("1x2"), (0, 0, 0));
+        assert_eq!(parse_line("1x1x1"), Ok((1, 1, 1)));
+        assert_eq!(parse_line("1x2x3"), Ok((1, 2, 3)));
+        assert_eq!(
+            parse_line("1x2x3x4"),
+            Err(ParseError::new(0, 7, "4", "expected the end of the line"))
+        );
+        assert_eq!(
+            parse_line("1x2"),
+            Err(ParseError::new(0, 4, "", "expected a height"))
+        );
+        assert_eq!(
+            parse_line("1xax3"),
+            Err(ParseError::new(0, 3, "a", "expected a number"))
+        );
     }
 
     #[test]
     fn test_calc_packs() {
-        assert_eq!(calc_packs("2x3x4\n1x1x10"), 58 + 43);
+        assert_eq!(calc_packs("2x3x4\n1x1x10"), Ok(58 + 43));
     }
 
     #[test]
@@ -91,6 +101,6 @@ mod tests {
 
     #[test]
     fn test_calc_ribbons() {
-        assert_eq!(calc_ribbons("2x3x4\n1x1x10"), 34 + 14);
+        assert_eq!(calc_ribbons("2x3x4\n1x1x10"), Ok(34 + 14));
     }
 }
@@ -1,19 +1,16 @@
+use advparse::{parse_number, ParseError};
 use std::ops::*;
 
-pub fn get_richest_house_before_strike(input: &str) -> usize {
-    if let Ok(desired_number) = input.parse::<usize>() {
-        find_house(desired_number, count_presents)
-    } else {
-        0
-    }
+pub fn get_richest_house_before_strike(input: &str) -> Result<usize, ParseError> {
+    let desired_number = parse_number(input, input).map_err(|err| err.at_line(1))?;
+
+    Ok(find_house(desired_number, count_presents))
 }
 
-pub fn get_richest_house_after_strike(input: &str) -> usize {
-    if let Ok(desired_number) = input.parse::<usize>() {
-        find_house(desired_number, count_strike_presents)
-    } else {
-        0
-    }
+pub fn get_richest_house_after_strike(input: &str) -> Result<usize, ParseError> {
+    let desired_number = parse_number(input, input).map_err(|err| err.at_line(1))?;
+
+    Ok(find_house(desired_number, count_strike_presents))
 }
 
 fn find_house(desired_number: usize, algorithm: fn(usize) -> usize) -> usize {
@@ -82,16 +79,16 @@ mod tests {
 
     #[test]
     fn test_get_richest_house_fast() {
-        assert_eq!(get_richest_house_before_strike(""), 0);
-        assert_eq!(get_richest_house_after_strike(""), 0);
-        assert_eq!(get_richest_house_before_strike("360000"), 10080);
-        assert_eq!(get_richest_house_after_strike("360000"), 10080);
+        assert!(get_richest_house_before_strike("").is_err());
+        assert!(get_richest_house_after_strike("many").is_err());
+        assert_eq!(get_richest_house_before_strike("360000"), Ok(10080));
+        assert_eq!(get_richest_house_after_strike("360000"), Ok(10080));
     }
 
     #[test]
     #[ignore]
     fn test_get_richest_house_slow() {
-        assert_eq!(get_richest_house_before_strike("36000000"), 831600);
-        assert_eq!(get_richest_house_after_strike("36000000"), 884520);
+        assert_eq!(get_richest_house_before_strike("36000000"), Ok(831600));
+        assert_eq!(get_richest_house_after_strike("36000000"), Ok(884520));
     }
 }
@@ -1,7 +1,9 @@
-pub fn find_cheapest_warrior(input: &str) -> usize {
+use advparse::{parse_lines, parse_number, ParseError};
+
+pub fn find_cheapest_warrior(input: &str) -> Result<usize, ParseError> {
     let mut humanity = Generator::new();
     let mut wealth = usize::MAX;
-    let enemy = parse_enemy(input);
+    let enemy = parse_enemy(input)?;
 
     while let Some(human) = humanity.next() {
         if wealth > human.wealth {
@@ -13,13 +15,17 @@ pub fn find_cheapest_warrior(input: &str) -> usize {
         }
     }
 
-    wealth
+    if wealth == usize::MAX {
+        return Err(ParseError::input("the boss can't be beaten"));
+    }
+
+    Ok(wealth)
 }
 
-pub fn find_expensive_loser(input: &str) -> usize {
+pub fn find_expensive_loser(input: &str) -> Result<usize, ParseError> {
     let mut humanity = Generator::new();
     let mut wealth = 0usize;
-    let enemy = parse_enemy(input);
+    let enemy = parse_enemy(input)?;
 
     while let Some(human) = humanity.next() {
         if wealth < human.wealth {
@@ -31,7 +37,7 @@ pub fn find_expensive_loser(input: &str) -> usize {
         }
     }
 
-    wealth
+    Ok(wealth)
 }
 
 #[derive(Debug)]
@@ -239,25 +245,34 @@ impl Iterator for Generator {
     }
 }
 
-fn parse_enemy(input: &str) -> Human {
-    input
-        .to_lowercase()
-        .lines()
-        .map(|line| line.trim())
-        .fold(Human::default(), |mut res, line| {
-            let mut pair = line.split(": ");
-
-            let item = pair.next().unwrap();
-            if let Ok(value) = pair.next().unwrap().parse::<usize>() {
-                match item {
-                    "hit points" => res.health = value,
-                    "damage" => res.damage = value,
-                    "armor" => res.armor = value,
-                    _ => {}
-                }
-            }
-            res
-        })
+// Hit Points: 100
+fn parse_enemy(input: &str) -> Result<Human, ParseError> {
+    let mut health = None;
+    let mut damage = None;
+    let mut armor = None;
+
+    parse_lines(input, |line| {
+        let (item, value) = line.split_once(':').ok_or_else(|| {
+            ParseError::in_line(line, line.trim(), "expected \"<property>: <value>\"")
+        })?;
+        let value = || parse_number(line, value.trim());
+
+        match item.trim().to_lowercase().as_str() {
+            "hit points" => health = Some(value()?),
+            "damage" => damage = Some(value()?),
+            "armor" => armor = Some(value()?),
+            _ => {}
+        }
+        Ok(())
+    })?;
+
+    Ok(Human {
+        health: health
+            .ok_or_else(|| ParseError::input("the hit points of the boss are missing"))?,
+        damage: damage.ok_or_else(|| ParseError::input("the damage of the boss is missing"))?,
+        armor: armor.ok_or_else(|| ParseError::input("the armor of the boss is missing"))?,
+        wealth: 0,
+    })
 }
 
 fn fight_to_death(human_sample: &Human, enemy_sample: &Human) -> Battle {
@@ -306,7 +321,8 @@ mod tests {
             Damage: 8
             Armor: 2
             Name: Tester"#,
-        );
+        )
+        .unwrap();
 
         assert_eq!(enemy.health, 100);
         assert_eq!(enemy.damage, 8);
@@ -390,7 +406,7 @@ mod tests {
             Armor: 2"#,
         );
 
-        assert_eq!(wealth, 91);
+        assert_eq!(wealth, Ok(91));
     }
 
     #[test]
@@ -401,7 +417,17 @@ mod tests {
             Armor: 2"#,
         );
 
-        assert_eq!(wealth, 158);
+        assert_eq!(wealth, Ok(158));
+    }
+
+    #[test]
+    fn test_wrong_enemy() {
+        assert_eq!(
+            parse_enemy("Hit Points: 100\nDamage: eight").err(),
+            Some(ParseError::new(2, 9, "eight", "expected a number"))
+        );
+        assert!(parse_enemy("Hit Points 100").is_err());
+        assert!(find_cheapest_warrior("Hit Points: 100\nDamage: 8").is_err());
     }
 
     #[test]
@@ -1,39 +1,43 @@
-const PLAYER: Player = Player {
-    health: 50,
-    mana: 500,
-    armor: 0,
-};
+use advcore::{Param, Params};
+use advparse::{parse_lines, parse_number, ParseError};
 
-pub fn find_easy_result(input: &str) -> usize {
-    let enemy = parse_enemy(input);
+pub const HIT_POINTS: Param = Param::new("hit-points", "50", "the hit points of the player");
+pub const MANA: Param = Param::new("mana", "500", "the mana of the player");
 
-    let mut game = GameState {
-        queue: Vec::new(),
-        player: PLAYER,
-        enemy,
-        won_cost: usize::MAX,
-        queue_cost: 0,
-    };
-
-    simulate_game(&mut game, false);
+pub fn find_easy_result(input: &str, params: &Params) -> Result<usize, ParseError> {
+    find_result(input, params, false)
+}
 
-    game.won_cost
+pub fn find_hard_result(input: &str, params: &Params) -> Result<usize, ParseError> {
+    find_result(input, params, true)
 }
 
-pub fn find_hard_result(input: &str) -> usize {
-    let enemy = parse_enemy(input);
+fn find_result(input: &str, params: &Params, hard: bool) -> Result<usize, ParseError> {
+    let enemy = parse_enemy(input)?;
 
     let mut game = GameState {
         queue: Vec::new(),
-        player: PLAYER,
+        player: Player {
+            health: params.get(&HIT_POINTS)?,
+            mana: params.get(&MANA)?,
+            armor: 0,
+        },
         enemy,
         won_cost: usize::MAX,
         queue_cost: 0,
     };
 
-    simulate_game(&mut game, true);
+    simulate_game(&mut game, hard);
 
-    game.won_cost
+    won_cost(&game)
+}
+
+fn won_cost(game: &GameState) -> Result<usize, ParseError> {
+    if game.won_cost == usize::MAX {
+        return Err(ParseError::input("the boss can't be beaten"));
+    }
+
+    Ok(game.won_cost)
 }
 
 struct GameState {
@@ -218,24 +222,30 @@ impl Default for Enemy {
     }
 }
 
-fn parse_enemy(input: &str) -> Enemy {
-    input
-        .to_lowercase()
-        .lines()
-        .map(|line| line.trim())
-        .fold(Enemy::default(), |mut res, line| {
-            let mut pair = line.split(": ");
-
-            let item = pair.next().unwrap();
-            if let Ok(value) = pair.next().unwrap().parse::<usize>() {
-                match item {
-                    "hit points" => res.health = value,
-                    "damage" => res.damage = value,
-                    _ => {}
-                }
-            }
-            res
-        })
+// Hit Points: 100
+fn parse_enemy(input: &str) -> Result<Enemy, ParseError> {
+    let mut health = None;
+    let mut damage = None;
+
+    parse_lines(input, |line| {
+        let (item, value) = line.split_once(':').ok_or_else(|| {
+            ParseError::in_line(line, line.trim(), "expected \"<property>: <value>\"")
+        })?;
+        let value = || parse_number(line, value.trim());
+
+        match item.trim().to_lowercase().as_str() {
+            "hit points" => health = Some(value()?),
+            "damage" => damage = Some(value()?),
+            _ => {}
+        }
+        Ok(())
+    })?;
+
+    Ok(Enemy {
+        health: health
+            .ok_or_else(|| ParseError::input("the hit points of the boss are missing"))?,
+        damage: damage.ok_or_else(|| ParseError::input("the damage of the boss is missing"))?,
+    })
 }
 
 fn is_queue_valid(queue: &Vec<Action>) -> bool {
@@ -386,6 +396,7 @@ fn simulate_battle(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advcore::Registry;
 
     impl PartialEq for Battle {
         fn eq(&self, other: &Self) -> bool {
@@ -414,10 +425,13 @@ mod tests {
             r#"Hit Points: 71
             Damage: 10
             Name: Tester"#,
-        );
+        )
+        .unwrap();
 
         assert_eq!(enemy.health, 71);
         assert_eq!(enemy.damage, 10);
+
+        assert!(parse_enemy("Hit Points: 71").is_err());
     }
 
     #[test]
@@ -636,6 +650,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_example_params() {
+        let mut params = Params::new();
+        params.set("hit-points", "10");
+        params.set("mana", "250");
+
+        assert_eq!(
+            find_easy_result("Hit Points: 13\nDamage: 8", &params),
+            Ok(226)
+        );
+        // Four magic missiles, the player survives three attacks with 50 hit points
+        assert_eq!(
+            find_easy_result("Hit Points: 13\nDamage: 8", &Params::new()),
+            Ok(4 * 53)
+        );
+
+        params.set("mana", "lots");
+        assert_eq!(
+            find_hard_result("Hit Points: 13\nDamage: 8", &params),
+            Err(ParseError::input("the parameter mana can't be \"lots\""))
+        );
+    }
+
+    #[test]
+    fn test_unknown_params() {
+        let mut registry = Registry::new();
+        crate::register(&mut registry);
+
+        let mut params = Params::new();
+        params.set("hitpoints", "10");
+        assert_eq!(
+            registry.check_params(&[(2015, 22)], &params),
+            Err(String::from(
+                "The selected days have no parameter hitpoints, expected one of: hit-points, mana"
+            ))
+        );
+    }
+
     #[test]
     fn test_simulate_draw() {
         let player = Player {
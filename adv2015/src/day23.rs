@@ -1,23 +1,24 @@
+use advparse::{next_token, parse_lines, parse_number, ParseError};
 use std::collections::HashMap;
 use std::str::FromStr;
 
-pub fn get_answer_b(input: &str) -> i32 {
-    let instructions = parse_code(input);
+pub fn get_answer_b(input: &str) -> Result<i32, ParseError> {
+    let instructions = parse_code(input)?;
     let mut machine = Machine::new(instructions);
 
     while machine.do_evaluate() {}
 
-    Machine::get_register(&mut machine.registers, &'b')
+    Ok(Machine::get_register(&mut machine.registers, &'b'))
 }
 
-pub fn get_answer_b_after_a(input: &str) -> i32 {
-    let instructions = parse_code(input);
+pub fn get_answer_b_after_a(input: &str) -> Result<i32, ParseError> {
+    let instructions = parse_code(input)?;
     let mut machine = Machine::new(instructions);
     Machine::set_register(&mut machine.registers, &'a', 1);
 
     while machine.do_evaluate() {}
 
-    Machine::get_register(&mut machine.registers, &'b')
+    Ok(Machine::get_register(&mut machine.registers, &'b'))
 }
 
 type Offset = i16;
@@ -119,68 +120,59 @@ impl Machine {
     }
 }
 
-#[derive(Debug)]
-struct ParseInstructionError(String);
+// A single letter, the registers aren't limited to a and b
+fn parse_register(line: &str, token: &str) -> Result<Register, ParseError> {
+    let mut chars = token.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(r), None) if r.is_ascii_lowercase() => Ok(r),
+        _ => Err(ParseError::in_line(line, token, "expected a register")),
+    }
+}
 
 impl FromStr for Instruction {
-    type Err = ParseInstructionError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split: Vec<&str> = s
-            .trim()
-            .split(|c: char| c == ' ' || c == ',')
-            .filter(|s| !s.is_empty())
-            .collect();
-
-        if split.len() != 2 && split.len() != 3 {
-            return Err(ParseInstructionError(String::from(s)));
-        }
+        let mut tokens = s
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty());
+
+        let inst = next_token(s, &mut tokens, "an instruction")?;
+        let mut register = || parse_register(s, next_token(s, &mut tokens, "a register")?);
+
+        let instruction = match inst {
+            "hlf" => Instruction::hlf(register()?),
+            "tpl" => Instruction::tpl(register()?),
+            "inc" => Instruction::inc(register()?),
+            "jmp" => Instruction::jmp(parse_number(s, next_token(s, &mut tokens, "an offset")?)?),
+            "jie" | "jio" => {
+                let r = register()?;
+                let o = parse_number(s, next_token(s, &mut tokens, "an offset")?)?;
+
+                if inst == "jie" {
+                    Instruction::jie(r, o)
+                } else {
+                    Instruction::jio(r, o)
+                }
+            }
+            _ => return Err(ParseError::in_line(s, inst, "unknown instruction")),
+        };
 
-        let inst = split[0];
-        match inst {
-            "hlf" => char::from_str(split[1])
-                .map_err(|_| ParseInstructionError(String::from(s)))
-                .map(|r| Instruction::hlf(r)),
-
-            "tpl" => char::from_str(split[1])
-                .map_err(|_| ParseInstructionError(String::from(s)))
-                .map(|r| Instruction::tpl(r)),
-
-            "inc" => char::from_str(split[1])
-                .map_err(|_| ParseInstructionError(String::from(s)))
-                .map(|r| Instruction::inc(r)),
-
-            "jmp" => Offset::from_str(split[1])
-                .map_err(|_| ParseInstructionError(String::from(s)))
-                .map(|o| Instruction::jmp(o)),
-
-            "jie" if split.len() == 3 => char::from_str(split[1])
-                .map_err(|_| ParseInstructionError(String::from(s)))
-                .and_then(|r| {
-                    Offset::from_str(split[2])
-                        .map_err(|_| ParseInstructionError(String::from(s)))
-                        .map(|o| Instruction::jie(r, o))
-                }),
-
-            "jio" if split.len() == 3 => char::from_str(split[1])
-                .map_err(|_| ParseInstructionError(String::from(s)))
-                .and_then(|r| {
-                    Offset::from_str(split[2])
-                        .map_err(|_| ParseInstructionError(String::from(s)))
-                        .map(|o| Instruction::jio(r, o))
-                }),
-
-            _ => Err(ParseInstructionError(String::from(s))),
+        if let Some(token) = tokens.next() {
+            return Err(ParseError::in_line(
+                s,
+                token,
+                "expected the end of the line",
+            ));
         }
+
+        Ok(instruction)
     }
 }
 
-fn parse_code(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
-        .map(|l| Instruction::from_str(l))
-        .filter_map(|r| r.ok())
-        .collect()
+fn parse_code(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(input, Instruction::from_str)
 }
 
 #[cfg(test)]
@@ -188,12 +180,6 @@ mod tests {
     use super::*;
     use crate::day23::Instruction::*;
 
-    impl PartialEq for ParseInstructionError {
-        fn eq(&self, other: &Self) -> bool {
-            self.0 == other.0
-        }
-    }
-
     impl PartialEq for Instruction {
         fn eq(&self, other: &Self) -> bool {
             match (self, other) {
@@ -228,16 +214,19 @@ mod tests {
         // PartialEq for Instruction
         assert_ne!(Instruction::from_str("jio c, -2").unwrap(), inc('c'));
 
-        // PartialEq for ParseInstructionError
         assert_eq!(
             Instruction::from_str("jio err, -2").unwrap_err(),
-            ParseInstructionError(String::from("jio err, -2"))
+            ParseError::new(0, 5, "err", "expected a register")
         );
 
         // only xxx y or xxx y, z
         assert_eq!(
             Instruction::from_str("jio a, b, c, d").unwrap_err(),
-            ParseInstructionError(String::from("jio a, b, c, d"))
+            ParseError::new(0, 8, "b", "expected a number")
+        );
+        assert_eq!(
+            Instruction::from_str("jmp 1, 2").unwrap_err(),
+            ParseError::new(0, 8, "2", "expected the end of the line")
         );
     }
 
@@ -269,13 +258,8 @@ mod tests {
         );
 
         assert_eq!(
-            format!("{:?}", Instruction::from_str("jpa  b, 3").unwrap_err()),
-            "ParseInstructionError(\"jpa  b, 3\")"
-        );
-
-        assert_eq!(
-            format!("{:?}", Instruction::from_str("jio err, -2").unwrap_err()),
-            "ParseInstructionError(\"jio err, -2\")"
+            Instruction::from_str("jpa  b, 3").unwrap_err().to_string(),
+            "line 0, column 1: unknown instruction, found \"jpa\""
         );
     }
 
@@ -283,17 +267,20 @@ mod tests {
     fn test_parse_code() {
         let input = r#"jio a, +19
                        inc a
-                       jop 123"#;
-        let instructions = parse_code(input);
+                       jmp -2"#;
+        let instructions = parse_code(input).unwrap();
         assert_eq!(instructions[0], Instruction::jio('a', 19));
         assert_eq!(instructions[1], Instruction::inc('a'));
-        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions.len(), 3);
+
+        let err = parse_code("inc a\n  jop 123").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 3, "jop", "unknown instruction"));
     }
 
     #[test]
     fn test_machine_1() {
         let input = r#"inc a"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         let result1 = machine.do_evaluate();
         let result2 = machine.do_evaluate();
         assert!(result1);
@@ -307,7 +294,7 @@ mod tests {
         let input = r#"inc a
                   inc a
                   hlf a"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         machine.do_evaluate(); // a == 1
         machine.do_evaluate(); // a == 2
         assert_eq!(machine.registers.get(&'a'), Some(&2));
@@ -320,7 +307,7 @@ mod tests {
         let input = r#"inc a
                   inc a
                   tpl a"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         machine.do_evaluate(); // a == 1
         machine.do_evaluate(); // a == 2
         assert_eq!(machine.registers.get(&'a'), Some(&2));
@@ -333,7 +320,7 @@ mod tests {
         let input = r#"jmp +2
                   inc a
                   tpl a"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         assert_eq!(machine.position, 0);
         machine.do_evaluate(); // position == 0 + 2
         assert_eq!(machine.position, 2);
@@ -345,7 +332,7 @@ mod tests {
     fn test_machine_4_overflow() {
         let input = r#"jmp +2
                   inc a"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         assert_eq!(machine.position, 0);
         let result = machine.do_evaluate(); // position == 0 + 2
         assert!(result);
@@ -357,7 +344,7 @@ mod tests {
         let input = r#"inc a
                   inc a
                   jmp -2"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         assert_eq!(machine.position, 0);
         machine.do_evaluate(); // position == 0 + 1
         assert_eq!(machine.position, 1);
@@ -373,7 +360,7 @@ mod tests {
         let input = r#"inc a
                   inc a
                   jie a, -2"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         assert_eq!(machine.position, 0);
         machine.do_evaluate(); // position == 0 + 1
         assert_eq!(machine.position, 1);
@@ -389,7 +376,7 @@ mod tests {
         let input = r#"inc a
                   inc b
                   jie a, -2"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         assert_eq!(machine.position, 0);
         machine.do_evaluate(); // position == 0 + 1
         assert_eq!(machine.position, 1);
@@ -406,7 +393,7 @@ mod tests {
         let input = r#"inc a
                   inc a
                   jio a, -2"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         assert_eq!(machine.position, 0);
         machine.do_evaluate(); // position == 0 + 1
         assert_eq!(machine.position, 1);
@@ -422,7 +409,7 @@ mod tests {
         let input = r#"inc a
                   inc b
                   jio a, -2"#;
-        let mut machine = Machine::new(parse_code(input));
+        let mut machine = Machine::new(parse_code(input).unwrap());
         assert_eq!(machine.position, 0);
         machine.do_evaluate(); // position == 0 + 1
         assert_eq!(machine.position, 1);
@@ -1,25 +1,31 @@
+use advparse::{parse_numbers, ParseError};
 use combinations::Combinations;
 use std::cmp::min;
 
-pub fn find_answer(input: &str) -> usize {
-    let packages = parse_packages(input);
-    let weight = packages.iter().sum::<usize>() / 3;
-
-    find_optimal_qe(&packages, weight)
-}
+pub fn balance_in_groups(packages: &[usize], groups: usize) -> Result<usize, ParseError> {
+    let total = packages.iter().sum::<usize>();
+    let no_balance = || {
+        ParseError::input(format!(
+            "the packages can't be split in {} groups of the same weight",
+            groups
+        ))
+    };
 
-pub fn find_answer_better(input: &str) -> usize {
-    let packages = parse_packages(input);
-    let weight = packages.iter().sum::<usize>() / 4;
+    if packages.is_empty() || total % groups != 0 {
+        return Err(no_balance());
+    }
 
-    find_optimal_qe(&packages, weight)
+    match find_optimal_qe(packages, total / groups) {
+        usize::MAX => Err(no_balance()),
+        qe => Ok(qe),
+    }
 }
 
-fn find_optimal_qe(packages: &Vec<usize>, weight: usize) -> usize {
+fn find_optimal_qe(packages: &[usize], weight: usize) -> usize {
     let mut lowest_qe = usize::MAX;
     let mut lowest_count = usize::MAX;
 
-    let sleigh = SleighCombination::new(&packages, weight);
+    let sleigh = SleighCombination::new(packages, weight);
 
     for group in sleigh {
         for filtered_group in group {
@@ -46,13 +52,8 @@ fn find_optimal_qe(packages: &Vec<usize>, weight: usize) -> usize {
     lowest_qe
 }
 
-fn parse_packages(input: &str) -> Vec<usize> {
-    input
-        .lines()
-        .map(|l| l.trim())
-        .filter(|l| !l.is_empty())
-        .filter_map(|p| p.parse::<usize>().ok())
-        .collect()
+pub fn parse_packages(input: &str) -> Result<Vec<usize>, ParseError> {
+    parse_numbers(input)
 }
 
 struct SleighCombination {
@@ -62,7 +63,7 @@ struct SleighCombination {
 }
 
 impl SleighCombination {
-    fn new(packages: &Vec<usize>, weight: usize) -> SleighCombination {
+    fn new(packages: &[usize], weight: usize) -> SleighCombination {
         let copy_packages = packages.to_vec();
 
         SleighCombination {
@@ -111,7 +112,7 @@ fn calc_qe(group: &Vec<usize>) -> usize {
     group.iter().fold(1, |acc, p| acc * p)
 }
 
-fn subtract_vectors(from: &Vec<usize>, rhs: &Vec<usize>) -> Vec<usize> {
+fn subtract_vectors(from: &[usize], rhs: &Vec<usize>) -> Vec<usize> {
     let mut result = Vec::new();
 
     result.extend(from.iter().filter(|i| !rhs.contains(i)));
@@ -130,11 +131,26 @@ mod tests {
         2
 
         6"#,
-        );
+        )
+        .unwrap();
         assert_eq!(packages.len(), 3);
         assert_eq!(packages[0], 1);
         assert_eq!(packages[1], 2);
         assert_eq!(packages[2], 6);
+
+        assert_eq!(
+            parse_packages("1\n2x"),
+            Err(ParseError::new(2, 1, "2x", "expected a number"))
+        );
+    }
+
+    #[test]
+    fn test_balance_in_groups() {
+        let packages: Vec<usize> = vec![1, 2, 3, 4, 5, 7, 8, 9, 10, 11];
+        assert_eq!(balance_in_groups(&packages, 3), Ok(99));
+        assert_eq!(balance_in_groups(&packages, 4), Ok(44));
+        assert!(balance_in_groups(&[1, 2, 4], 3).is_err());
+        assert!(balance_in_groups(&[], 3).is_err());
     }
 
     #[test]
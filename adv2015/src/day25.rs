@@ -1,9 +1,11 @@
+use advparse::ParseError;
+
 const FIRST_CODE: usize = 20151125;
 const MULTIPLY: usize = 252533;
 const DIVIDE: usize = 33554393;
 
-pub fn find_code(input: &str) -> usize {
-    let target = parse_input(input);
+pub fn find_code(input: &str) -> Result<usize, ParseError> {
+    let target = parse_input(input)?;
     let list = ListCodes::new(&target);
     let mut result = 0usize;
 
@@ -11,23 +13,24 @@ pub fn find_code(input: &str) -> usize {
         result = code;
     }
 
-    result
+    Ok(result)
 }
 
-fn parse_input(input: &str) -> Coord {
+// The only numbers of the sentence are the row and the column
+fn parse_input(input: &str) -> Result<Coord, ParseError> {
     let result: Vec<usize> = input
         .split(|c: char| c.is_whitespace() || c.is_alphabetic() || c.is_ascii_punctuation())
         .filter(|s| !s.is_empty())
         .filter_map(|s| s.parse::<usize>().ok())
         .collect();
 
-    if result.len() != 2 {
-        return Coord { row: 0, col: 0 };
-    }
-
-    Coord {
-        row: result[0],
-        col: result[1],
+    match result[..] {
+        [row, col] if row > 0 && col > 0 => Ok(Coord { row, col }),
+        [_, _] => Err(ParseError::input("the row and the column start with 1")),
+        _ => Err(ParseError::input(format!(
+            "expected a row and a column, found {} numbers",
+            result.len()
+        ))),
     }
 }
 
@@ -98,7 +101,7 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let coord = parse_input("To continue, please consult the code grid in the manual.  Enter the code at row 2947, column 3029.");
+        let coord = parse_input("To continue, please consult the code grid in the manual.  Enter the code at row 2947, column 3029.").unwrap();
         assert_eq!(coord.row, 2947);
         assert_eq!(coord.col, 3029);
 
@@ -113,9 +116,14 @@ mod tests {
 
     #[test]
     fn test_parse_bad_input() {
-        let coord = parse_input("To continue, Test 123 please consult the code grid in the manual.  Enter the code at row 2947, column 3029.");
-        assert_eq!(coord.row, 0);
-        assert_eq!(coord.col, 0);
+        let err = parse_input("To continue, Test 123 please consult the code grid in the manual.  Enter the code at row 2947, column 3029.");
+        assert_eq!(
+            err,
+            Err(ParseError::input(
+                "expected a row and a column, found 3 numbers"
+            ))
+        );
+        assert!(parse_input("row 0, column 1").is_err());
     }
 
     #[test]
@@ -138,7 +146,7 @@ mod tests {
     fn test_find_code() {
         assert_eq!(
             find_code("Give me bla-bla-bla row 6, bla-bla-bla col 4"),
-            24659492
+            Ok(24659492)
         );
     }
 }
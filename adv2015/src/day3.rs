@@ -1,3 +1,8 @@
+use advparse::ParseError;
+use advrender::Scene;
+use std::collections::HashSet;
+use std::iter::once;
+
 #[derive(Debug, Copy, Clone)]
 struct Coord {
     x: i16,
@@ -22,24 +27,26 @@ impl PartialEq for Coord {
     }
 }
 
-pub fn count_houses(path: &str) -> usize {
+pub fn count_houses(path: &str) -> Result<usize, ParseError> {
+    let path = parse_path(path)?;
     let mut houses: Vec<Coord> = Vec::new();
     houses.push(Coord { x: 0, y: 0 });
 
     let mut house = Coord { x: 0, y: 0 };
 
-    for c in path.chars() {
+    for c in path {
         house.step(c);
 
         if !houses.contains(&house) {
-            houses.push(house.clone());
+            houses.push(house);
         }
     }
 
-    houses.len()
+    Ok(houses.len())
 }
 
-pub fn count_houses_together(path: &str) -> usize {
+pub fn count_houses_together(path: &str) -> Result<usize, ParseError> {
+    let path = parse_path(path)?;
     let mut houses: Vec<Coord> = Vec::new();
     houses.push(Coord { x: 0, y: 0 });
 
@@ -48,29 +55,104 @@ pub fn count_houses_together(path: &str) -> usize {
 
     let mut santa_turn = true;
 
-    for c in path.chars() {
+    for c in path {
         if santa_turn {
             santa.step(c);
             if !houses.contains(&santa) {
-                houses.push(santa.clone());
+                houses.push(santa);
             }
         } else {
             robot.step(c);
             if !houses.contains(&robot) {
-                houses.push(robot.clone());
+                houses.push(robot);
             }
         }
 
         santa_turn = !santa_turn;
     }
 
-    houses.len()
+    Ok(houses.len())
+}
+
+// Santa walks in the middle of the screen, # are the visited houses
+pub fn animate(path: &str) -> Result<Vec<Scene>, ParseError> {
+    let path = parse_path(path)?;
+    let total = path.len();
+    let mut santa = Coord { x: 0, y: 0 };
+    let mut houses = HashSet::from([(0, 0)]);
+
+    let first = draw_walk(0, total, &houses, santa);
+    let steps = path.into_iter().enumerate().map(move |(i, c)| {
+        santa.step(c);
+        houses.insert((santa.x, santa.y));
+
+        draw_walk(i + 1, total, &houses, santa)
+    });
+
+    Ok(vec![Scene::new("santa", once(first).chain(steps))])
+}
+
+const VIEW_WIDTH: i16 = 61;
+const VIEW_HEIGHT: i16 = 21;
+
+fn draw_walk(step: usize, total: usize, houses: &HashSet<(i16, i16)>, santa: Coord) -> String {
+    let mut frame = format!("step {}/{}, {} houses\n", step, total, houses.len());
+
+    // North is up
+    for dy in 0..VIEW_HEIGHT {
+        let y = santa.y + VIEW_HEIGHT / 2 - dy;
+        for dx in 0..VIEW_WIDTH {
+            let x = santa.x - VIEW_WIDTH / 2 + dx;
+            frame.push(match (x, y) {
+                _ if x == santa.x && y == santa.y => '@',
+                _ if houses.contains(&(x, y)) => '#',
+                _ => '.',
+            });
+        }
+        frame.push('\n');
+    }
+
+    frame
+}
+
+fn parse_path(path: &str) -> Result<Vec<char>, ParseError> {
+    path.char_indices()
+        .map(|(i, c)| match c {
+            '^' | '>' | 'v' | '<' => Ok(c),
+            _ => Err(
+                ParseError::in_line(path, &path[i..i + c.len_utf8()], "expected a direction")
+                    .at_line(1),
+            ),
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_animate() {
+        let frames: Vec<String> = animate("^>v").unwrap().remove(0).collect();
+        assert_eq!(frames.len(), 4);
+        assert!(frames[0].starts_with("step 0/3, 1 houses\n"));
+        assert!(frames[3].starts_with("step 3/3, 4 houses\n"));
+
+        // The last house is in the middle, the first one is on the left
+        let middle: Vec<&str> = frames[3]
+            .lines()
+            .skip(1 + VIEW_HEIGHT as usize / 2)
+            .collect();
+        assert_eq!(&middle[0][29..32], "#@.");
+        assert_eq!(&middle[1][29..32], "...");
+        assert_eq!(
+            &frames[3].lines().nth(VIEW_HEIGHT as usize / 2).unwrap()[29..32],
+            "##."
+        );
+
+        assert!(animate("^x").is_err());
+    }
+
     #[test]
     fn test_circle() {
         let mut c = Coord { x: 0, y: 0 };
@@ -107,21 +189,34 @@ mod tests {
 
     #[test]
     fn test_count_houses() {
-        assert_eq!(count_houses(""), 1, "We always start with one house");
-        assert_eq!(count_houses(">"), 2, "> - 2");
-        assert_eq!(count_houses("^>v<"), 4, "^>v< - 4");
-        assert_eq!(count_houses("^v^v^v^v^v"), 2, "^v^v^v^v^v - 2");
+        assert_eq!(count_houses(""), Ok(1), "We always start with one house");
+        assert_eq!(count_houses(">"), Ok(2), "> - 2");
+        assert_eq!(count_houses("^>v<"), Ok(4), "^>v< - 4");
+        assert_eq!(count_houses("^v^v^v^v^v"), Ok(2), "^v^v^v^v^v - 2");
+    }
+
+    #[test]
+    fn test_count_houses_wrong_path() {
+        assert_eq!(
+            count_houses("^>o<"),
+            Err(ParseError::new(1, 3, "o", "expected a direction"))
+        );
+        assert!(count_houses_together("^>\n<").is_err());
     }
 
     #[test]
     fn test_count_houses_together() {
         assert_eq!(
             count_houses_together(""),
-            1,
+            Ok(1),
             "We always start with one house"
         );
-        assert_eq!(count_houses_together("^v"), 3, "^v - 3");
-        assert_eq!(count_houses_together("^>v<"), 3, "^>v< - 3");
-        assert_eq!(count_houses_together("^v^v^v^v^v"), 11, "^v^v^v^v^v - 11");
+        assert_eq!(count_houses_together("^v"), Ok(3), "^v - 3");
+        assert_eq!(count_houses_together("^>v<"), Ok(3), "^>v< - 3");
+        assert_eq!(
+            count_houses_together("^v^v^v^v^v"),
+            Ok(11),
+            "^v^v^v^v^v - 11"
+        );
     }
 }
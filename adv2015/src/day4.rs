@@ -1,4 +1,10 @@
-pub fn mine_suffix(secret: &str, zeroes: u8) -> u32 {
+use advparse::ParseError;
+
+pub fn mine_suffix(secret: &str, zeroes: u8) -> Result<u32, ParseError> {
+    if secret.is_empty() {
+        return Err(ParseError::input("the secret key is empty"));
+    }
+
     let mut answer = 1;
 
     let mut pattern = String::with_capacity(zeroes as usize);
@@ -10,7 +16,7 @@ pub fn mine_suffix(secret: &str, zeroes: u8) -> u32 {
         answer += 1;
     }
 
-    answer
+    Ok(answer)
 }
 
 fn calc_md5(secret: &str, suffix: u32) -> String {
@@ -49,22 +55,27 @@ mod tests {
     fn test_mine_suffix_slow() {
         assert_eq!(
             mine_suffix("abcdef", 5),
-            609043,
+            Ok(609043),
             "Didn't mine suffix properly"
         );
         assert_eq!(
             mine_suffix("pqrstuv", 5),
-            1048970,
+            Ok(1048970),
             "Didn't mine suffix properly"
         );
     }
 
     #[test]
     fn test_mine_suffix_fast() {
-        let abc = mine_suffix("abcdef", 1);
+        let abc = mine_suffix("abcdef", 1).unwrap();
         assert_eq!(abc, 31, "Didn't mine suffix properly");
 
         let hash = calc_md5("abcdef", abc);
         assert!(hash.starts_with('0'), "Hash doesn't start with 0: {}", hash);
     }
+
+    #[test]
+    fn test_mine_without_secret() {
+        assert!(mine_suffix("", 1).is_err());
+    }
 }
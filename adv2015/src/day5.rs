@@ -1,11 +1,27 @@
+use advparse::{parse_lines, ParseError};
 use std::collections::HashSet;
 
-pub fn count_nice_lines(input: &str) -> usize {
-    input.lines().filter(|&s| is_nice(s)).count()
+pub fn count_nice_lines(input: &str) -> Result<usize, ParseError> {
+    let lines = parse_lines(input, parse_word)?;
+
+    Ok(lines.into_iter().filter(|s| is_nice(s)).count())
 }
 
-pub fn count_nice_lines_advanced(input: &str) -> usize {
-    input.lines().filter(|&s| is_nice_advanced(s)).count()
+pub fn count_nice_lines_advanced(input: &str) -> Result<usize, ParseError> {
+    let lines = parse_lines(input, parse_word)?;
+
+    Ok(lines.into_iter().filter(|s| is_nice_advanced(s)).count())
+}
+
+fn parse_word(line: &str) -> Result<&str, ParseError> {
+    match line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+        Some((i, c)) => Err(ParseError::in_line(
+            line,
+            &line[i..i + c.len_utf8()],
+            "expected a letter",
+        )),
+        None => Ok(line),
+    }
 }
 
 fn is_nice(s: &str) -> bool {
@@ -119,7 +135,7 @@ mod tests {
 
         assert_eq!(
             count_nice_lines(input.as_str()),
-            2,
+            Ok(2),
             "{} has two nice strings",
             input
         );
@@ -131,12 +147,20 @@ mod tests {
 
         assert_eq!(
             count_nice_lines_advanced(input.as_str()),
-            3,
+            Ok(3),
             "{} has three nice strings",
             input
         );
     }
 
+    #[test]
+    fn test_count_wrong_lines() {
+        assert_eq!(
+            count_nice_lines("aaa\nugknb fddgicrmopn"),
+            Err(ParseError::new(2, 6, " ", "expected a letter"))
+        );
+    }
+
     #[test]
     fn test_is_nice_advanced() {
         ["qjhvhtzxzqqjkmpb", "xxyxx"].iter().for_each(|s| {
@@ -1,15 +1,31 @@
+use advgrid::Grid;
+use advparse::ParseError;
+use advrender::{Animation, Frame};
 use std::fmt;
 
-pub fn count_bulbs(input: &str) -> usize {
+pub fn count_bulbs(input: &str) -> Result<usize, ParseError> {
     let mut santa = SantaInterpreter::new();
-    santa.interpret(String::from(input));
-    santa.get_state()
+    santa.interpret(String::from(input))?;
+    Ok(santa.get_state())
 }
 
-pub fn count_brightness(input: &str) -> usize {
+pub fn count_brightness(input: &str) -> Result<usize, ParseError> {
     let mut santa = SantaBetterInterpreter::new();
-    santa.interpret(String::from(input));
-    santa.get_state()
+    santa.interpret(String::from(input))?;
+    Ok(santa.get_state())
+}
+
+// The lights which are on are white, the brightness is a shade of gray
+pub fn render(input: &str) -> Result<Vec<Animation>, ParseError> {
+    let mut santa = SantaInterpreter::new();
+    santa.interpret(String::from(input))?;
+    let mut better_santa = SantaBetterInterpreter::new();
+    better_santa.interpret(String::from(input))?;
+
+    Ok(vec![
+        Animation::still("bulbs", santa.frame()),
+        Animation::still("brightness", better_santa.frame()),
+    ])
 }
 
 const LIGHT_MAX_SIZE: usize = 1000;
@@ -35,6 +51,9 @@ enum Token {
     // 768,548
     Coord(usize, usize),
 
+    // a typo, or a coordinate, which isn't a number
+    Unknown(String),
+
     EOF,
 }
 
@@ -54,6 +73,7 @@ enum Call {
 struct Lexer {
     input: String,
     current_pos: usize,
+    token_pos: usize,
 }
 
 struct Parser {
@@ -62,12 +82,12 @@ struct Parser {
 }
 
 struct SantaInterpreter {
-    state: Vec<Vec<bool>>,
+    state: Grid<bool>,
     parser: Parser,
 }
 
 struct SantaBetterInterpreter {
-    state: Vec<Vec<usize>>,
+    state: Grid<usize>,
     parser: Parser,
 }
 
@@ -78,16 +98,18 @@ impl Token {
             "turn off" => return Token::TurnOff,
             "toggle" => return Token::Toggle,
             "through" => return Token::Through,
+            "" => Token::EOF,
             _ if input.contains(',') => {
-                return {
-                    let coord_pair: Vec<&str> = input.split(',').collect();
-                    let res_x = coord_pair[0].parse::<usize>();
-                    let res_y = coord_pair[1].parse::<usize>();
+                let coord_pair: Vec<&str> = input.split(',').collect();
+                let res_x = coord_pair[0].parse::<usize>();
+                let res_y = coord_pair[1].parse::<usize>();
 
-                    Token::Coord(res_x.unwrap_or(0usize), res_y.unwrap_or(0usize))
+                match (coord_pair.len(), res_x, res_y) {
+                    (2, Ok(x), Ok(y)) => Token::Coord(x, y),
+                    _ => Token::Unknown(String::from(input)),
                 }
             }
-            _ => Token::EOF,
+            _ => Token::Unknown(String::from(input)),
         }
     }
 }
@@ -99,15 +121,40 @@ impl Lexer {
         Lexer {
             input,
             current_pos: 0usize,
+            token_pos: 0usize,
         }
     }
 
     fn next_token(&mut self) -> Token {
+        // get_token skips everything, which is neither a word nor a coordinate
+        self.token_pos = self
+            .input
+            .chars()
+            .enumerate()
+            .skip(self.current_pos)
+            .find(|(_, c)| c.is_alphanumeric() || *c == ',')
+            .map_or(self.current_pos, |(i, _)| i);
+
         let next_token = get_token(self.input.as_str(), self.current_pos);
         self.current_pos = next_token.1;
 
         next_token.0
     }
+
+    // Points to the last token
+    fn error(&self, reason: &str) -> ParseError {
+        let before: String = self.input.chars().take(self.token_pos).collect();
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        let text: String = self
+            .input
+            .chars()
+            .skip(self.token_pos)
+            .take(self.current_pos.saturating_sub(self.token_pos))
+            .collect();
+
+        ParseError::new(line, column, text.trim(), reason)
+    }
 }
 
 impl Parser {
@@ -118,15 +165,15 @@ impl Parser {
         }
     }
 
-    fn next_operation(&mut self) -> Call {
+    fn next_operation(&mut self) -> Result<Call, ParseError> {
         if !self.parsing {
-            return Call::EOF;
+            return Ok(Call::EOF);
         }
 
         match self.lexer.next_token() {
             Token::EOF => {
                 self.parsing = false;
-                Call::EOF
+                Ok(Call::EOF)
             }
             token @ Token::TurnOn | token @ Token::TurnOff | token @ Token::Toggle => {
                 let op = match token {
@@ -134,44 +181,35 @@ impl Parser {
                     Token::TurnOn => Operation::TurnOn,
                     _ => Operation::TurnOff, // eventually TurnOff
                 };
-                let c1 = match self.lexer.next_token() {
-                    Token::Coord(x, y) => Coord(x, y),
-                    t => {
-                        println!("Unexpected token {} after token {}", t, token);
-                        self.parsing = false;
-                        return Call::EOF;
-                    }
-                };
-                let th = match self.lexer.next_token() {
-                    t @ Token::Through => t,
-                    t => {
-                        println!("Unexpected token {} after token {}", t, c1);
-                        self.parsing = false;
-                        return Call::EOF;
-                    }
-                };
-                let c2 = match self.lexer.next_token() {
-                    Token::Coord(x, y) => Coord(x, y),
-                    t => {
-                        println!("Unexpected token {} after token {}", t, th);
-                        self.parsing = false;
-                        return Call::EOF;
-                    }
+                let c1 = self.next_coord()?;
+                match self.lexer.next_token() {
+                    Token::Through => (),
+                    _ => return Err(self.fail("expected \"through\"")),
                 };
+                let c2 = self.next_coord()?;
 
-                Call::Call(op, c1, c2)
-            }
-            token => {
-                println!("Unexpected token in the line: {}", token);
-                self.parsing = false;
-                Call::EOF
+                Ok(Call::Call(op, c1, c2))
             }
+            _ => Err(self.fail("expected \"turn on\", \"turn off\" or \"toggle\"")),
+        }
+    }
+
+    fn next_coord(&mut self) -> Result<Coord, ParseError> {
+        match self.lexer.next_token() {
+            Token::Coord(x, y) if x < LIGHT_MAX_SIZE && y < LIGHT_MAX_SIZE => Ok(Coord(x, y)),
+            Token::Coord(_, _) => Err(self.fail("the coordinate is outside of the grid")),
+            _ => Err(self.fail("expected a coordinate")),
         }
     }
+
+    fn fail(&mut self, reason: &str) -> ParseError {
+        self.parsing = false;
+        self.lexer.error(reason)
+    }
 }
 
 impl Iterator for Parser {
-    type Item = Call;
+    type Item = Result<Call, ParseError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         Some(self.next_operation())
@@ -182,21 +220,21 @@ impl SantaInterpreter {
     fn new() -> SantaInterpreter {
         SantaInterpreter {
             parser: Parser::new(String::from("")),
-            state: vec![vec![false; LIGHT_MAX_SIZE]; LIGHT_MAX_SIZE],
+            state: Grid::new(LIGHT_MAX_SIZE, LIGHT_MAX_SIZE, false),
         }
     }
 
-    fn interpret(&mut self, input: String) {
+    fn interpret(&mut self, input: String) -> Result<(), ParseError> {
         self.parser = Parser::new(input);
 
         while let Some(c) = self.parser.next() {
-            if let Call::Call(op, c1, c2) = c {
+            if let Call::Call(op, c1, c2) = c? {
                 for x in c1.0..=c2.0 {
                     for y in c1.1..=c2.1 {
-                        self.state[x][y] = match op {
+                        self.state[(x, y)] = match op {
                             Operation::TurnOn => true,
                             Operation::TurnOff => false,
-                            Operation::Toggle => !self.state[x][y],
+                            Operation::Toggle => !self.state[(x, y)],
                         };
                     }
                 }
@@ -204,23 +242,21 @@ impl SantaInterpreter {
                 break;
             }
         }
+
+        Ok(())
     }
 
     #[allow(dead_code)]
     fn reset(&mut self) {
-        for x in 0..LIGHT_MAX_SIZE {
-            for y in 0..LIGHT_MAX_SIZE {
-                self.state[x][y] = false;
-            }
-        }
+        self.state.fill(false);
     }
 
     fn get_state(&self) -> usize {
-        self.state.iter().fold(0usize, |acc1, line| {
-            acc1 + line
-                .iter()
-                .fold(0usize, |acc2, &bulb| acc2 + if bulb { 1 } else { 0 })
-        })
+        self.state.count(|&bulb| bulb)
+    }
+
+    fn frame(&self) -> Frame {
+        Frame::from_grid(&self.state, |&bulb| if bulb { 255 } else { 0 })
     }
 }
 
@@ -228,21 +264,22 @@ impl SantaBetterInterpreter {
     fn new() -> SantaBetterInterpreter {
         SantaBetterInterpreter {
             parser: Parser::new(String::from("")),
-            state: vec![vec![0; LIGHT_MAX_SIZE]; LIGHT_MAX_SIZE],
+            state: Grid::new(LIGHT_MAX_SIZE, LIGHT_MAX_SIZE, 0),
         }
     }
 
-    fn interpret(&mut self, input: String) {
+    fn interpret(&mut self, input: String) -> Result<(), ParseError> {
         self.parser = Parser::new(input);
 
         while let Some(c) = self.parser.next() {
-            if let Call::Call(op, c1, c2) = c {
+            if let Call::Call(op, c1, c2) = c? {
                 for x in c1.0..=c2.0 {
                     for y in c1.1..=c2.1 {
-                        self.state[x][y] = match op {
-                            Operation::TurnOn => self.state[x][y].saturating_add(1),
-                            Operation::TurnOff => self.state[x][y].saturating_sub(1),
-                            Operation::Toggle => self.state[x][y].saturating_add(2),
+                        let brightness = self.state[(x, y)];
+                        self.state[(x, y)] = match op {
+                            Operation::TurnOn => brightness.saturating_add(1),
+                            Operation::TurnOff => brightness.saturating_sub(1),
+                            Operation::Toggle => brightness.saturating_add(2),
                         };
                     }
                 }
@@ -250,24 +287,26 @@ impl SantaBetterInterpreter {
                 break;
             }
         }
+
+        Ok(())
     }
 
     #[allow(dead_code)]
     fn reset(&mut self) {
-        for x in 0..LIGHT_MAX_SIZE {
-            for y in 0..LIGHT_MAX_SIZE {
-                self.state[x][y] = 0;
-            }
-        }
+        self.state.fill(0);
     }
 
     fn get_state(&self) -> usize {
-        self.state.iter().fold(0usize, |acc1, line| {
-            acc1.saturating_add(
-                line.iter()
-                    .fold(0usize, |acc2, &brightness| acc2.saturating_add(brightness)),
-            )
-        })
+        self.state
+            .cells()
+            .fold(0usize, |acc, &brightness| acc.saturating_add(brightness))
+    }
+
+    // The brightest light is white
+    fn frame(&self) -> Frame {
+        let max = self.state.cells().copied().max().unwrap_or(0).max(1);
+
+        Frame::from_grid(&self.state, |&brightness| (brightness * 255 / max) as u8)
     }
 }
 
@@ -313,6 +352,7 @@ impl fmt::Display for Token {
             Token::TurnOff => f.write_str("off"),
             Token::Through => f.write_str(";"),
             Token::Coord(x, y) => f.write_fmt(format_args!("({},{})", x, y)),
+            Token::Unknown(word) => f.write_str(word),
             Token::EOF => f.write_str(";"),
         }
     }
@@ -386,6 +426,7 @@ mod tests {
                 Token::TurnOff => f.write_str("TurnOff"),
                 Token::Through => f.write_str("Through"),
                 Token::Coord(x, y) => f.write_fmt(format_args!("Coord({}, {})", x, y)),
+                Token::Unknown(word) => f.write_fmt(format_args!("Unknown({})", word)),
                 Token::EOF => f.write_str("EOF"),
             }
         }
@@ -406,6 +447,7 @@ mod tests {
                 (Token::Through, Token::Through) => true,
                 (Token::EOF, Token::EOF) => true,
                 (Token::Coord(x1, y1), Token::Coord(x2, y2)) if x1 == x2 && y1 == y2 => true,
+                (Token::Unknown(w1), Token::Unknown(w2)) => w1 == w2,
                 _ => false,
             }
         }
@@ -574,33 +616,64 @@ mod tests {
         let mut parser = Parser::new(String::from("turn on 499,989 through 806,992"));
         assert_eq!(
             parser.next_operation(),
-            Call::Call(Operation::TurnOn, Coord(499, 989), Coord(806, 992),)
+            Ok(Call::Call(
+                Operation::TurnOn,
+                Coord(499, 989),
+                Coord(806, 992),
+            ))
         );
         assert!(parser.parsing);
 
-        assert_eq!(parser.next_operation(), Call::EOF);
+        assert_eq!(parser.next_operation(), Ok(Call::EOF));
         assert!(!parser.parsing);
 
-        assert_eq!(parser.next_operation(), Call::EOF);
+        assert_eq!(parser.next_operation(), Ok(Call::EOF));
     }
 
     #[test]
     fn test_invalid_parser() {
         let mut parser = Parser::new(String::from("turn on on 499,989 through 806,992"));
-        assert_eq!(parser.next_operation(), Call::EOF);
+        assert_eq!(
+            parser.next_operation(),
+            Err(ParseError::new(1, 9, "on", "expected a coordinate"))
+        );
         assert!(!parser.parsing);
+        assert_eq!(parser.next_operation(), Ok(Call::EOF));
 
         let mut parser = Parser::new(String::from("turn on 499,989 on 806,992"));
-        assert_eq!(parser.next_operation(), Call::EOF);
+        assert_eq!(
+            parser.next_operation(),
+            Err(ParseError::new(1, 17, "on", "expected \"through\""))
+        );
         assert!(!parser.parsing);
 
         let mut parser = Parser::new(String::from("turn on 499,989 through on 806,992"));
-        assert_eq!(parser.next_operation(), Call::EOF);
+        assert!(parser.next_operation().is_err());
         assert!(!parser.parsing);
 
         let mut parser = Parser::new(String::from("through turn on 499,989 through on 806,992"));
-        assert_eq!(parser.next_operation(), Call::EOF);
+        assert!(parser.next_operation().is_err());
         assert!(!parser.parsing);
+
+        let mut parser = Parser::new(String::from(
+            "toggle 0,0 through 0,0\ntoggle 0,x through 1000,0",
+        ));
+        assert!(parser.next_operation().is_ok());
+        assert_eq!(
+            parser.next_operation(),
+            Err(ParseError::new(2, 8, "0,x", "expected a coordinate"))
+        );
+
+        let mut parser = Parser::new(String::from("toggle 0,0 through 1000,0"));
+        assert_eq!(
+            parser.next_operation(),
+            Err(ParseError::new(
+                1,
+                20,
+                "1000,0",
+                "the coordinate is outside of the grid"
+            ))
+        );
     }
 
     #[test]
@@ -610,11 +683,15 @@ mod tests {
         ));
         assert_eq!(
             parser.next_operation(),
-            Call::Call(Operation::TurnOn, Coord(59, 99), Coord(806, 99),)
+            Ok(Call::Call(Operation::TurnOn, Coord(59, 99), Coord(806, 99),))
         );
         assert_eq!(
             parser.next_operation(),
-            Call::Call(Operation::TurnOff, Coord(812, 389), Coord(865, 874),)
+            Ok(Call::Call(
+                Operation::TurnOff,
+                Coord(812, 389),
+                Coord(865, 874),
+            ))
         );
     }
 
@@ -627,7 +704,7 @@ mod tests {
         ));
         println!("Testing: {}", input);
         let mut basic = SantaInterpreter::new();
-        basic.interpret(input);
+        basic.interpret(input).unwrap();
         let answer = basic.get_state();
         println!("{} bulbs are showing us Christmas", answer);
 
@@ -650,7 +727,7 @@ mod tests {
             LIGHT_MAX_SIZE - 1
         ));
         let mut basic = SantaInterpreter::new();
-        basic.interpret(input);
+        basic.interpret(input).unwrap();
         let answer = basic.get_state();
         assert_eq!(
             answer, 0,
@@ -669,7 +746,7 @@ mod tests {
             LIGHT_MAX_SIZE - 1
         ));
         let mut basic = SantaInterpreter::new();
-        basic.interpret(input);
+        basic.interpret(input).unwrap();
         let answer = basic.get_state();
         assert_eq!(
             answer, 0,
@@ -681,9 +758,11 @@ mod tests {
     #[test]
     fn test_santa_interpreter() {
         let mut basic = SantaInterpreter::new();
-        basic.interpret(String::from(
-            "turn on 0,0 through 99,99\r\ntoggle 100,100 through 199,199",
-        ));
+        basic
+            .interpret(String::from(
+                "turn on 0,0 through 99,99\r\ntoggle 100,100 through 199,199",
+            ))
+            .unwrap();
         let answer = basic.get_state();
         assert_eq!(
             answer,
@@ -697,9 +776,11 @@ mod tests {
     #[test]
     fn test_santa_reset() {
         let mut basic = SantaInterpreter::new();
-        basic.interpret(String::from(
-            "turn on 0,0 through 99,99\r\ntoggle 100,100 through 199,199",
-        ));
+        basic
+            .interpret(String::from(
+                "turn on 0,0 through 99,99\r\ntoggle 100,100 through 199,199",
+            ))
+            .unwrap();
         basic.reset();
         let answer = basic.get_state();
 
@@ -715,7 +796,7 @@ mod tests {
         ));
         println!("Testing: {}", input);
         let mut basic = SantaBetterInterpreter::new();
-        basic.interpret(input);
+        basic.interpret(input).unwrap();
         let answer = basic.get_state();
         println!("{} Christmas brightness", answer);
 
@@ -738,7 +819,7 @@ mod tests {
             LIGHT_MAX_SIZE - 1
         ));
         let mut basic = SantaBetterInterpreter::new();
-        basic.interpret(input);
+        basic.interpret(input).unwrap();
         let answer = basic.get_state();
         assert_eq!(
             answer, 0,
@@ -755,7 +836,7 @@ mod tests {
             LIGHT_MAX_SIZE - 1
         ));
         let mut basic = SantaBetterInterpreter::new();
-        basic.interpret(input);
+        basic.interpret(input).unwrap();
         let answer = basic.get_state();
         assert_eq!(
             answer,
@@ -770,7 +851,7 @@ mod tests {
     fn test_santa_better_interpreter_one_toggle() {
         let input = String::from("toggle 0,0 through 0,0");
         let mut basic = SantaBetterInterpreter::new();
-        basic.interpret(input);
+        basic.interpret(input).unwrap();
         let answer = basic.get_state();
         assert_eq!(
             answer, 2,
@@ -782,9 +863,11 @@ mod tests {
     #[test]
     fn test_santa_better_reset() {
         let mut basic = SantaBetterInterpreter::new();
-        basic.interpret(String::from(
-            "turn on 0,0 through 99,99\r\ntoggle 100,100 through 199,199",
-        ));
+        basic
+            .interpret(String::from(
+                "turn on 0,0 through 99,99\r\ntoggle 100,100 through 199,199",
+            ))
+            .unwrap();
         basic.reset();
         let answer = basic.get_state();
 
@@ -794,20 +877,41 @@ mod tests {
     #[test]
     fn test_count_bulbs() {
         let result = count_bulbs("toggle 0,0 through 0,0\ntoggle 1,1 through 1,1");
-        assert_eq!(result, 2);
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn test_count_brightness() {
         let result = count_brightness("toggle 0,0 through 0,0\ntoggle 1,1 through 1,1");
-        assert_eq!(result, 4);
+        assert_eq!(result, Ok(4));
+    }
+
+    #[test]
+    fn test_render() {
+        let animations = render("toggle 0,0 through 1,0\nturn on 1,0 through 1,0").unwrap();
+        assert_eq!(animations.len(), 2);
+
+        let bulbs = &animations[0].frames()[0];
+        assert_eq!(bulbs.width(), LIGHT_MAX_SIZE);
+        assert_eq!(bulbs.get(0, 0), Some(255));
+        assert_eq!(bulbs.get(0, 1), Some(0));
+
+        let brightness = &animations[1].frames()[0];
+        assert_eq!(brightness.get(0, 0), Some(170));
+        assert_eq!(brightness.get(1, 0), Some(255));
+
+        assert!(render("toggle 0,0").is_err());
     }
 
     #[test]
     fn test_lexer_unexpected_token() {
         let mut lexer = Lexer::new(String::from("turn on oops 599,989 through 806,993"));
         assert_eq!(lexer.next_token(), Token::TurnOn, "Unexpected Token");
-        assert_eq!(lexer.next_token(), Token::EOF, "Unexpected Token");
+        assert_eq!(
+            lexer.next_token(),
+            Token::Unknown(String::from("oops")),
+            "Unexpected Token"
+        );
     }
 
     #[test]
@@ -818,6 +922,7 @@ mod tests {
         assert_eq!(Token::EOF.to_string(), ";");
         assert_eq!(Token::Through.to_string(), ";");
         assert_eq!(Token::Coord(23, 32).to_string(), "(23,32)");
+        assert_eq!(Token::Unknown(String::from("oops")).to_string(), "oops");
 
         assert_eq!(Coord(23, 32).to_string(), "(23,32)");
 
@@ -865,9 +970,17 @@ mod tests {
     #[test]
     fn test_wrong_syntax() {
         let mut basic = SantaInterpreter::new();
-        basic.interpret(String::from("turn on through\nturn on"));
+        assert!(basic
+            .interpret(String::from("turn on through\nturn on"))
+            .is_err());
 
         let mut basic = SantaBetterInterpreter::new();
-        basic.interpret(String::from("turn off through\nturn on"));
+        assert!(basic
+            .interpret(String::from("turn off through\nturn on"))
+            .is_err());
+        assert_eq!(
+            count_bulbs("turn on 0,0 through 1,1\nturn on"),
+            Err(ParseError::new(2, 8, "", "expected a coordinate"))
+        );
     }
 }
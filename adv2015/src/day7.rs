@@ -1,22 +1,21 @@
+use advparse::ParseError;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Formatter;
 
-pub fn count_input_a(input: &str) -> u16 {
+pub fn count_input_a(input: &str) -> Result<u16, ParseError> {
     let mut bobby = BobbyInterpreter::new();
-    bobby.interpret(String::from(input));
+    bobby.interpret(String::from(input))?;
 
-    bobby.evaluate(&String::from("a")).unwrap_or_default()
+    bobby.evaluate(&String::from("a"))
 }
 
-pub fn count_input_a_override(input: &str) -> u16 {
+pub fn count_input_a_override(input: &str) -> Result<u16, ParseError> {
     let mut bobby = BobbyInterpreter::new();
-    bobby.interpret(String::from(input));
+    bobby.interpret(String::from(input))?;
 
-    let value_for_b = bobby.evaluate(&String::from("a")).unwrap();
-    bobby
-        .evaluate_override_signal(&String::from("a"), &String::from("b"), value_for_b)
-        .unwrap_or_default()
+    let value_for_b = bobby.evaluate(&String::from("a"))?;
+    bobby.evaluate_override_signal(&String::from("a"), &String::from("b"), value_for_b)
 }
 
 struct NextToken(Token, usize);
@@ -76,6 +75,7 @@ enum Expression {
 struct Lexer {
     input: String,
     current_pos: usize,
+    token_pos: usize,
 }
 
 struct Parser {
@@ -94,15 +94,40 @@ impl Lexer {
         Lexer {
             input,
             current_pos: 0usize,
+            token_pos: 0usize,
         }
     }
 
     fn next_token(&mut self) -> Token {
+        // get_token skips the whitespaces before the token
+        self.token_pos = self
+            .input
+            .chars()
+            .enumerate()
+            .skip(self.current_pos)
+            .find(|(_, c)| !c.is_whitespace())
+            .map_or(self.current_pos, |(i, _)| i);
+
         let next_token = get_token(self.input.as_str(), self.current_pos);
         self.current_pos = next_token.1;
 
         next_token.0
     }
+
+    // Points to the text from the position to the end of the last token
+    fn error(&self, from: usize, reason: &str) -> ParseError {
+        let before: String = self.input.chars().take(from).collect();
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+        let text: String = self
+            .input
+            .chars()
+            .skip(from)
+            .take(self.current_pos.saturating_sub(from))
+            .collect();
+
+        ParseError::new(line, column, text.trim(), reason)
+    }
 }
 
 impl Token {
@@ -183,67 +208,67 @@ impl Parser {
         }
     }
 
-    fn next_operation(&mut self) -> Expression {
+    fn next_operation(&mut self) -> Result<Expression, ParseError> {
         if !self.parsing {
-            return Expression::NOP;
+            return Ok(Expression::NOP);
         }
 
         let mut commands: Vec<Token> = Vec::new();
+        let mut statement_pos = None;
 
         while self.parsing {
-            match self.lexer.next_token() {
-                Token::EOF => self.parsing = false,
+            let token = self.lexer.next_token();
+            let from = *statement_pos.get_or_insert(self.lexer.token_pos);
+
+            match token {
+                Token::EOF if commands.is_empty() => self.parsing = false,
+                Token::EOF => {
+                    return Err(self.fail(from, "expected \"->\" and a wire"));
+                }
 
                 Token::Assign => {
                     if commands.is_empty() {
-                        println!("Error in parsing, there were no commands before the assignment");
-                        self.parsing = false;
-                    } else {
-                        let token = self.lexer.next_token();
-                        if let Token::Wire(target) = token {
-                            if commands.len() == 1 {
-                                if let Some(lvalue) = lvalue_from_one(&commands) {
-                                    return Expression::Assign(
-                                        Command::Result(lvalue),
-                                        LValue::Var(target),
-                                    );
-                                }
-                            } else if commands.len() == 2 {
-                                if let Some(lvalue) = lvalue_from_two(&commands) {
-                                    return Expression::Assign(lvalue, LValue::Var(target));
-                                }
-                            } else if commands.len() == 3 {
-                                if let Some(lvalue) = lvalue_from_three(&commands) {
-                                    return Expression::Assign(lvalue, LValue::Var(target));
-                                }
-                            } else {
-                                println!("Cannot parse tokens to a command. The size of the vector is {}:", commands.len());
-                                for c in &commands {
-                                    print!("{} ", c);
-                                }
-                                println!();
-                                self.parsing = false;
-                            }
-                        } else {
-                            println!("Unexpected token, expected a wire, but got {}", token);
-                            self.parsing = false;
-                        }
+                        return Err(self.fail(from, "expected a signal or a wire before \"->\""));
                     }
+
+                    let token = self.lexer.next_token();
+                    let target = match token {
+                        Token::Wire(target) => target,
+                        _ => return Err(self.fail(from, "expected a wire after \"->\"")),
+                    };
+
+                    let command = match commands.len() {
+                        1 => lvalue_from_one(&commands).map(Command::Result),
+                        2 => lvalue_from_two(&commands),
+                        3 => lvalue_from_three(&commands),
+                        _ => None,
+                    };
+
+                    return match command {
+                        Some(command) => Ok(Expression::Assign(command, LValue::Var(target))),
+                        None => Err(self.fail(from, "unsupported command")),
+                    };
                 }
 
                 token => commands.push(token),
             }
         }
-        return Expression::NOP;
+
+        Ok(Expression::NOP)
+    }
+
+    fn fail(&mut self, from: usize, reason: &str) -> ParseError {
+        self.parsing = false;
+        self.lexer.error(from, reason)
     }
 }
 
 impl Iterator for Parser {
-    type Item = Expression;
+    type Item = Result<Expression, ParseError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.next_operation() {
-            Expression::NOP => None,
+            Ok(Expression::NOP) => None,
             result => Some(result),
         }
     }
@@ -394,13 +419,13 @@ impl BobbyInterpreter {
         }
     }
 
-    fn interpret(&mut self, input: String) {
+    fn interpret(&mut self, input: String) -> Result<(), ParseError> {
         self.parser = Parser::new(input);
         self.tree.clear();
         self.cache.clear();
 
         while let Some(e) = self.parser.next() {
-            match e {
+            match e? {
                 Expression::Assign(c, r) => {
                     let LValue::Var(var_name) = r;
                     self.tree.insert(var_name, c);
@@ -408,6 +433,8 @@ impl BobbyInterpreter {
                 Expression::NOP => {}
             };
         }
+
+        Ok(())
     }
 
     #[allow(dead_code)]
@@ -418,60 +445,50 @@ impl BobbyInterpreter {
         }
     }
 
-    fn evaluate(&mut self, wire: &String) -> Option<u16> {
+    fn evaluate(&mut self, wire: &String) -> Result<u16, ParseError> {
         // Read from cache, if the value exists there
         if let Some(&cached_value) = self.cache.get(wire) {
-            return Some(cached_value);
-        }
-
-        let next_command = self.tree.get(&wire.clone());
-        if next_command.is_none() {
-            eprintln!("Wire '{}' doesn't have a command", wire);
-            return None;
+            return Ok(cached_value);
         }
 
-        let result = match next_command.unwrap().clone() {
-            Command::Result(lvalue) => match lvalue {
-                RValue::Const(c) => Some(c.clone()),
-                RValue::Var(w) => self.evaluate(&w),
-            },
-            Command::Unary(op, lvalue) => {
-                // There is only one Unary operation, for more you can add "match"
-                if let Operation::Not = op {
-                    match lvalue {
-                        RValue::Const(c) => Some(!c.clone()),
-                        RValue::Var(w) => Some(!self.evaluate(&w).unwrap()),
-                    }
-                } else {
-                    None
-                }
+        let next_command = match self.tree.get(wire) {
+            Some(command) => command.clone(),
+            None => {
+                return Err(ParseError::input(format!(
+                    "the wire {} doesn't have a command",
+                    wire
+                )))
             }
-            Command::Binary(l1, op, l2) => {
-                let lvalue1 = match l1 {
-                    RValue::Const(c) => c.clone(),
-                    RValue::Var(w) => self.evaluate(&w).unwrap(),
-                };
+        };
 
-                let lvalue2 = match l2 {
-                    RValue::Const(c) => c.clone(),
-                    RValue::Var(w) => self.evaluate(&w).unwrap(),
-                };
+        let result = match next_command {
+            Command::Result(lvalue) => self.evaluate_rvalue(lvalue)?,
+            // There is only one Unary operation
+            Command::Unary(_, lvalue) => !self.evaluate_rvalue(lvalue)?,
+            Command::Binary(l1, op, l2) => {
+                let lvalue1 = self.evaluate_rvalue(l1)?;
+                let lvalue2 = self.evaluate_rvalue(l2)?;
 
                 match op {
-                    Operation::And => Some(lvalue1 & lvalue2),
-                    Operation::Or => Some(lvalue1 | lvalue2),
-                    Operation::LShift => Some(lvalue1 << lvalue2),
-                    Operation::RShift => Some(lvalue1 >> lvalue2),
-                    _ => None,
+                    Operation::And => lvalue1 & lvalue2,
+                    Operation::Or => lvalue1 | lvalue2,
+                    Operation::LShift => lvalue1.checked_shl(lvalue2 as u32).unwrap_or(0),
+                    Operation::RShift => lvalue1.checked_shr(lvalue2 as u32).unwrap_or(0),
+                    Operation::Not => !lvalue2,
                 }
             }
         };
 
-        if let Some(u) = result {
-            self.cache.insert(wire.clone(), u);
-        }
+        self.cache.insert(wire.clone(), result);
 
-        result
+        Ok(result)
+    }
+
+    fn evaluate_rvalue(&mut self, rvalue: RValue) -> Result<u16, ParseError> {
+        match rvalue {
+            RValue::Const(c) => Ok(c),
+            RValue::Var(w) => self.evaluate(&w),
+        }
     }
 
     fn evaluate_override_signal(
@@ -479,7 +496,7 @@ impl BobbyInterpreter {
         wire: &String,
         new_wire: &String,
         new_value: u16,
-    ) -> Option<u16> {
+    ) -> Result<u16, ParseError> {
         self.cache.clear();
         self.tree.insert(
             (*new_wire).clone(),
@@ -845,7 +862,7 @@ mod tests {
                                            NOT x -> h
                                            NOT y -> i",
         ));
-        match parser.next_operation() {
+        match parser.next_operation().unwrap() {
             Expression::Assign(c, v) => {
                 if let Command::Result(RValue::Const(u)) = c {
                     assert_eq!(u, 123, "A wrong signal was parsed");
@@ -858,7 +875,7 @@ mod tests {
             _ => panic!("Shouldn't happen"),
         };
 
-        match parser.next_operation() {
+        match parser.next_operation().unwrap() {
             Expression::Assign(c, v) => {
                 if let Command::Result(RValue::Const(u)) = c {
                     assert_eq!(u, 456, "A wrong signal was parsed");
@@ -871,7 +888,7 @@ mod tests {
             _ => panic!("Shouldn't happen"),
         };
 
-        match parser.next_operation() {
+        match parser.next_operation().unwrap() {
             Expression::Assign(c, v) => {
                 if let Command::Binary(RValue::Var(x), Operation::And, RValue::Var(y)) = c {
                     assert_eq!(x, "x", "A wrong wire was parsed");
@@ -885,7 +902,7 @@ mod tests {
             _ => panic!("Shouldn't happen"),
         };
 
-        match parser.next_operation() {
+        match parser.next_operation().unwrap() {
             Expression::Assign(c, v) => {
                 if let Command::Binary(RValue::Var(x), Operation::Or, RValue::Var(y)) = c {
                     assert_eq!(x, "x", "A wrong wire was parsed");
@@ -913,7 +930,7 @@ mod tests {
                                            NOT y -> i",
         );
         let mut bobby = BobbyInterpreter::new();
-        bobby.interpret(input);
+        bobby.interpret(input).unwrap();
         bobby.print_ast();
         let def = 0xffffu16;
 
@@ -992,7 +1009,7 @@ mod tests {
         eo LSHIFT 15 -> es",
         ));
 
-        match parser.next_operation() {
+        match parser.next_operation().unwrap() {
             Expression::Assign(c, v) => {
                 if let Command::Binary(RValue::Var(x), Operation::RShift, RValue::Const(y)) = c {
                     assert_eq!(x, "jp", "A wrong wire was parsed");
@@ -1006,7 +1023,7 @@ mod tests {
             _ => panic!("Shouldn't happen"),
         };
 
-        match parser.next_operation() {
+        match parser.next_operation().unwrap() {
             Expression::Assign(c, v) => {
                 if let Command::Binary(RValue::Const(x), Operation::And, RValue::Var(y)) = c {
                     assert_eq!(x, 1, "A wrong wire was parsed");
@@ -1020,7 +1037,7 @@ mod tests {
             _ => panic!("Shouldn't happen"),
         };
 
-        match parser.next_operation() {
+        match parser.next_operation().unwrap() {
             Expression::Assign(c, v) => {
                 if let Command::Binary(RValue::Var(x), Operation::LShift, RValue::Const(y)) = c {
                     assert_eq!(x, "eo", "A wrong wire was parsed");
@@ -1042,7 +1059,7 @@ mod tests {
                                            456 -> y",
         );
         let mut bobby = BobbyInterpreter::new();
-        bobby.interpret(input);
+        bobby.interpret(input).unwrap();
         bobby.print_ast();
         let def = 0xffffu16;
 
@@ -1113,7 +1130,7 @@ mod tests {
     #[test]
     fn test_parser_errors() {
         let mut parser = Parser::new(String::from("2 -> x"));
-        let assign = parser.next_operation();
+        let assign = parser.next_operation().unwrap();
         assert_eq!(
             assign,
             Expression::Assign(
@@ -1123,7 +1140,7 @@ mod tests {
         );
 
         let mut parser = Parser::new(String::from("x RSHIFT 2 -> y"));
-        let rshift_good = parser.next_operation();
+        let rshift_good = parser.next_operation().unwrap();
         assert_eq!(
             rshift_good,
             Expression::Assign(
@@ -1137,7 +1154,7 @@ mod tests {
         );
 
         let mut parser = Parser::new(String::from("x LSHIFT 3 -> ly"));
-        let lshift_good = parser.next_operation();
+        let lshift_good = parser.next_operation().unwrap();
         assert_eq!(
             lshift_good,
             Expression::Assign(
@@ -1151,7 +1168,7 @@ mod tests {
         );
 
         let mut parser = Parser::new(String::from("x AND y -> ay"));
-        let and_good = parser.next_operation();
+        let and_good = parser.next_operation().unwrap();
         assert_eq!(
             and_good,
             Expression::Assign(
@@ -1165,7 +1182,7 @@ mod tests {
         );
 
         let mut parser = Parser::new(String::from("y OR x -> oy"));
-        let or_good = parser.next_operation();
+        let or_good = parser.next_operation().unwrap();
         assert_eq!(
             or_good,
             Expression::Assign(
@@ -1179,7 +1196,7 @@ mod tests {
         );
 
         let mut parser = Parser::new(String::from("NOT y -> ny"));
-        let not_good = parser.next_operation();
+        let not_good = parser.next_operation().unwrap();
         assert_eq!(
             not_good,
             Expression::Assign(
@@ -1190,22 +1207,22 @@ mod tests {
 
         let mut parser = Parser::new(String::from("2 LSHIFT 1 -> z"));
         let lshift = parser.next_operation();
-        assert_eq!(lshift, Expression::NOP); // constants are not supported
+        assert!(lshift.is_err()); // constants are not supported
 
         let mut parser = Parser::new(String::from("3 AND 1 -> w"));
         let and = parser.next_operation();
-        assert_eq!(and, Expression::NOP); // constants are not supported
+        assert!(and.is_err()); // constants are not supported
 
         let mut parser = Parser::new(String::from("3 OR 1 -> v"));
         let or = parser.next_operation();
-        assert_eq!(or, Expression::NOP); // constants are not supported
+        assert!(or.is_err()); // constants are not supported
 
         let mut parser = Parser::new(String::from("4 RSHIFT 2 -> u"));
         let rshift_bad = parser.next_operation();
-        assert_eq!(rshift_bad, Expression::NOP); // constants are not supported
+        assert!(rshift_bad.is_err()); // constants are not supported
 
         let mut parser = Parser::new(String::from("x -> t"));
-        let assign_bad = parser.next_operation();
+        let assign_bad = parser.next_operation().unwrap();
         assert_eq!(
             assign_bad,
             Expression::Assign(
@@ -1216,50 +1233,84 @@ mod tests {
 
         let mut parser = Parser::new(String::from("8 RSHIFT x -> t"));
         let rshift_x = parser.next_operation();
-        assert_eq!(rshift_x, Expression::NOP); // constants are not supported
+        assert!(rshift_x.is_err()); // constants are not supported
 
         let mut parser = Parser::new(String::from("2 RSHIFT -> s"));
         let rshift_s = parser.next_operation();
-        assert_eq!(rshift_s, Expression::NOP);
+        assert!(rshift_s.is_err());
 
         let mut parser = Parser::new(String::from("RSHIFT -> r"));
         let rshift_empty = parser.next_operation();
-        assert_eq!(rshift_empty, Expression::NOP);
+        assert!(rshift_empty.is_err());
 
         let mut parser = Parser::new(String::from("RSHIFT AND OR LSHIFT -> q"));
         let token_mess = parser.next_operation();
-        assert_eq!(token_mess, Expression::NOP);
+        assert!(token_mess.is_err());
 
         let mut parser = Parser::new(String::from("2 NOT -> p"));
         let not = parser.next_operation();
-        assert_eq!(not, Expression::NOP);
+        assert!(not.is_err());
 
         let mut parser = Parser::new(String::from("2 NOT NOT -> p"));
         let double_not = parser.next_operation();
-        assert_eq!(double_not, Expression::NOP);
+        assert!(double_not.is_err());
 
         let mut parser = Parser::new(String::from("2 -> 2"));
         let lvalue = parser.next_operation();
-        assert_eq!(lvalue, Expression::NOP);
+        assert!(lvalue.is_err());
 
         let mut parser = Parser::new(String::from("-> x"));
         let empty = parser.next_operation();
-        assert_eq!(empty, Expression::NOP);
+        assert!(empty.is_err());
 
         let nop = parser.next_operation();
-        assert_eq!(nop, Expression::NOP);
+        assert_eq!(nop, Ok(Expression::NOP));
         assert!(!parser.parsing, "Parsing is not stopped");
     }
 
+    #[test]
+    fn test_parser_error_position() {
+        let mut parser = Parser::new(String::from("123 -> x\n  456 -> y\n  3 AND 1 -> w"));
+        assert!(parser.next_operation().is_ok());
+        assert!(parser.next_operation().is_ok());
+        assert_eq!(
+            parser.next_operation(),
+            Err(ParseError::new(3, 3, "3 AND 1 -> w", "unsupported command"))
+        );
+
+        let mut parser = Parser::new(String::from("123 -> x\nx AND y"));
+        assert!(parser.next_operation().is_ok());
+        assert_eq!(
+            parser.next_operation(),
+            Err(ParseError::new(
+                2,
+                1,
+                "x AND y",
+                "expected \"->\" and a wire"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_missing_wire() {
+        assert_eq!(
+            count_input_a("1 -> b\nb AND d -> a"),
+            Err(ParseError::input("the wire d doesn't have a command"))
+        );
+        assert!(count_input_a("1 -> b\n3 OR 1 -> a").is_err());
+    }
+
     #[test]
     fn test_interpreter() {
         let mut bobby = BobbyInterpreter::new();
-        bobby.interpret(String::from(
-            r#"NOT a -> b
+        bobby
+            .interpret(String::from(
+                r#"NOT a -> b
         b AND d -> e
         1 -> a
         a -> d"#,
-        ));
+            ))
+            .unwrap();
         let answer = bobby.evaluate(&String::from("e"));
         assert_eq!(answer.unwrap(), 0);
     }
@@ -1270,7 +1321,7 @@ mod tests {
         b AND d -> e
         NOT e -> a
         3 -> d"#;
-        assert_eq!(count_input_a(INPUT), 65534);
-        assert_eq!(count_input_a_override(INPUT), 65533);
+        assert_eq!(count_input_a(INPUT), Ok(65534));
+        assert_eq!(count_input_a_override(INPUT), Ok(65533));
     }
 }
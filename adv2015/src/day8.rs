@@ -1,16 +1,17 @@
+use advparse::{parse_lines, ParseError};
 use std::char;
 
-pub fn calc_difference(input: &str) -> usize {
+pub fn calc_difference(input: &str) -> Result<usize, ParseError> {
     let literals = input
         .lines()
         .fold(0, |acc, line| acc + count_literals(line));
 
-    let chars = input.lines().fold(0, |acc, line| acc + count_chars(line));
+    let chars: usize = parse_lines(input, count_chars)?.iter().sum();
 
-    literals - chars
+    Ok(literals - chars)
 }
 
-pub fn calc_new_difference(input: &str) -> usize {
+pub fn calc_new_difference(input: &str) -> Result<usize, ParseError> {
     let literals = input
         .lines()
         .fold(0, |acc, line| acc + count_literals(line));
@@ -19,15 +20,15 @@ pub fn calc_new_difference(input: &str) -> usize {
         .lines()
         .fold(0, |acc, line| acc + count_escaped_chars(line));
 
-    chars - literals
+    Ok(chars - literals)
 }
 
 fn count_literals(input: &str) -> usize {
     input.chars().count()
 }
 
-fn count_chars(input: &str) -> usize {
-    unescape(input).chars().count()
+fn count_chars(input: &str) -> Result<usize, ParseError> {
+    Ok(unescape(input)?.chars().count())
 }
 
 fn count_escaped_chars(input: &str) -> usize {
@@ -36,15 +37,15 @@ fn count_escaped_chars(input: &str) -> usize {
 
 // Info: this code was taken from the crate "snailquote". It wasn't stolen for sale, but for
 // education purposes only. Just to solve Advent Calendar 2015
-fn unescape(input: &str) -> String {
+fn unescape(input: &str) -> Result<String, ParseError> {
     let mut in_single_quote = false;
     let mut in_double_quote = false;
 
-    let mut chars = input.chars();
+    let mut chars = input.char_indices();
 
     let mut res = String::with_capacity(input.len());
 
-    while let Some(c) = chars.next() {
+    while let Some((i, c)) = chars.next() {
         if in_single_quote {
             if c == '\'' {
                 in_single_quote = false;
@@ -57,21 +58,26 @@ fn unescape(input: &str) -> String {
             }
 
             if c == '\\' {
-                match chars.next() {
-                    None => return String::new(),
-                    Some(c2) => {
-                        res.push(match c2 {
-                            '\\' => '\\',
-                            '\'' => '\'',
-                            '"' => '"',
-                            'x' => parse_unicode(&mut chars),
-                            _ => {
-                                return String::new();
-                            }
-                        });
-                        continue;
+                let escaped = match chars.next() {
+                    Some((_, '\\')) => Some('\\'),
+                    Some((_, '\'')) => Some('\''),
+                    Some((_, '"')) => Some('"'),
+                    Some((_, 'x')) => parse_unicode(&mut chars),
+                    _ => None,
+                };
+
+                match escaped {
+                    Some(escaped) => res.push(escaped),
+                    None => {
+                        let end = chars.next().map_or(input.len(), |(end, _)| end);
+                        return Err(ParseError::in_line(
+                            input,
+                            &input[i..end],
+                            "unknown escape sequence",
+                        ));
                     }
                 }
+                continue;
             }
         } else if c == '\'' {
             in_single_quote = true;
@@ -84,7 +90,7 @@ fn unescape(input: &str) -> String {
         res.push(c);
     }
 
-    res
+    Ok(res)
 }
 
 fn escape(input: &str) -> String {
@@ -106,19 +112,14 @@ fn escape(input: &str) -> String {
     res
 }
 
-fn parse_unicode<I>(chars: &mut I) -> char
+fn parse_unicode<I>(chars: &mut I) -> Option<char>
 where
-    I: Iterator<Item = char>,
+    I: Iterator<Item = (usize, char)>,
 {
-    let c1 = chars.next().unwrap();
-    let c2 = chars.next().unwrap();
-
-    let d1 = c1.to_digit(16).unwrap();
-    let d2 = c2.to_digit(16).unwrap();
-
-    let hex = d1 * 16 + d2;
+    let d1 = chars.next()?.1.to_digit(16)?;
+    let d2 = chars.next()?.1.to_digit(16)?;
 
-    char::from_u32(hex).unwrap()
+    char::from_u32(d1 * 16 + d2)
 }
 
 #[cfg(test)]
@@ -135,18 +136,32 @@ mod tests {
 
     #[test]
     fn test_unescape() {
-        assert_eq!(unescape("\"\""), "");
-        assert_eq!(unescape("\"abc\""), "abc");
-        assert_eq!(unescape("\"aaa\\\"aaa\""), "aaa\"aaa");
-        assert_eq!(unescape("\"\\x27\""), "'");
+        assert_eq!(unescape("\"\"").unwrap(), "");
+        assert_eq!(unescape("\"abc\"").unwrap(), "abc");
+        assert_eq!(unescape("\"aaa\\\"aaa\"").unwrap(), "aaa\"aaa");
+        assert_eq!(unescape("\"\\x27\"").unwrap(), "'");
+    }
+
+    #[test]
+    fn test_unescape_wrong_sequence() {
+        assert_eq!(
+            unescape("\"ab\\q\""),
+            Err(ParseError::new(0, 4, "\\q", "unknown escape sequence"))
+        );
+        assert!(unescape("\"\\x2").is_err());
+        assert!(unescape("\"\\xzz\"").is_err());
+        assert_eq!(
+            calc_difference("\"abc\"\n\"a\\q\""),
+            Err(ParseError::new(2, 3, "\\q", "unknown escape sequence"))
+        );
     }
 
     #[test]
     fn test_count_chars() {
-        assert_eq!(count_chars("\"\""), 0);
-        assert_eq!(count_chars("\"abc\""), 3);
-        assert_eq!(count_chars("\"aaa\\\"aaa\""), 7);
-        assert_eq!(count_chars("\"\\x27\""), 1);
+        assert_eq!(count_chars("\"\""), Ok(0));
+        assert_eq!(count_chars("\"abc\""), Ok(3));
+        assert_eq!(count_chars("\"aaa\\\"aaa\""), Ok(7));
+        assert_eq!(count_chars("\"\\x27\""), Ok(1));
     }
 
     #[test]
@@ -180,13 +195,13 @@ mod tests {
 
     #[test]
     fn test_calc_difference() {
-        assert_eq!(calc_difference("\"aaa\\\"aaa\""), 3);
-        assert_eq!(calc_difference("\'aaa\\\'aaa\'"), 3);
+        assert_eq!(calc_difference("\"aaa\\\"aaa\""), Ok(3));
+        assert_eq!(calc_difference("\'aaa\\\'aaa\'"), Ok(3));
     }
 
     #[test]
     fn test_calc_new_difference() {
-        assert_eq!(calc_new_difference("\"aaa\\\"aaa\""), 6);
-        assert_eq!(calc_new_difference("\'aaa\\\'aaa\'"), 6);
+        assert_eq!(calc_new_difference("\"aaa\\\"aaa\""), Ok(6));
+        assert_eq!(calc_new_difference("\'aaa\\\'aaa\'"), Ok(6));
     }
 }
@@ -1,28 +1,27 @@
+use advparse::{expect_token, next_token, parse_lines, parse_number, ParseError};
 use permute::permutations_of;
 use std::cmp;
 use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
-pub fn calc_shortest(input: &str) -> usize {
+pub fn calc_shortest(input: &str) -> Result<usize, ParseError> {
     let mut santa = Santa::new();
 
-    input.lines().for_each(|line| {
-        let path = parse_to_path(line);
+    for path in parse_lines(input, parse_to_path)? {
         santa.add_path(&path);
-    });
+    }
 
-    santa.find_path().0
+    Ok(santa.find_path()?.0)
 }
 
-pub fn calc_longest(input: &str) -> usize {
+pub fn calc_longest(input: &str) -> Result<usize, ParseError> {
     let mut santa = Santa::new();
 
-    input.lines().for_each(|line| {
-        let path = parse_to_path(line);
+    for path in parse_lines(input, parse_to_path)? {
         santa.add_path(&path);
-    });
+    }
 
-    santa.find_path().1
+    Ok(santa.find_path()?.1)
 }
 
 struct Santa {
@@ -51,7 +50,7 @@ impl Santa {
             .insert((city2.clone(), city1.clone()), *distance);
     }
 
-    fn find_path(&self) -> (usize, usize) {
+    fn find_path(&self) -> Result<(usize, usize), ParseError> {
         let vector: Vec<&String> = Vec::from_iter(&self.cities);
         let mut min_distance = usize::max_value();
         let mut max_distance = usize::min_value();
@@ -68,7 +67,12 @@ impl Santa {
                 let path = *self
                     .distances
                     .get(&(prev_city.clone(), (*city).clone()))
-                    .unwrap();
+                    .ok_or_else(|| {
+                        ParseError::input(format!(
+                            "there is no distance between {} and {}",
+                            prev_city, city
+                        ))
+                    })?;
                 prev_city = (*city).clone();
 
                 current_distance += path;
@@ -78,18 +82,21 @@ impl Santa {
             max_distance = cmp::max(max_distance, current_distance);
         }
 
-        (min_distance, max_distance)
+        Ok((min_distance, max_distance))
     }
 }
 
-fn parse_to_path(input: &str) -> Path {
-    let split: Vec<&str> = input.split_whitespace().collect();
+// London to Dublin = 464
+fn parse_to_path(input: &str) -> Result<Path, ParseError> {
+    let mut split = input.split_whitespace();
+
+    let city1 = next_token(input, &mut split, "a city")?;
+    expect_token(input, &mut split, "to")?;
+    let city2 = next_token(input, &mut split, "a city")?;
+    expect_token(input, &mut split, "=")?;
+    let distance = parse_number(input, next_token(input, &mut split, "a distance")?)?;
 
-    Path(
-        split[0].to_string(),
-        split[2].to_string(),
-        split[4].parse::<usize>().unwrap(),
-    )
+    Ok(Path(city1.to_string(), city2.to_string(), distance))
 }
 
 #[cfg(test)]
@@ -99,7 +106,7 @@ mod tests {
     #[test]
     fn test_parse_to_path() {
         let input = "London to Dublin = 464";
-        let Path(city1, city2, distance) = parse_to_path(input);
+        let Path(city1, city2, distance) = parse_to_path(input).unwrap();
         assert_eq!(city1, "London");
         assert_eq!(city2, "Dublin");
         assert_eq!(distance, 464);
@@ -113,14 +120,14 @@ mod tests {
          Dublin to Belfast = 141"
             .lines()
             .for_each(|line| {
-                let path = parse_to_path(line);
+                let path = parse_to_path(line).unwrap();
                 santa.add_path(&path);
             });
 
         assert_eq!(santa.cities.len(), 3);
         assert_eq!(santa.distances.len(), 6);
 
-        assert_eq!(santa.find_path(), (605, 982));
+        assert_eq!(santa.find_path(), Ok((605, 982)));
 
         for i in santa.distances {
             println!("{} {} = {}", (i.0).0, (i.0).1, i.1)
@@ -132,7 +139,20 @@ mod tests {
         const INPUT: &str = "London to Dublin = 464
          London to Belfast = 518
          Dublin to Belfast = 141";
-        assert_eq!(calc_shortest(INPUT), 605);
-        assert_eq!(calc_longest(INPUT), 982);
+        assert_eq!(calc_shortest(INPUT), Ok(605));
+        assert_eq!(calc_longest(INPUT), Ok(982));
+    }
+
+    #[test]
+    fn test_wrong_routes() {
+        assert_eq!(
+            calc_shortest("London to Dublin = 464\nLondon to Belfast 518"),
+            Err(ParseError::new(2, 19, "518", "expected \"=\""))
+        );
+        assert_eq!(
+            calc_shortest("London to Dublin ="),
+            Err(ParseError::new(1, 19, "", "expected a distance"))
+        );
+        assert!(calc_longest("London to Dublin = 464\nLondon to Belfast = 518").is_err());
     }
 }
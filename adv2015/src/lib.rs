@@ -26,165 +26,235 @@ mod day8;
 mod day9;
 /* </editor-fold> */
 
+use advcore::{unsolved, Answer, Day, ParsedDay, Registry, SolveError};
+use advrender::{Animator, Logger, Renderer};
+
+pub const YEAR: u16 = 2015;
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::new(
+        YEAR,
+        1,
+        |input| day1::count_brackets(input).map(Into::into),
+        |input| day1::count_position(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        2,
+        |input| day2::calc_packs(input).map(Into::into),
+        |input| day2::calc_ribbons(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        3,
+        |input| day3::count_houses(input).map(Into::into),
+        |input| day3::count_houses_together(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        4,
+        |input| day4::mine_suffix(input, 5).map(Into::into),
+        |input| day4::mine_suffix(input, 6).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        5,
+        |input| day5::count_nice_lines(input).map(Into::into),
+        |input| day5::count_nice_lines_advanced(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        6,
+        |input| day6::count_bulbs(input).map(Into::into),
+        |input| day6::count_brightness(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        7,
+        |input| day7::count_input_a(input).map(Into::into),
+        |input| day7::count_input_a_override(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        8,
+        |input| day8::calc_difference(input).map(Into::into),
+        |input| day8::calc_new_difference(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        9,
+        |input| day9::calc_shortest(input).map(Into::into),
+        |input| day9::calc_longest(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        10,
+        |input| day10::calc_first(input).map(Into::into),
+        |input| day10::calc_second(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        11,
+        |input| day11::get_expired_once(input).map(Into::into),
+        |input| day11::get_expired_twice(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        12,
+        |input| day12::get_answer(input).map(Into::into),
+        |input| day12::get_answer_without_red(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        13,
+        |input| day13::get_answer(input).map(Into::into),
+        |input| day13::get_answer_with_me(input).map(Into::into),
+    ));
+    registry.register(Day::with_params(
+        YEAR,
+        14,
+        &[day14::TOTAL_TIME],
+        |input, params| day14::get_answer(input, params).map(Into::into),
+        |input, params| day14::get_answer_points(input, params).map(Into::into),
+    ));
+    registry.register(Day::with_params(
+        YEAR,
+        15,
+        &[day15::TOTAL_CALORIES],
+        |input, _| day15::get_answer(input).map(Into::into),
+        |input, params| day15::get_answer_with_calories(input, params).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        16,
+        |input| day16::get_answer(input).map(Into::into),
+        |input| day16::get_answer_from_retroencabulator(input).map(Into::into),
+    ));
+    registry.register(Day::with_params(
+        YEAR,
+        17,
+        &[day17::REQUIRED_VOLUME],
+        |input, params| day17::get_total_count_of_combinations(input, params).map(Into::into),
+        |input, params| day17::get_minimal_count_of_cans(input, params).map(Into::into),
+    ));
+    registry.register(Day::with_params(
+        YEAR,
+        18,
+        &[day18::SIZE, day18::COUNT],
+        |input, params| day18::get_answer_normal(input, params).map(Into::into),
+        |input, params| day18::get_answer_broken(input, params).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        19,
+        |input| day19::count_unique_molecules(input).map(Into::into),
+        |input| day19::count_molecules_from_electron(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        20,
+        |input| day20::get_richest_house_before_strike(input).map(Into::into),
+        |input| day20::get_richest_house_after_strike(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        21,
+        |input| day21::find_cheapest_warrior(input).map(Into::into),
+        |input| day21::find_expensive_loser(input).map(Into::into),
+    ));
+    registry.register(Day::with_params(
+        YEAR,
+        22,
+        &[day22::HIT_POINTS, day22::MANA],
+        |input, params| day22::find_easy_result(input, params).map(Into::into),
+        |input, params| day22::find_hard_result(input, params).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        23,
+        |input| day23::get_answer_b(input).map(Into::into),
+        |input| day23::get_answer_b_after_a(input).map(Into::into),
+    ));
+    registry.register(ParsedDay::new(
+        YEAR,
+        24,
+        day24::parse_packages,
+        |packages| day24::balance_in_groups(packages, 3).map(Into::into),
+        |packages| day24::balance_in_groups(packages, 4).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        25,
+        |input| day25::find_code(input).map(Into::into),
+        unsolved,
+    ));
+}
+
+// The days, which can be drawn
+pub fn renderers() -> Vec<(u8, Renderer)> {
+    vec![(6, |input, _| day6::render(input)), (18, day18::render)]
+}
+
+// The days, which can be animated in the terminal
+pub fn animators() -> Vec<(u8, Animator)> {
+    vec![(3, |input, _| day3::animate(input)), (18, day18::animate)]
+}
+
+// The days, which can print their log
+pub fn loggers() -> Vec<(u8, Logger)> {
+    Vec::new()
+}
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    register(&mut registry);
+
+    registry
+}
+
+pub fn solve(day: u8, input: &str) -> Result<(Answer, Answer), SolveError> {
+    registry().solve(YEAR, day, input)
+}
+
 pub fn print_answers(day: u8, input: String) {
-    // Trim is mandatory for one-line inputs
-    let input = input.as_str().trim();
-    let prefix = format!("Day {} / 2015:", day);
-    match day {
-        1 => println!(
-            "{} {} and {}",
-            prefix,
-            day1::count_brackets(input),
-            day1::count_position(input).unwrap_or(-1)
-        ),
-        2 => println!(
-            "{} {} and {}",
-            prefix,
-            day2::calc_packs(input),
-            day2::calc_ribbons(input)
-        ),
-        3 => println!(
-            "{} {} and {}",
-            prefix,
-            day3::count_houses(input),
-            day3::count_houses_together(input)
-        ),
-        4 => println!(
-            "{} {} and {}",
-            prefix,
-            day4::mine_suffix(input, 5),
-            day4::mine_suffix(input, 6)
-        ),
-        5 => println!(
-            "{} {} and {}",
-            prefix,
-            day5::count_nice_lines(input),
-            day5::count_nice_lines_advanced(input)
-        ),
-        6 => println!(
-            "{} {} and {}",
-            prefix,
-            day6::count_bulbs(input),
-            day6::count_brightness(input)
-        ),
-        7 => println!(
-            "{} {} and {}",
-            prefix,
-            day7::count_input_a(input),
-            day7::count_input_a_override(input)
-        ),
-        8 => println!(
-            "{} {} and {}",
-            prefix,
-            day8::calc_difference(input),
-            day8::calc_new_difference(input)
-        ),
-        9 => println!(
-            "{} {} and {}",
-            prefix,
-            day9::calc_shortest(input),
-            day9::calc_longest(input)
-        ),
-        10 => println!(
-            "{} {} and {}",
-            prefix,
-            day10::calc_first(input),
-            day10::calc_second(input)
-        ),
-        11 => println!(
-            "{} {} and {}",
-            prefix,
-            day11::get_expired_once(input),
-            day11::get_expired_twice(input)
-        ),
-        12 => println!(
-            "{} {} and {}",
-            prefix,
-            day12::get_answer(input),
-            day12::get_answer_without_red(input)
-        ),
-        13 => println!(
-            "{} {} and {}",
-            prefix,
-            day13::get_answer(input),
-            day13::get_answer_with_me(input)
-        ),
-        14 => println!(
-            "{} {} and {}",
-            prefix,
-            day14::get_answer(input),
-            day14::get_answer_points(input)
-        ),
-        15 => println!(
-            "{} {} and {}",
-            prefix,
-            day15::get_answer(input),
-            day15::get_answer_with_calories(input)
-        ),
-        16 => println!(
-            "{} {} and {}",
-            prefix,
-            day16::get_answer(input),
-            day16::get_answer_from_retroencabulator(input)
-        ),
-        17 => println!(
-            "{} {} and {}",
-            prefix,
-            day17::get_total_count_of_combinations(input),
-            day17::get_minimal_count_of_cans(input)
-        ),
-        18 => println!(
-            "{} {} and {}",
-            prefix,
-            day18::get_answer_normal(input),
-            day18::get_answer_broken(input)
-        ),
-        19 => println!(
-            "{} {} and {}",
-            prefix,
-            day19::count_unique_molecules(input),
-            day19::count_molecules_from_electron(input)
-        ),
-        20 => println!(
-            "{} {} and {}",
-            prefix,
-            day20::get_richest_house_before_strike(input),
-            day20::get_richest_house_after_strike(input)
-        ),
-        21 => println!(
-            "{} {} and {}",
-            prefix,
-            day21::find_cheapest_warrior(input),
-            day21::find_expensive_loser(input)
-        ),
-        22 => println!(
-            "{} {} and {}",
-            prefix,
-            day22::find_easy_result(input),
-            day22::find_hard_result(input)
-        ),
-        23 => println!(
-            "{} {} and {}",
-            prefix,
-            day23::get_answer_b(input),
-            day23::get_answer_b_after_a(input)
-        ),
-        24 => println!(
-            "{} {} and {}",
-            prefix,
-            day24::find_answer(input),
-            day24::find_answer_better(input)
-        ),
-        25 => println!("{} {}", prefix, day25::find_code(input)),
-        _ => {
-            eprintln!("2015: I don't know the answer for day {} :(", day)
-        }
-    }
+    registry().print_answers(YEAR, day, input.as_str());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advparse::ParseError;
+
+    #[test]
+    fn test_registry() {
+        let registry = registry();
+
+        assert_eq!(registry.len(), 25);
+        assert_eq!(registry.years(), vec![YEAR]);
+        assert!(registry.get(YEAR, 1).is_some());
+        assert!(registry.get(YEAR, 26).is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve(1, "()())\n"),
+            Ok((Answer::Signed(-1), Answer::Signed(5)))
+        );
+        assert_eq!(
+            solve(25, "row 1, column 1"),
+            Ok((Answer::Unsigned(20151125), Answer::Nothing))
+        );
+        assert_eq!(solve(26, ""), Err(SolveError::UnknownDay(YEAR, 26)));
+        assert_eq!(
+            solve(1, "(()x"),
+            Err(SolveError::Input(
+                YEAR,
+                1,
+                ParseError::new(1, 4, "x", "expected a bracket")
+            ))
+        );
+    }
 
     #[test]
     fn test_print_answers() {
@@ -1,53 +1,46 @@
-use combinations::Combinations;
+use advcore::{Param, Params};
+use advparse::{parse_numbers, ParseError};
+use itertools::Itertools;
 
-const DESIRED_SUM: usize = 2020;
+pub const DESIRED_SUM: Param = Param::new("sum", "2020", "the sum of the entries");
 
-pub fn find_expenses(input: &str) -> usize {
-    let input = parse_input(input);
-    let result = find_pair_with_sum(input, DESIRED_SUM);
+pub fn find_expenses(input: &str, params: &Params) -> Result<usize, ParseError> {
+    let input = parse_input(input)?;
+    let desired_sum = params.get(&DESIRED_SUM)?;
+    let result = find_pair_with_sum(input, desired_sum)
+        .ok_or_else(|| ParseError::input(format!("no two entries sum to {}", desired_sum)))?;
 
-    result.0 * result.1
+    Ok(result.0 * result.1)
 }
 
-pub fn find_more_expenses(input: &str) -> usize {
-    let input = parse_input(input);
-    let result = find_triple_with_sum(input, DESIRED_SUM);
+pub fn find_more_expenses(input: &str, params: &Params) -> Result<usize, ParseError> {
+    let input = parse_input(input)?;
+    let desired_sum = params.get(&DESIRED_SUM)?;
+    let result = find_triple_with_sum(input, desired_sum)
+        .ok_or_else(|| ParseError::input(format!("no three entries sum to {}", desired_sum)))?;
 
-    result.0 * result.1 * result.2
+    Ok(result.0 * result.1 * result.2)
 }
 
-fn parse_input(input: &str) -> Vec<usize> {
-    input
-        .lines()
-        .map(|l| l.trim())
-        .filter_map(|n| n.parse::<usize>().ok())
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<usize>, ParseError> {
+    parse_numbers(input)
 }
 
-fn find_pair_with_sum(nums: Vec<usize>, desired_sum: usize) -> (usize, usize) {
-    if nums.is_empty() {
-        return (0, 0);
-    }
-
-    let mut computed = Combinations::new(nums, 2);
-    if let Some(result) = computed.find(|pair| pair[0] + pair[1] == desired_sum) {
-        return (result[0], result[1]);
-    }
+// The entries are sorted, so are the found ones
+fn find_pair_with_sum(mut nums: Vec<usize>, desired_sum: usize) -> Option<(usize, usize)> {
+    nums.sort_unstable();
 
-    return (0, 0);
+    nums.into_iter()
+        .tuple_combinations()
+        .find(|&(a, b)| a.checked_add(b) == Some(desired_sum))
 }
 
-fn find_triple_with_sum(nums: Vec<usize>, desired_sum: usize) -> (usize, usize, usize) {
-    if nums.is_empty() {
-        return (0, 0, 0);
-    }
-
-    let mut computed = Combinations::new(nums, 3);
-    if let Some(result) = computed.find(|pair| pair[0] + pair[1] + pair[2] == desired_sum) {
-        return (result[0], result[1], result[2]);
-    }
+fn find_triple_with_sum(mut nums: Vec<usize>, desired_sum: usize) -> Option<(usize, usize, usize)> {
+    nums.sort_unstable();
 
-    return (0, 0, 0);
+    nums.into_iter()
+        .tuple_combinations()
+        .find(|&(a, b, c)| a.checked_add(b).and_then(|sum| sum.checked_add(c)) == Some(desired_sum))
 }
 
 #[cfg(test)]
@@ -62,31 +55,54 @@ mod tests {
                    299
                    675
                    1456"#;
-        let result = parse_input(input);
+        let result = parse_input(input).unwrap();
         assert_eq!(result.len(), 6);
         assert_eq!(result[0], 1721);
         assert_eq!(result[4], 675);
+
+        assert_eq!(
+            parse_input("1721\n97g"),
+            Err(ParseError::new(2, 1, "97g", "expected a number"))
+        );
     }
 
     #[test]
     fn test_find_pair_with_sum() {
-        let result = find_pair_with_sum(vec![1721, 979, 366, 299, 675, 1456], DESIRED_SUM);
-        assert_eq!(result.1, 1721);
-        assert_eq!(result.0, 299);
+        let result = find_pair_with_sum(vec![1721, 979, 366, 299, 675, 1456], 2020);
+        assert_eq!(result, Some((299, 1721)));
     }
 
     #[test]
     fn test_find_no_pair_with_sum() {
-        let result = find_pair_with_sum(vec![1, 979, 2, 299, 675, 3], DESIRED_SUM);
-        assert_eq!(result.1, 0);
-        assert_eq!(result.0, 0);
+        let result = find_pair_with_sum(vec![1, 979, 2, 299, 675, 3], 2020);
+        assert_eq!(result, None);
     }
     #[test]
     fn test_find_triple_with_sum() {
-        let result = find_triple_with_sum(vec![1, 979, 2, 299, 675, 3], DESIRED_SUM);
-        assert_eq!(result.2, 0);
-        assert_eq!(result.1, 0);
-        assert_eq!(result.0, 0);
+        let result = find_triple_with_sum(vec![1, 979, 2, 299, 675, 3], 2020);
+        assert_eq!(result, None);
+        assert!(find_more_expenses("1\n979", &Params::new()).is_err());
+        assert_eq!(find_triple_with_sum(vec![2, 1, 3], 6), Some((1, 2, 3)));
+    }
+
+    #[test]
+    fn test_few_entries() {
+        let params = Params::new();
+
+        assert_eq!(
+            find_pair_with_sum(vec![1010, 1010], 2020),
+            Some((1010, 1010))
+        );
+        assert_eq!(find_pair_with_sum(vec![2020], 2020), None);
+        assert_eq!(
+            find_expenses("1\n2", &params),
+            Err(ParseError::input("no two entries sum to 2020"))
+        );
+        assert_eq!(
+            find_more_expenses("1\n\n2", &params),
+            Err(ParseError::input("no three entries sum to 2020"))
+        );
+        assert!(find_expenses("18446744073709551615\n1", &params).is_err());
     }
 
     #[test]
@@ -98,9 +114,10 @@ mod tests {
                    366
                    299
                    675
-                   1456"#
+                   1456"#,
+                &Params::new()
             ),
-            514579
+            Ok(514579)
         );
     }
 
@@ -113,9 +130,20 @@ mod tests {
                    366
                    299
                    675
-                   1456"#
+                   1456"#,
+                &Params::new()
             ),
-            241861950
+            Ok(241861950)
         );
     }
+
+    #[test]
+    fn test_desired_sum() {
+        let mut params = Params::new();
+        params.set("sum", "1345");
+        assert_eq!(find_expenses("1721\n979\n366", &params), Ok(358314));
+
+        params.set("sum", "-1");
+        assert!(find_expenses("1721\n979\n366", &params).is_err());
+    }
 }
@@ -1,31 +1,33 @@
+use advparse::{parse_numbers, ParseError};
 use itertools::Itertools;
 
-pub fn find_one_by_three(input: &str) -> usize {
-    let input: Vec<usize> = parse_as_sorted(input);
-    let OneTwo(ones, threes) = group_by_difference(&input);
+pub fn one_by_three(input: &[usize]) -> usize {
+    let OneTwo(ones, threes) = group_by_difference(input);
 
     ones * threes
 }
 
-pub fn find_all_combinations(input: &str) -> usize {
-    let input: Vec<usize> = parse_as_sorted(input);
-    count_possible_valid_chains(&input)
-}
-
-fn parse_as_sorted(input: &str) -> Vec<usize> {
-    let mut result: Vec<usize> = input
-        .lines()
-        .map(&str::trim)
-        .map(&str::parse::<usize>)
-        .filter_map(Result::ok)
-        .collect();
+pub fn parse_as_sorted(input: &str) -> Result<Vec<usize>, ParseError> {
+    let mut result: Vec<usize> = parse_numbers(input)?;
 
     // there is always a jolt with 0 and highest + 3 (according to the exercise description)
     result.push(0);
     result.sort();
-    result.push(result.last().unwrap() + 3);
+    let highest = result.last().copied().unwrap_or_default();
+    result.push(
+        highest
+            .checked_add(3)
+            .ok_or_else(|| ParseError::input(format!("the adapter {} is too high", highest)))?,
+    );
+
+    if let Some(pair) = result.windows(2).find(|pair| !check_jolt(pair)) {
+        return Err(ParseError::input(format!(
+            "the adapters {} and {} can't be chained",
+            pair[0], pair[1]
+        )));
+    }
 
-    result
+    Ok(result)
 }
 
 struct OneTwo(usize, usize);
@@ -70,26 +72,29 @@ fn is_chain_valid(chain: &[usize]) -> bool {
     chain.windows(2).find(|pair| !check_jolt(pair)).is_none()
 }
 
-fn count_possible_valid_chains(chain: &[usize]) -> usize {
+pub fn count_possible_valid_chains(chain: &[usize]) -> usize {
     let mut one_diffs: Vec<Vec<usize>> = vec![vec![]];
 
     // creates a vector with jolts, with the difference equal to 1:
     // one_diffs: [[0, 1], [4, 5, 6, 7], [10, 11, 12], [15, 16], [19], [22]]
     chain.windows(2).for_each(|window| {
-        let diff = window[1] - window[0];
-        let last = one_diffs.last_mut().unwrap();
-        last.push(window[0]);
+        let diff = window[1].saturating_sub(window[0]);
+        if let Some(last) = one_diffs.last_mut() {
+            last.push(window[0]);
+        }
         if diff == 3 {
             one_diffs.push(vec![]);
         }
     });
-    one_diffs.last_mut().unwrap().push(*chain.last().unwrap());
+    if let (Some(last), Some(&jolt)) = (one_diffs.last_mut(), chain.last()) {
+        last.push(jolt);
+    }
 
     one_diffs
         .iter()
         .map(|joins| {
             let length = joins.len();
-            if length == 1 || length == 2 {
+            if length <= 2 {
                 return 1;
             } else if length == 3 {
                 return 2;
@@ -138,19 +143,35 @@ mod tests {
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_one_by_three(""), 0);
-        assert_eq!(find_all_combinations(""), 1);
+        assert_eq!(one_by_three(&parse_as_sorted("").unwrap()), 0);
+        assert_eq!(
+            count_possible_valid_chains(&parse_as_sorted("").unwrap()),
+            1
+        );
     }
 
     #[test]
     fn test_parse_as_sorted() {
-        let result = parse_as_sorted("3 \n 1\n2\n\n");
+        let result = parse_as_sorted("3 \n 1\n2\n\n").unwrap();
         assert_eq!(result.len(), 5);
         assert_eq!(result[0], 0);
         assert_eq!(result[1], 1);
         assert_eq!(result[2], 2);
         assert_eq!(result[3], 3);
         assert_eq!(result[4], 6);
+
+        assert!(parse_as_sorted("1\n2\n2").is_err());
+        assert!(parse_as_sorted("1\n5").is_err());
+        assert_eq!(
+            parse_as_sorted("18446744073709551615"),
+            Err(ParseError::input(
+                "the adapter 18446744073709551615 is too high"
+            ))
+        );
+        assert_eq!(
+            parse_as_sorted("1\n 2x"),
+            Err(ParseError::new(2, 2, "2x", "expected a number"))
+        );
     }
 
     #[test]
@@ -162,8 +183,8 @@ mod tests {
 
     #[test]
     fn test_find_one_by_three() {
-        assert_eq!(find_one_by_three(SHORT_INPUT), 7 * 5);
-        assert_eq!(find_one_by_three(LONG_INPUT), 22 * 10);
+        assert_eq!(one_by_three(&parse_as_sorted(SHORT_INPUT).unwrap()), 7 * 5);
+        assert_eq!(one_by_three(&parse_as_sorted(LONG_INPUT).unwrap()), 22 * 10);
     }
 
     #[test]
@@ -186,6 +207,7 @@ mod tests {
             count_possible_valid_chains(&vec![0, 1, 4, 5, 6, 7, 10, 11, 12, 15, 16, 19, 22]),
             8
         );
+        assert_eq!(count_possible_valid_chains(&[]), 1);
         assert_eq!(
             count_possible_valid_chains(&vec![
                 0, 1, 2, 3, 4, 7, 8, 9, 10, 11, 14, 17, 18, 19, 20, 23, 24, 25, 28, 31, 32, 33, 34,
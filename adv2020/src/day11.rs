@@ -1,196 +1,205 @@
-use itertools::iproduct;
-use std::convert::TryFrom;
+use advgrid::{Automaton, Grid, Neighbourhood, Neighbours, Outcome, Rule};
+use advparse::ParseError;
+use advrender::{Animation, Frame, Scene};
 
-pub fn find_places_direct(input: &str) -> usize {
-    let mut plane = Plane::new(input);
-    let iter = plane.evo_direct_iter();
-    iter.for_each(drop);
+pub fn find_places_direct(input: &str) -> Result<usize, ParseError> {
+    let mut plane = Plane::new(input)?;
+    plane.settle_direct()?;
 
-    plane.count_all()
+    Ok(plane.count_all())
 }
 
-pub fn find_seats_visible_only(input: &str) -> usize {
-    let mut plane = Plane::new(input);
-    let iter = plane.evo_visible_iter();
-    iter.for_each(drop);
+pub fn find_seats_visible_only(input: &str) -> Result<usize, ParseError> {
+    let mut plane = Plane::new(input)?;
+    plane.settle_visible()?;
 
-    plane.count_all()
+    Ok(plane.count_all())
 }
 
-#[derive(Eq, PartialEq, Clone, Copy)]
+// Every round until the seats settle
+pub fn render(input: &str) -> Result<Vec<Animation>, ParseError> {
+    let plane = Plane::new(input)?;
+    let (neighbours, seating) = direct();
+    let direct = plane.animate("direct", neighbours, seating);
+    let (neighbours, seating) = visible();
+    let visible = plane.animate("visible", neighbours, seating);
+
+    Ok(vec![direct, visible])
+}
+
+// The same rounds in the terminal
+pub fn animate(input: &str) -> Result<Vec<Scene>, ParseError> {
+    let plane = Plane::new(input)?;
+    let (neighbours, seating) = direct();
+    let direct = plane.scene("direct", neighbours, seating);
+    let (neighbours, seating) = visible();
+    let visible = plane.scene("visible", neighbours, seating);
+
+    Ok(vec![direct, visible])
+}
+
+// The seats settle much earlier
+const MAX_ROUNDS: usize = 1000;
+
+// A seat is a square of pixels
+const PIXEL_SIZE: usize = 4;
+
+#[derive(Eq, PartialEq, Clone, Copy, Hash)]
 enum Seat {
     Vacant,
     Occupied,
     NoSeat,
 }
 
-struct Plane {
-    seats: Vec<Vec<Seat>>,
-
-    rows: usize,
-    cols: usize,
+// A passenger leaves when the occupied neighbours reach the tolerance
+struct Seating {
+    tolerance: usize,
 }
 
-struct PlaneIter<'a> {
-    plane: &'a mut Plane,
-    count_fn: fn(&Plane, usize, usize) -> usize,
-    should_seat_fn: fn(Seat, usize) -> Seat,
+impl Rule<Seat> for Seating {
+    fn is_alive(&self, seat: &Seat) -> bool {
+        *seat == Seat::Occupied
+    }
 
-    round: usize,
+    fn next(&self, seat: &Seat, neighbours: usize) -> Seat {
+        match seat {
+            Seat::Vacant if neighbours == 0 => Seat::Occupied,
+            Seat::Occupied if neighbours >= self.tolerance => Seat::Vacant,
+            &seat => seat,
+        }
+    }
 }
 
-impl Plane {
-    fn new(input: &str) -> Plane {
-        let seats: Vec<Vec<Seat>> = input
-            .lines()
-            .map(&str::trim)
-            .map(|line| line.chars().map(Seat::from).collect())
-            .collect();
-
-        let rows = seats.len();
-        let cols = seats.get(0).unwrap_or(&Vec::new()).len();
+struct Plane {
+    seats: Grid<Seat>,
+}
 
-        Plane { seats, rows, cols }
-    }
+impl Plane {
+    fn new(input: &str) -> Result<Plane, ParseError> {
+        let seats = Grid::parse(input)?;
 
-    fn get_seat(&self, row: i32, col: i32) -> &Seat {
-        match (usize::try_from(row), usize::try_from(col)) {
-            (Ok(row), Ok(col)) => self
-                .seats
-                .get(row)
-                .and_then(|row| row.get(col))
-                .unwrap_or(&Seat::NoSeat),
-            _ => &Seat::NoSeat,
+        if seats.height() == 0 {
+            return Err(ParseError::input("the plane is empty"));
         }
+
+        Ok(Plane { seats })
     }
 
     #[allow(dead_code)]
     fn print(&self) {
-        for row in 0..(self.rows as i32) {
-            for col in 0..(self.cols as i32) {
-                print!(
-                    "{}",
-                    match self.get_seat(row, col) {
-                        Seat::Vacant => 'L',
-                        Seat::Occupied => '#',
-                        Seat::NoSeat => '.',
-                    }
-                );
-            }
-            println!();
-        }
+        println!("{}", self.seats);
     }
 
     fn count_all(&self) -> usize {
-        self.seats
-            .iter()
-            .map(|row| row.iter().filter(|&seat| seat == &Seat::Occupied).count())
-            .sum()
+        self.seats.count(|&seat| seat == Seat::Occupied)
     }
 
-    fn evo_direct_iter(&mut self) -> PlaneIter {
-        PlaneIter {
-            plane: self,
-            count_fn: count_direct_neighbours,
-            should_seat_fn: should_seat_or_free_strict,
-            round: 0,
-        }
+    fn settle_direct(&mut self) -> Result<usize, ParseError> {
+        let (neighbours, seating) = direct();
+        self.settle(neighbours, seating)
+    }
+
+    fn settle_visible(&mut self) -> Result<usize, ParseError> {
+        let (neighbours, seating) = visible();
+        self.settle(neighbours, seating)
     }
 
-    fn evo_visible_iter(&mut self) -> PlaneIter {
-        PlaneIter {
-            plane: self,
-            count_fn: count_visible_neighbours,
-            should_seat_fn: should_seat_or_free_less_strict,
-            round: 0,
+    // Returns the rounds which have changed the seats
+    fn settle(
+        &mut self,
+        neighbours: Neighbours<Seat>,
+        seating: Seating,
+    ) -> Result<usize, ParseError> {
+        let mut automaton = Automaton::new(self.seats.clone(), neighbours, seating);
+
+        match automaton.run_until_repeat(MAX_ROUNDS) {
+            Some(Outcome::Stable { generation }) => {
+                self.seats = automaton.state().clone();
+                Ok(generation)
+            }
+            _ => Err(ParseError::input("the seats never settle")),
         }
     }
-}
 
-fn count_direct_neighbours(plane: &Plane, row: usize, col: usize) -> usize {
-    iproduct!(-1..=1, -1..=1)
-        .filter(|&(dr, dc)| !(dr == 0 && dc == 0)) // exclude center
-        .filter(|&(dr, dc)| plane.get_seat(row as i32 + dr, col as i32 + dc) == &Seat::Occupied)
-        .count()
-}
+    // Occupied seats are white, vacant ones are gray and the floor is black
+    fn animate(&self, name: &str, neighbours: Neighbours<Seat>, seating: Seating) -> Animation {
+        let frame = |seats: &Grid<Seat>| {
+            Frame::from_grid(seats, |seat| match seat {
+                Seat::Occupied => 255,
+                Seat::Vacant => 128,
+                Seat::NoSeat => 0,
+            })
+            .scaled(PIXEL_SIZE)
+        };
+        let mut automaton = Automaton::new(self.seats.clone(), neighbours, seating);
+        let mut animation = Animation::new(name);
+
+        animation.push(frame(automaton.state()));
+        while automaton.generation() < MAX_ROUNDS && automaton.step() {
+            animation.push(frame(automaton.state()));
+        }
 
-fn should_seat_or_free_strict(current_seat: Seat, neighbours: usize) -> Seat {
-    match current_seat {
-        Seat::Vacant if neighbours == 0 => Seat::Occupied,
-        Seat::Occupied if neighbours >= 4 => Seat::Vacant,
-        seat => seat,
+        animation
     }
-}
 
-fn should_seat_or_free_less_strict(current_seat: Seat, neighbours: usize) -> Seat {
-    match current_seat {
-        Seat::Vacant if neighbours == 0 => Seat::Occupied,
-        Seat::Occupied if neighbours >= 5 => Seat::Vacant,
-        seat => seat,
-    }
-}
+    // The last frame is the settled round
+    fn scene(&self, name: &str, neighbours: Neighbours<Seat>, seating: Seating) -> Scene {
+        let mut automaton = Automaton::new(self.seats.clone(), neighbours, seating);
+        let mut settled = false;
 
-fn count_visible_neighbours(plane: &Plane, row: usize, col: usize) -> usize {
-    iproduct!(-1..=1, -1..=1)
-        .filter(|&(dr, dc)| !(dr == 0 && dc == 0)) // exclude center
-        .filter(|&(dr, dc)| {
-            let (mut r, mut c) = (row as i32, col as i32);
-            while r >= 0 && r < plane.rows as i32 && c >= 0 && c < plane.cols as i32 {
-                r += dr;
-                c += dc;
-
-                match plane.get_seat(r, c) {
-                    Seat::Occupied => {
-                        return true;
-                    }
-                    Seat::Vacant => {
-                        return false;
-                    }
-                    _ => (),
+        Scene::new(
+            name,
+            std::iter::from_fn(move || {
+                if settled {
+                    return None;
                 }
-            }
 
-            false
-        })
-        .count()
-}
+                let frame = format!(
+                    "round {}, {} occupied\n{}\n",
+                    automaton.generation(),
+                    automaton.population(),
+                    automaton.state()
+                );
+                settled = automaton.generation() >= MAX_ROUNDS || !automaton.step();
 
-impl<'a> Iterator for PlaneIter<'a> {
-    type Item = usize;
+                Some(frame)
+            }),
+        )
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut state = Vec::<Vec<Seat>>::with_capacity(self.plane.rows);
-        (0..self.plane.rows).for_each(|_| state.push(vec![Seat::NoSeat; self.plane.cols]));
+fn direct() -> (Neighbours<Seat>, Seating) {
+    (
+        Neighbours::Adjacent(Neighbourhood::Moore),
+        Seating { tolerance: 4 },
+    )
+}
 
-        iproduct!(0..self.plane.rows, 0..self.plane.cols).for_each(|(row, col)| {
-            let neighbours = (self.count_fn)(self.plane, row, col);
-            let &current_seat = self.plane.get_seat(row as i32, col as i32);
+// The floor doesn't block the view
+fn visible() -> (Neighbours<Seat>, Seating) {
+    (
+        Neighbours::Visible(Neighbourhood::Moore, |&seat| seat == Seat::NoSeat),
+        Seating { tolerance: 5 },
+    )
+}
 
-            state[row][col] = (self.should_seat_fn)(current_seat, neighbours);
-        });
+impl advgrid::Cell for Seat {
+    const EXPECTED: &'static str = "L, # or .";
 
-        if state
-            .iter()
-            .zip(self.plane.seats.iter())
-            .filter(|(r1, r2)| r1.eq(r2))
-            .count()
-            == self.plane.rows
-        {
-            return None;
+    fn from_char(c: char) -> Option<Seat> {
+        match c {
+            'L' => Some(Self::Vacant),
+            '#' => Some(Self::Occupied),
+            '.' => Some(Self::NoSeat),
+            _ => None,
         }
-
-        self.plane.seats = state;
-        self.round += 1;
-        Some(self.round)
     }
-}
 
-impl From<char> for Seat {
-    fn from(c: char) -> Self {
-        match c {
-            'L' => Self::Vacant,
-            '#' => Self::Occupied,
-            _ => Self::NoSeat,
+    fn to_char(&self) -> char {
+        match self {
+            Seat::Vacant => 'L',
+            Seat::Occupied => '#',
+            Seat::NoSeat => '.',
         }
     }
 }
@@ -200,6 +209,16 @@ mod tests {
     use super::*;
     use std::fmt::{Debug, Formatter, Result, Write};
 
+    fn count_visible_neighbours(plane: &Plane, row: usize, col: usize) -> usize {
+        plane
+            .seats
+            .line_of_sight((col, row), Neighbourhood::Moore, |&seat| {
+                seat == Seat::NoSeat
+            })
+            .filter(|&&seat| seat == Seat::Occupied)
+            .count()
+    }
+
     const INPUT: &str = r#"L.LL.LL.LL
                            LLLLLLL.LL
                            L.L.L..L..
@@ -223,38 +242,75 @@ mod tests {
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_places_direct(""), 0);
-        assert_eq!(find_seats_visible_only(""), 0);
+        assert!(find_places_direct("").is_err());
+        assert!(find_seats_visible_only("").is_err());
+        assert_eq!(
+            find_places_direct("L.L\n.X."),
+            Err(ParseError::new(2, 2, "X", "expected L, # or ."))
+        );
+        assert!(find_places_direct("L.L\nL.").is_err());
     }
 
     #[test]
     fn test_plane() {
-        let plane = Plane::new(INPUT);
-        assert_eq!(plane.seats.len(), INPUT.lines().count());
-        assert_eq!(plane.get_seat(-1, -1), &Seat::NoSeat);
-        assert_eq!(plane.get_seat(0, 0), &Seat::Vacant);
-        assert_eq!(plane.get_seat(100, 100), &Seat::NoSeat);
-        assert_eq!(plane.get_seat(0, 5), &Seat::Vacant);
+        let plane = Plane::new(INPUT).unwrap();
+        assert_eq!(plane.seats.height(), INPUT.lines().count());
+        assert_eq!(plane.seats.at(-1, -1), None);
+        assert_eq!(plane.seats.at(0, 0), Some(&Seat::Vacant));
+        assert_eq!(plane.seats.at(100, 100), None);
+        assert_eq!(plane.seats.at(5, 0), Some(&Seat::Vacant));
     }
 
     #[test]
     fn test_plane_direct_evolution() {
-        let mut plane = Plane::new(INPUT);
+        let mut plane = Plane::new(INPUT).unwrap();
 
-        assert_eq!(plane.evo_direct_iter().max(), Some(5));
+        assert_eq!(plane.settle_direct(), Ok(5));
         assert_eq!(plane.count_all(), 37);
     }
 
     #[test]
     fn test_plane_visible_evolution() {
-        let mut plane = Plane::new(INPUT);
+        let mut plane = Plane::new(INPUT).unwrap();
 
-        assert_eq!(plane.evo_visible_iter().max(), Some(6));
+        assert_eq!(plane.settle_visible(), Ok(6));
         assert_eq!(plane.count_all(), 26);
 
         plane.print();
     }
 
+    #[test]
+    fn test_render() {
+        let animations = render(INPUT).unwrap();
+        assert_eq!(animations.len(), 2);
+
+        // The last frames are the settled rounds
+        assert_eq!(animations[0].frames().len(), 6);
+        assert_eq!(animations[1].frames().len(), 7);
+
+        let first = &animations[0].frames()[0];
+        assert_eq!(first.width(), 10 * PIXEL_SIZE);
+        assert_eq!(first.get(0, 0), Some(128));
+        assert_eq!(first.get(PIXEL_SIZE, 0), Some(0));
+        assert_eq!(animations[0].frames()[1].get(0, 0), Some(255));
+
+        assert!(render("").is_err());
+    }
+
+    #[test]
+    fn test_animate() {
+        let scenes = animate(INPUT).unwrap();
+        assert_eq!(scenes[0].name(), "direct");
+        assert_eq!(scenes[1].name(), "visible");
+
+        let frames: Vec<String> = animate(INPUT).unwrap().remove(0).collect();
+        assert_eq!(frames.len(), 6);
+        assert!(frames[0].starts_with("round 0, 0 occupied\nL.LL.LL.LL\n"));
+        assert!(frames[5].starts_with("round 5, 37 occupied\n#.#L.L#.##\n"));
+
+        assert!(animate("").is_err());
+    }
+
     #[test]
     fn test_count_visible_neighbours() {
         let plane1 = Plane::new(
@@ -267,14 +323,16 @@ mod tests {
                                         .........
                                         #........
                                         ...#....."#,
-        );
+        )
+        .unwrap();
         assert_eq!(count_visible_neighbours(&plane1, 4, 3), 8);
 
         let plane2 = Plane::new(
             r#".............
                                         .L.L.#.#.#.#.
                                         ............."#,
-        );
+        )
+        .unwrap();
         assert_eq!(count_visible_neighbours(&plane2, 1, 1), 0);
 
         let plane3 = Plane::new(
@@ -285,7 +343,8 @@ mod tests {
                                         ##...##
                                         #.#.#.#
                                         .##.##."#,
-        );
+        )
+        .unwrap();
         assert_eq!(count_visible_neighbours(&plane3, 3, 3), 0);
     }
 
@@ -1,36 +1,66 @@
-pub fn find_answer1(input: &str) -> i32 {
+use advparse::{parse_lines, parse_number, ParseError};
+
+pub fn find_answer1(input: &str) -> Result<i32, ParseError> {
     let mut ship = Ship {
         position: (0, 0),
         direction: 0,
     };
 
-    input.lines().for_each(|line| {
-        let mut chars = line.chars();
-        let direction = chars.next().unwrap();
-        let value_str = chars.collect::<String>();
-        let value = value_str.parse::<i32>().unwrap();
-
-        match direction {
-            'N' => ship.position.1 += value,
-            'S' => ship.position.1 -= value,
-            'E' => ship.position.0 += value,
-            'W' => ship.position.0 -= value,
-            'L' => ship.direction += value,
-            'R' => ship.direction -= value,
-            'F' if ship.direction == 0 => ship.position.0 += value,
-            'F' if ship.direction == 90 => ship.position.1 += value,
-            'F' if ship.direction == 180 => ship.position.0 -= value,
-            'F' if ship.direction == 270 => ship.position.1 -= value,
-            'F' if ship.direction == -90 => ship.position.1 -= value,
-            'F' if ship.direction == -180 => ship.position.0 -= value,
-            'F' if ship.direction == -270 => ship.position.1 += value,
-            _ => println!("pfffffff"),
+    parse_input(input)?
+        .into_iter()
+        .for_each(|(direction, value)| {
+            match direction {
+                'N' => ship.position.1 += value,
+                'S' => ship.position.1 -= value,
+                'E' => ship.position.0 += value,
+                'W' => ship.position.0 -= value,
+                'L' => ship.direction += value,
+                'R' => ship.direction -= value,
+                'F' if ship.direction == 0 => ship.position.0 += value,
+                'F' if ship.direction == 90 => ship.position.1 += value,
+                'F' if ship.direction == 180 => ship.position.0 -= value,
+                'F' if ship.direction == 270 => ship.position.1 -= value,
+                'F' if ship.direction == -90 => ship.position.1 -= value,
+                'F' if ship.direction == -180 => ship.position.0 -= value,
+                'F' if ship.direction == -270 => ship.position.1 += value,
+                _ => (),
+            }
+
+            ship.direction %= 360;
+        });
+
+    Ok(ship.position.0.abs() + ship.position.1.abs())
+}
+
+// F10, the turns are multiples of 90 degrees
+fn parse_action(line: &str) -> Result<(char, i32), ParseError> {
+    let action = line.trim();
+    let direction = match action.chars().next() {
+        Some(c @ ('N' | 'S' | 'E' | 'W' | 'L' | 'R' | 'F')) => c,
+        Some(c) => {
+            return Err(ParseError::in_line(
+                line,
+                &action[..c.len_utf8()],
+                "expected an action",
+            ))
         }
+        None => return Err(ParseError::end_of_line(line, "expected an action")),
+    };
+    let value = parse_number::<i32>(line, &action[1..])?;
+
+    if (direction == 'L' || direction == 'R') && value % 90 != 0 {
+        return Err(ParseError::in_line(
+            line,
+            &action[1..],
+            "expected a multiple of 90",
+        ));
+    }
 
-        ship.direction %= 360;
-    });
+    Ok((direction, value))
+}
 
-    ship.position.0.abs() + ship.position.1.abs()
+fn parse_input(input: &str) -> Result<Vec<(char, i32)>, ParseError> {
+    parse_lines(input, parse_action)
 }
 
 struct Ship {
@@ -43,56 +73,52 @@ struct Titanic {
     waypoint: (i32, i32),
 }
 
-pub fn find_answer2(input: &str) -> i32 {
+pub fn find_answer2(input: &str) -> Result<i32, ParseError> {
     let mut titanic = Titanic {
         position: (0, 0),
         waypoint: (10, 1),
     };
 
-    input.lines().for_each(|line| {
-        let mut chars = line.chars();
-        let direction = chars.next().unwrap();
-        let value_str = chars.collect::<String>();
-        let value = value_str.parse::<i32>().unwrap();
-        let value_neg = -value;
-
-        let ca = (value as f64).to_radians().cos() as i32;
-        let sa = (value as f64).to_radians().sin() as i32;
-
-        let cra = (value_neg as f64).to_radians().cos() as i32;
-        let sra = (value_neg as f64).to_radians().sin() as i32;
-
-        match direction {
-            'N' => titanic.waypoint.1 += value,
-            'S' => titanic.waypoint.1 -= value,
-            'E' => titanic.waypoint.0 += value,
-            'W' => titanic.waypoint.0 -= value,
-
-            'L' => {
-                titanic.waypoint = (
-                    ca * titanic.waypoint.0 - sa * titanic.waypoint.1,
-                    sa * titanic.waypoint.0 + ca * titanic.waypoint.1,
-                )
-            }
-            'R' => {
-                titanic.waypoint = (
-                    cra * titanic.waypoint.0 - sra * titanic.waypoint.1,
-                    sra * titanic.waypoint.0 + cra * titanic.waypoint.1,
-                )
-            }
-
-            'F' => {
-                titanic.position.0 += value * titanic.waypoint.0;
-                titanic.position.1 += value * titanic.waypoint.1;
+    parse_input(input)?
+        .into_iter()
+        .for_each(|(direction, value)| {
+            let value_neg = -value;
+
+            let ca = (value as f64).to_radians().cos() as i32;
+            let sa = (value as f64).to_radians().sin() as i32;
+
+            let cra = (value_neg as f64).to_radians().cos() as i32;
+            let sra = (value_neg as f64).to_radians().sin() as i32;
+
+            match direction {
+                'N' => titanic.waypoint.1 += value,
+                'S' => titanic.waypoint.1 -= value,
+                'E' => titanic.waypoint.0 += value,
+                'W' => titanic.waypoint.0 -= value,
+
+                'L' => {
+                    titanic.waypoint = (
+                        ca * titanic.waypoint.0 - sa * titanic.waypoint.1,
+                        sa * titanic.waypoint.0 + ca * titanic.waypoint.1,
+                    )
+                }
+                'R' => {
+                    titanic.waypoint = (
+                        cra * titanic.waypoint.0 - sra * titanic.waypoint.1,
+                        sra * titanic.waypoint.0 + cra * titanic.waypoint.1,
+                    )
+                }
+
+                'F' => {
+                    titanic.position.0 += value * titanic.waypoint.0;
+                    titanic.position.1 += value * titanic.waypoint.1;
+                }
+
+                _ => (),
             }
+        });
 
-            _ => println!("pfffffff"),
-        }
-
-        println!("{} - {:?} {:?}", line, titanic.position, titanic.waypoint);
-    });
-
-    titanic.position.0.abs() + titanic.position.1.abs()
+    Ok(titanic.position.0.abs() + titanic.position.1.abs())
 }
 /*
 fn parse_input<'a>(input: &'a str) -> impl Iterator<Item=Seat> + 'a {
@@ -116,7 +142,7 @@ F7
 R90
 F11"#
             ),
-            25
+            Ok(25)
         );
         assert_eq!(
             find_answer2(
@@ -126,7 +152,25 @@ F7
 R90
 F11"#
             ),
-            286
+            Ok(286)
+        );
+    }
+
+    #[test]
+    fn test_parse_action() {
+        assert_eq!(parse_action("F10"), Ok(('F', 10)));
+        assert_eq!(
+            parse_action("X10"),
+            Err(ParseError::new(0, 1, "X", "expected an action"))
+        );
+        assert_eq!(
+            parse_action("R45"),
+            Err(ParseError::new(0, 2, "45", "expected a multiple of 90"))
+        );
+        assert!(parse_action("F").is_err());
+        assert_eq!(
+            find_answer1("F10\nN"),
+            Err(ParseError::new(2, 2, "", "expected a number"))
         );
     }
 }
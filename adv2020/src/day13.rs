@@ -1,62 +1,69 @@
-pub fn find_earliest_bus(input: &str) -> usize {
-    if input.is_empty() {
-        return 0;
-    }
+use advparse::{parse_number, ParseError};
 
-    let mut lines = input.lines();
-    let time = lines.next().unwrap().parse::<usize>().unwrap();
-    let bus = lines
-        .next()
-        .unwrap()
-        .split(|c| c == ',')
-        .map(str::parse::<usize>)
-        .filter_map(Result::ok)
+pub fn find_earliest_bus(input: &str) -> Result<usize, ParseError> {
+    let (time, buses) = parse_notes(input)?;
+    let bus = buses
+        .iter()
+        .filter_map(|bus| *bus)
         .map(|bus| (bus, find_next_bus(time, bus)))
         .min_by(|a, b| a.1.cmp(&b.1))
-        .unwrap();
+        .ok_or_else(no_buses)?;
 
-    (bus.1 - time) * bus.0
+    Ok((bus.1 - time) * bus.0)
 }
 
-pub fn find_earliest_timestamp(input: &str) -> usize {
-    if input.is_empty() {
-        return 0;
+pub fn find_earliest_timestamp(input: &str) -> Result<usize, ParseError> {
+    let (_, buses) = parse_notes(input)?;
+    let buses: Vec<(usize, usize)> = buses
+        .into_iter()
+        .enumerate()
+        .filter_map(|(pos, num)| num.map(|num| (pos, num)))
+        // the bus leaves pos minutes after the timestamp, so the timestamp is -pos modulo num
+        .map(|(pos, num)| ((num - pos % num) % num, num))
+        .collect();
+
+    if buses.is_empty() {
+        return Err(no_buses());
     }
 
-    let buses: Vec<(usize, usize)> = input
-        .lines()
-        .skip(1)
+    chinese_remainder_theorem(&buses)
+        .map(|(timestamp, _)| timestamp)
+        .ok_or_else(|| ParseError::input("the bus IDs must be pairwise coprime"))
+}
+
+fn no_buses() -> ParseError {
+    ParseError::input("there are no buses in service")
+}
+
+// 939
+// 7,13,x,x,59,x,31,19
+fn parse_notes(input: &str) -> Result<(usize, Vec<Option<usize>>), ParseError> {
+    let mut lines = input.lines();
+    let time = match lines.next() {
+        Some(line) => parse_number(line, line).map_err(|err| err.at_line(1))?,
+        None => return Err(ParseError::input("the timestamp is missing")),
+    };
+    let line = lines
         .next()
-        .unwrap()
-        .split(|c| c == ',')
-        .enumerate()
-        .filter_map(|(pos, str)| {
-            if let Ok(num) = str.parse::<usize>() {
-                Some((pos, num))
-            } else {
-                None
-            }
+        .ok_or_else(|| ParseError::input("the bus IDs are missing"))?;
+
+    let buses = line
+        .trim()
+        .split(',')
+        .map(|bus| match bus {
+            "x" => Ok(None),
+            _ => match parse_number::<usize>(line, bus)? {
+                0 => Err(ParseError::in_line(line, bus, "expected a bus ID")),
+                bus => Ok(Some(bus)),
+            },
         })
-        .map(|(pos, num)| {
-            if pos != 0 {
-                let mut checked_sub = None;
-                let mut i = 1usize;
-                while checked_sub.is_none() {
-                    checked_sub = (num * i).checked_sub(pos);
-                    i += 1;
-                }
-
-                (checked_sub.unwrap(), num)
-            } else {
-                (pos, num)
-            }
-        })
-        .collect();
+        .collect::<Result<Vec<Option<usize>>, ParseError>>()
+        .map_err(|err| err.at_line(2))?;
 
-    chinese_remainder_theorem(&buses).0
+    Ok((time, buses))
 }
 
-fn chinese_remainder_theorem(nums: &[(usize, usize)]) -> (usize, usize) {
+fn chinese_remainder_theorem(nums: &[(usize, usize)]) -> Option<(usize, usize)> {
     let m_product: usize = nums.iter().map(|(_, modulo)| modulo).product();
 
     let mi = nums
@@ -68,15 +75,21 @@ fn chinese_remainder_theorem(nums: &[(usize, usize)]) -> (usize, usize) {
         .iter()
         .map(|(_, modulo)| *modulo)
         .zip(mi.iter())
-        .map(|(modulo, &mi)| (1..mi).find(|&rem| (rem * mi) % modulo == 1).unwrap())
-        .collect::<Vec<usize>>();
+        .map(|(modulo, &mi)| {
+            if modulo == 1 {
+                Some(0)
+            } else {
+                (1..modulo).find(|&rem| (rem * mi) % modulo == 1)
+            }
+        })
+        .collect::<Option<Vec<usize>>>()?;
 
     let x = (0..nums.len())
         .map(|i| nums[i].0 * mi[i] * yi[i])
         .sum::<usize>()
         % m_product;
 
-    (x, m_product)
+    Some((x, m_product))
 }
 
 fn find_next_bus(time: usize, bus: usize) -> usize {
@@ -95,8 +108,13 @@ mod tests {
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_earliest_bus(""), 0);
-        assert_eq!(find_earliest_timestamp(""), 0);
+        assert!(find_earliest_bus("").is_err());
+        assert!(find_earliest_timestamp("939").is_err());
+        assert!(find_earliest_bus("939\nx,x").is_err());
+        assert_eq!(
+            find_earliest_bus("939\n7,y,13"),
+            Err(ParseError::new(2, 3, "y", "expected a number"))
+        );
     }
 
     #[test]
@@ -110,28 +128,35 @@ mod tests {
 
     #[test]
     fn test_find_earliest_bus() {
-        assert_eq!(find_earliest_bus("939\n7,13,x,x,59,x,31,19"), 295);
+        assert_eq!(find_earliest_bus("939\n7,13,x,x,59,x,31,19"), Ok(295));
     }
 
     #[test]
     fn test_find_earliest_timestamp() {
-        assert_eq!(find_earliest_timestamp("939\n7,13,x,x,59,x,31,19"), 1068781);
-        assert_eq!(find_earliest_timestamp("939\n17,x,13,19"), 3417);
-        assert_eq!(find_earliest_timestamp("939\n67,7,59,61"), 754018);
-        assert_eq!(find_earliest_timestamp("939\n67,x,7,59,61"), 779210);
-        assert_eq!(find_earliest_timestamp("939\n67,7,x,59,61"), 1261476);
-        assert_eq!(find_earliest_timestamp("939\n1789,37,47,1889"), 1202161486);
+        assert_eq!(
+            find_earliest_timestamp("939\n7,13,x,x,59,x,31,19"),
+            Ok(1068781)
+        );
+        assert_eq!(find_earliest_timestamp("939\n17,x,13,19"), Ok(3417));
+        assert_eq!(find_earliest_timestamp("939\n67,7,59,61"), Ok(754018));
+        assert_eq!(find_earliest_timestamp("939\n67,x,7,59,61"), Ok(779210));
+        assert_eq!(find_earliest_timestamp("939\n67,7,x,59,61"), Ok(1261476));
+        assert_eq!(
+            find_earliest_timestamp("939\n1789,37,47,1889"),
+            Ok(1202161486)
+        );
     }
 
     #[test]
     fn test_chinese_remainder_theorem() {
         assert_eq!(
             chinese_remainder_theorem(&[(2, 3), (3, 5), (1, 7)]),
-            (8, 105)
+            Some((8, 105))
         );
         assert_eq!(
             chinese_remainder_theorem(&[(0, 7), (12, 13), (55, 59), (25, 31), (12, 19)]),
-            (1068781, 7 * 13 * 59 * 31 * 19)
+            Some((1068781, 7 * 13 * 59 * 31 * 19))
         );
+        assert_eq!(chinese_remainder_theorem(&[(1, 4), (1, 6)]), None);
     }
 }
@@ -1,32 +1,70 @@
+use advparse::{expect_token, next_token, parse_lines, parse_number, ParseError};
 use std::collections::HashMap;
 
-pub fn find_answer1(input: &str) -> usize {
+const MASK_LENGTH: usize = 36;
+
+enum Command {
+    Mask(String),
+    Write(usize, usize),
+}
+
+pub fn find_answer1(input: &str) -> Result<usize, ParseError> {
     let mut mem: HashMap<usize, usize> = HashMap::new();
     let mut current_mask = String::new();
 
-    input.lines().map(&str::trim).for_each(|line: &str| {
-        if line.starts_with("mask") {
-            current_mask = extract_mask(line);
+    parse_input(input)?
+        .into_iter()
+        .for_each(|command| match command {
+            Command::Mask(mask) => current_mask = mask,
+            Command::Write(address, value) => {
+                mem.insert(address, apply_mask(value, current_mask.as_str()));
+            }
+        });
+
+    Ok(mem.values().sum())
+}
+
+fn parse_input(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_lines(input, |line| {
+        if line.trim().starts_with("mask") {
+            extract_mask(line).map(Command::Mask)
         } else {
-            let (address, mut value) = extract_address_value(line);
-            value = apply_mask(value, current_mask.as_str());
-            mem.insert(address, value);
+            extract_address_value(line).map(|(address, value)| Command::Write(address, value))
         }
-    });
-
-    mem.values().sum()
+    })
 }
 
-fn extract_mask(input: &str) -> String {
-    String::from(&input[7..])
+// mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+fn extract_mask(input: &str) -> Result<String, ParseError> {
+    let mut tokens = input.split_whitespace();
+    expect_token(input, &mut tokens, "mask")?;
+    expect_token(input, &mut tokens, "=")?;
+    let mask = next_token(input, &mut tokens, "a mask")?;
+
+    if mask.len() != MASK_LENGTH || mask.contains(|c| c != '0' && c != '1' && c != 'X') {
+        return Err(ParseError::in_line(
+            input,
+            mask,
+            format!("expected {} bits of 0, 1 or X", MASK_LENGTH),
+        ));
+    }
+
+    Ok(String::from(mask))
 }
 
-fn extract_address_value(input: &str) -> (usize, usize) {
-    let tokens: Vec<&str> = input.split_whitespace().collect();
-    let address = tokens[0][4..tokens[0].len() - 1].parse::<usize>().unwrap();
-    let value = tokens[2].parse::<usize>().unwrap();
+// mem[8] = 11
+fn extract_address_value(input: &str) -> Result<(usize, usize), ParseError> {
+    let mut tokens = input.split_whitespace();
+    let memory = next_token(input, &mut tokens, "mem[<address>]")?;
+    let address = memory
+        .strip_prefix("mem[")
+        .and_then(|address| address.strip_suffix(']'))
+        .ok_or_else(|| ParseError::in_line(input, memory, "expected mem[<address>]"))?;
+    let address = parse_number(input, address)?;
+    expect_token(input, &mut tokens, "=")?;
+    let value = parse_number(input, next_token(input, &mut tokens, "a value")?)?;
 
-    (address, value)
+    Ok((address, value))
 }
 
 fn apply_mask(mut value: usize, bitmask: &str) -> usize {
@@ -92,23 +130,23 @@ fn generate_addresses(bitmask: &str) -> Vec<String> {
         .collect()
 }
 
-pub fn find_answer2(input: &str) -> usize {
+pub fn find_answer2(input: &str) -> Result<usize, ParseError> {
     let mut mem: HashMap<usize, usize> = HashMap::new();
     let mut mask: String = String::new();
 
-    input.lines().map(&str::trim).for_each(|line: &str| {
-        if line.starts_with("mask") {
-            mask = extract_mask(line);
-        } else {
-            let (address, value) = extract_address_value(line);
-            let addresses = generate_addresses(create_mask(address, mask.as_str()).as_str());
-            addresses.iter().for_each(|addr| {
-                mem.insert(bit_to_usize(addr), value);
-            })
-        }
-    });
+    parse_input(input)?
+        .into_iter()
+        .for_each(|command| match command {
+            Command::Mask(new_mask) => mask = new_mask,
+            Command::Write(address, value) => {
+                let addresses = generate_addresses(create_mask(address, mask.as_str()).as_str());
+                addresses.iter().for_each(|addr| {
+                    mem.insert(bit_to_usize(addr), value);
+                })
+            }
+        });
 
-    mem.values().sum()
+    Ok(mem.values().sum())
 }
 
 fn bit_to_usize(input: &str) -> usize {
@@ -123,15 +161,24 @@ mod tests {
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_answer1(""), 0);
-        assert_eq!(find_answer2(""), 0);
+        assert_eq!(find_answer1(""), Ok(0));
+        assert_eq!(find_answer2(""), Ok(0));
     }
 
     #[test]
     fn test_extract_mask() {
         assert_eq!(
             extract_mask("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X"),
-            "XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X"
+            Ok(String::from("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X"))
+        );
+        assert_eq!(
+            extract_mask("mask = XX1XXXX0X"),
+            Err(ParseError::new(
+                0,
+                8,
+                "XX1XXXX0X",
+                "expected 36 bits of 0, 1 or X"
+            ))
         );
     }
 
@@ -139,8 +186,13 @@ mod tests {
     fn test_extract_address_value() {
         assert_eq!(
             extract_address_value("mem[23073] = 4721114"),
-            (23073, 4721114)
+            Ok((23073, 4721114))
+        );
+        assert_eq!(
+            extract_address_value("mem[23x73] = 4721114"),
+            Err(ParseError::new(0, 5, "23x73", "expected a number"))
         );
+        assert!(extract_address_value("mem 23073 = 4721114").is_err());
     }
 
     #[test]
@@ -159,7 +211,7 @@ mod tests {
                                    mem[7] = 101
                                    mem[8] = 0"#
             ),
-            165
+            Ok(165)
         );
     }
 
@@ -198,7 +250,7 @@ mod tests {
                                    mask = 00000000000000000000000000000000X0XX
                                    mem[26] = 1"#
             ),
-            208
+            Ok(208)
         );
     }
 
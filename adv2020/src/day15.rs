@@ -1,9 +1,10 @@
+use advparse::{parse_separated, ParseError};
 use std::collections::HashMap;
 
-pub fn find_number_2020(input: &str) -> usize {
-    let mut game = Game::new(input);
+pub fn find_number_2020(input: &str) -> Result<usize, ParseError> {
+    let mut game = Game::new(input)?;
 
-    game.simulate_rounds(2020)
+    Ok(game.simulate_rounds(2020))
 }
 
 struct Game {
@@ -12,24 +13,23 @@ struct Game {
 }
 
 impl Game {
-    fn new(input: &str) -> Game {
+    // 0,3,6
+    fn new(input: &str) -> Result<Game, ParseError> {
         let mut numbers: HashMap<usize, usize> = HashMap::new();
-        let round = input
-            .split(|c| c == ',')
-            .map(&str::parse::<usize>)
-            .filter_map(Result::ok)
-            .enumerate()
-            .map(|(pos, num)| {
-                numbers.insert(num, pos + 1);
-                pos + 1
-            })
-            .max()
-            .unwrap_or_default();
-
-        Game {
+        let input = input.trim();
+        let mut round = 0;
+
+        if !input.is_empty() {
+            for (pos, number) in parse_separated(input, ',')?.into_iter().enumerate() {
+                numbers.insert(number, pos + 1);
+                round = pos + 1;
+            }
+        }
+
+        Ok(Game {
             numbers_rounds: numbers,
             round,
-        }
+        })
     }
 
     fn simulate_rounds(&mut self, max_rounds: usize) -> usize {
@@ -67,10 +67,10 @@ impl Game {
     }
 }
 
-pub fn find_number_30000000(input: &str) -> usize {
-    let mut game = Game::new(input);
+pub fn find_number_30000000(input: &str) -> Result<usize, ParseError> {
+    let mut game = Game::new(input)?;
 
-    game.simulate_rounds(30000000)
+    Ok(game.simulate_rounds(30000000))
 }
 
 #[cfg(test)]
@@ -79,48 +79,53 @@ mod tests {
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_number_2020(""), 0);
-        assert_eq!(find_number_30000000(""), 0);
+        assert_eq!(find_number_2020(""), Ok(0));
+        assert_eq!(find_number_30000000(""), Ok(0));
     }
 
     #[test]
     fn test_game_new() {
-        let game = Game::new("0,3,6");
+        let game = Game::new("0,3,6").unwrap();
 
         assert_eq!(game.round, 3);
         assert_eq!(game.numbers_rounds.len(), 3);
+
+        assert_eq!(
+            Game::new("0,x,6").err(),
+            Some(ParseError::new(0, 3, "x", "expected a number"))
+        );
     }
 
     #[test]
     fn test_simulate_rounds() {
-        let mut game = Game::new("0,3,6");
+        let mut game = Game::new("0,3,6").unwrap();
 
         assert_eq!(game.simulate_rounds(2020), 436);
     }
 
     #[test]
     fn test_find_number_2020() {
-        assert_eq!(find_number_2020("0,3,6"), 436);
-
-        assert_eq!(find_number_2020("1,3,2"), 1);
-        assert_eq!(find_number_2020("2,1,3"), 10);
-        assert_eq!(find_number_2020("1,2,3"), 27);
-        assert_eq!(find_number_2020("2,3,1"), 78);
-        assert_eq!(find_number_2020("3,2,1"), 438);
-        assert_eq!(find_number_2020("3,1,2"), 1836);
+        assert_eq!(find_number_2020("0,3,6"), Ok(436));
+
+        assert_eq!(find_number_2020("1,3,2"), Ok(1));
+        assert_eq!(find_number_2020("2,1,3"), Ok(10));
+        assert_eq!(find_number_2020("1,2,3"), Ok(27));
+        assert_eq!(find_number_2020("2,3,1"), Ok(78));
+        assert_eq!(find_number_2020("3,2,1"), Ok(438));
+        assert_eq!(find_number_2020("3,1,2"), Ok(1836));
     }
 
     #[test]
     #[ignore]
     fn test_find_number_30000000() {
-        assert_eq!(find_number_30000000("0,3,6"), 175594);
+        assert_eq!(find_number_30000000("0,3,6"), Ok(175594));
         /*
-               assert_eq!(find_number_30000000("1,3,2"), 2578);
-               assert_eq!(find_number_30000000("2,1,3"), 3544142);
-               assert_eq!(find_number_30000000("1,2,3"), 261214);
-               assert_eq!(find_number_30000000("2,3,1"), 6895259);
-               assert_eq!(find_number_30000000("3,2,1"), 18);
-               assert_eq!(find_number_30000000("3,1,2"), 362);
+               assert_eq!(find_number_30000000("1,3,2"), Ok(2578));
+               assert_eq!(find_number_30000000("2,1,3"), Ok(3544142));
+               assert_eq!(find_number_30000000("1,2,3"), Ok(261214));
+               assert_eq!(find_number_30000000("2,3,1"), Ok(6895259));
+               assert_eq!(find_number_30000000("3,2,1"), Ok(18));
+               assert_eq!(find_number_30000000("3,1,2"), Ok(362));
         */
     }
 }
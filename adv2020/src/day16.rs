@@ -1,5 +1,6 @@
+use crate::elimination::eliminate;
+use advparse::{numbered_lines, parse_number, parse_separated, ParseError};
 use itertools::Itertools;
-use std::collections::HashMap;
 
 struct Validation<'a> {
     name: &'a str,
@@ -14,97 +15,132 @@ impl Validation<'_> {
     }
 }
 
-pub fn find_invalid_tickets(input: &str) -> usize {
-    let mut lines = input.lines();
-    let validations = extract_rules(&mut lines);
+struct Notes<'a> {
+    validations: Vec<Validation<'a>>,
+    your_ticket: Vec<usize>,
+    nearby_tickets: Vec<Vec<usize>>,
+}
 
-    lines.find(|&line| line == "nearby tickets:");
+pub fn find_invalid_tickets(input: &str) -> Result<usize, ParseError> {
+    if input.is_empty() {
+        return Ok(0);
+    }
 
-    lines
-        .map(parse_numbers)
-        .map(|numbers: Vec<usize>| {
-            validate_all_rules(&numbers, &validations)
+    let notes = parse_notes(input)?;
+
+    Ok(notes
+        .nearby_tickets
+        .iter()
+        .map(|numbers| {
+            validate_all_rules(numbers, &notes.validations)
                 .iter()
                 .sum::<usize>()
         })
-        .sum::<usize>()
+        .sum::<usize>())
 }
 
-pub fn find_departure_tickets(input: &str) -> usize {
+pub fn find_departure_tickets(input: &str) -> Result<usize, ParseError> {
     if input.is_empty() {
-        return 0;
+        return Ok(0);
     }
 
-    let mut lines = input.lines();
-    let validations = extract_rules(&mut lines);
+    let notes = parse_notes(input)?;
 
-    lines.find(|&line| line == "your ticket:");
-
-    let your_ticket = parse_numbers(lines.next().unwrap());
-
-    lines.find(|&line| line == "nearby tickets:");
-
-    let other_tickets = lines
-        .map(parse_numbers)
+    let other_tickets = notes
+        .nearby_tickets
+        .into_iter()
         .filter(|numbers| {
             numbers
                 .iter()
-                .all(|number| is_number_correct(*number, &validations))
+                .all(|number| is_number_correct(*number, &notes.validations))
         })
         .collect::<Vec<Vec<usize>>>();
 
-    let correct_combination = find_validation_combination(&other_tickets, &validations);
+    let correct_combination = find_validation_combination(&other_tickets, &notes.validations)?;
 
-    correct_combination
+    Ok(correct_combination
         .iter()
         .enumerate()
         .filter(|&(_, rule)| rule.name.starts_with("departure"))
-        .map(|(pos, _)| your_ticket[pos])
-        .product()
+        .map(|(pos, _)| notes.your_ticket[pos])
+        .product())
 }
 
-fn extract_rules<'a>(lines: &mut dyn Iterator<Item = &'a str>) -> Vec<Validation<'a>> {
+fn parse_notes(input: &str) -> Result<Notes<'_>, ParseError> {
+    let mut lines = numbered_lines(input);
+    let validations = extract_rules(&mut lines)?;
+    let fields = validations.len();
+
+    expect_header(&mut lines, "your ticket:")?;
+    let your_ticket = match lines.next() {
+        Some((number, line)) => parse_ticket(line, fields).map_err(|err| err.at_line(number))?,
+        None => return Err(ParseError::input("expected your ticket")),
+    };
+
+    expect_header(&mut lines, "nearby tickets:")?;
+    let nearby_tickets = lines
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| parse_ticket(line, fields).map_err(|err| err.at_line(number)))
+        .collect::<Result<Vec<Vec<usize>>, ParseError>>()?;
+
+    Ok(Notes {
+        validations,
+        your_ticket,
+        nearby_tickets,
+    })
+}
+
+fn expect_header<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    header: &str,
+) -> Result<(), ParseError> {
+    let expected = format!("expected \"{}\"", header);
+
+    match lines.find(|(_, line)| !line.trim().is_empty()) {
+        Some((_, line)) if line.trim() == header => Ok(()),
+        Some((number, line)) => Err(ParseError::in_line(line, line, expected).at_line(number)),
+        None => Err(ParseError::input(expected)),
+    }
+}
+
+fn extract_rules<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Vec<Validation<'a>>, ParseError> {
     let mut validations: Vec<Validation> = Vec::new();
 
-    while let Some(line) = lines.next() {
+    for (number, line) in lines {
         if line.is_empty() {
             break;
         }
-        if let Some(validation) = map_rule(line) {
-            validations.push(validation);
-        }
+        validations.push(map_rule(line).map_err(|err| err.at_line(number))?);
     }
 
-    validations
+    Ok(validations)
 }
 
-fn map_rule(input: &str) -> Option<Validation> {
-    let mut splitter = input.split(':');
-    let name = splitter.next()?;
-    let range = splitter
-        .next()?
-        .split("or")
-        .map(&str::trim)
-        .map(|part: &str| {
-            part.split('-')
-                .map(&str::parse::<usize>)
-                .filter_map(Result::ok)
-                .collect::<Vec<usize>>()
-        })
-        .filter_map(|parts| {
-            if parts.len() == 2 {
-                Some((parts[0], parts[1]))
-            } else {
-                None
+// arrival platform: 42-729 or 751-959
+fn map_rule(input: &str) -> Result<Validation<'_>, ParseError> {
+    let (name, ranges) = input
+        .split_once(':')
+        .ok_or_else(|| ParseError::end_of_line(input, "expected \":\""))?;
+
+    let rules = ranges
+        .split(" or ")
+        .map(|range| {
+            let (from, to) = range
+                .split_once('-')
+                .ok_or_else(|| ParseError::in_line(input, range, "expected a range from-to"))?;
+            let (from, to) = (parse_number(input, from)?, parse_number(input, to)?);
+
+            if from > to {
+                return Err(ParseError::in_line(input, range, "the range is empty"));
             }
+
+            Ok((from, to))
         })
-        .collect::<Vec<(usize, usize)>>();
+        .collect::<Result<Vec<(usize, usize)>, ParseError>>()?;
 
-    if range.is_empty() {
-        None
-    } else {
-        Some(Validation { name, rules: range })
-    }
+    Ok(Validation { name, rules })
 }
 
 fn validate_all_rules(vec: &Vec<usize>, validations: &Vec<Validation>) -> Vec<usize> {
@@ -122,52 +158,46 @@ fn is_number_correct(number: usize, validations: &Vec<Validation>) -> bool {
     })
 }
 
-fn parse_numbers(input: &str) -> Vec<usize> {
-    input
-        .split(',')
-        .map(&str::parse::<usize>)
-        .filter_map(Result::ok)
-        .collect::<Vec<usize>>()
+// Every ticket has a number for each field
+fn parse_ticket(input: &str, fields: usize) -> Result<Vec<usize>, ParseError> {
+    let numbers = parse_separated(input, ',')?;
+
+    if numbers.len() != fields {
+        return Err(ParseError::in_line(
+            input,
+            input,
+            format!("expected {} numbers in the ticket", fields),
+        ));
+    }
+
+    Ok(numbers)
 }
 
+// Every rule is valid for some positions of all tickets, the single one is found by elimination
 fn find_validation_combination<'a>(
     tickets: &Vec<Vec<usize>>,
     validations: &'a Vec<Validation<'a>>,
-) -> Vec<&'a Validation<'a>> {
-    let validations_count = validations.len();
-
-    let mut valid_rules = HashMap::<usize, &Validation>::new();
-
-    while valid_rules.len() != validations_count {
-        let mut candidate: Option<(&Validation, usize)> = None;
-
-        'rule: for rule in validations {
-            'pos: for pos in 0..validations_count {
-                if valid_rules.contains_key(&pos) {
-                    continue 'pos;
-                }
-
-                if tickets.iter().all(|ticket| rule.is_valid(ticket[pos])) {
-                    if candidate.is_none() {
-                        candidate = Some((rule, pos));
-                    } else {
-                        candidate = None;
-                        continue 'rule;
-                    }
-                }
-            }
+) -> Result<Vec<&'a Validation<'a>>, ParseError> {
+    let candidates = validations
+        .iter()
+        .enumerate()
+        .map(|(index, rule)| {
+            let positions = (0..validations.len())
+                .filter(|&pos| tickets.iter().all(|ticket| rule.is_valid(ticket[pos])))
+                .collect();
 
-            if let Some((candidate_rule, candidate_pos)) = candidate {
-                valid_rules.insert(candidate_pos, candidate_rule);
-            }
-        }
-    }
+            (index, positions)
+        })
+        .collect();
+
+    let positions = eliminate(candidates)
+        .ok_or_else(|| ParseError::input("the fields can't be matched to the ticket positions"))?;
 
-    valid_rules
+    Ok(positions
         .into_iter()
-        .sorted_by(|a, b| a.0.cmp(&b.0))
-        .map(|(_, rule)| rule)
-        .collect()
+        .sorted_by_key(|&(_, pos)| pos)
+        .map(|(index, _)| &validations[index])
+        .collect())
 }
 
 #[cfg(test)]
@@ -176,8 +206,8 @@ mod tests {
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_invalid_tickets(""), 0);
-        assert_eq!(find_departure_tickets(""), 0);
+        assert_eq!(find_invalid_tickets(""), Ok(0));
+        assert_eq!(find_departure_tickets(""), Ok(0));
     }
 
     #[test]
@@ -189,16 +219,25 @@ mod tests {
         assert_eq!(result.rules[1], (751, 959));
 
         let result = map_rule("arrival: platform: 42-729 or 751-959");
-        assert!(result.is_none());
+        assert!(result.is_err());
+
+        let result = map_rule("arrival platform: 42-729 or 959-751");
+        assert_eq!(
+            result.err(),
+            Some(ParseError::new(0, 29, "959-751", "the range is empty"))
+        );
     }
 
     #[test]
-    fn test_parse_numbers() {
-        let result = parse_numbers("7,3,47");
+    fn test_parse_ticket() {
+        let result = parse_ticket("7,3,47", 3).unwrap();
         assert_eq!(result.len(), 3);
         assert_eq!(result[0], 7);
         assert_eq!(result[1], 3);
         assert_eq!(result[2], 47);
+
+        assert!(parse_ticket("7,,47", 3).is_err());
+        assert!(parse_ticket("7,3", 3).is_err());
     }
 
     const INPUT: &'static str = r#"class: 1-3 or 5-7
@@ -218,13 +257,12 @@ nearby tickets:
     fn test_find_invalid_tickets() {
         let result = find_invalid_tickets(INPUT);
 
-        assert_eq!(result, 71);
+        assert_eq!(result, Ok(71));
     }
 
     #[test]
     fn test_validate_all_rules() {
-        let mut lines = INPUT.lines();
-        let rules = extract_rules(&mut lines);
+        let rules = extract_rules(&mut numbered_lines(INPUT)).unwrap();
         let result = validate_all_rules(&vec![7, 3, 47], &rules);
         assert!(result.is_empty());
 
@@ -234,11 +272,10 @@ nearby tickets:
 
     #[test]
     fn test_extract_rules() {
-        let mut lines = r#"class: 1-3 or 5-7
+        let lines = r#"class: 1-3 or 5-7
 row: 6-11 or 33-44
-seat: 13-40 or 45-50"#
-            .lines();
-        let result = extract_rules(&mut lines);
+seat: 13-40 or 45-50"#;
+        let result = extract_rules(&mut numbered_lines(lines)).unwrap();
 
         assert_eq!(result.len(), 3);
         assert_eq!(result[2].name, "seat");
@@ -246,8 +283,7 @@ seat: 13-40 or 45-50"#
 
     #[test]
     fn test_is_number_correct() {
-        let mut lines = INPUT.lines();
-        let rules = extract_rules(&mut lines);
+        let rules = extract_rules(&mut numbered_lines(INPUT)).unwrap();
 
         assert!(!is_number_correct(4, &rules));
         assert!(is_number_correct(40, &rules));
@@ -256,19 +292,21 @@ seat: 13-40 or 45-50"#
 
     #[test]
     fn test_find_validation_combination() {
-        let mut lines = r#"class: 0-1 or 4-19
+        let lines = r#"class: 0-1 or 4-19
 row: 0-5 or 8-19
-seat: 0-13 or 16-19"#
-            .lines();
-        let rules = extract_rules(&mut lines);
+seat: 0-13 or 16-19"#;
+        let rules = extract_rules(&mut numbered_lines(lines)).unwrap();
         let tickets = vec![vec![3, 9, 18], vec![15, 1, 5], vec![5, 14, 9]];
 
-        let ordered_rules = find_validation_combination(&tickets, &rules);
+        let ordered_rules = find_validation_combination(&tickets, &rules).unwrap();
 
         assert_eq!(ordered_rules.len(), rules.len());
         assert_eq!(ordered_rules[0].name, "row");
         assert_eq!(ordered_rules[1].name, "class");
         assert_eq!(ordered_rules[2].name, "seat");
+
+        let tickets = vec![vec![3, 9, 18]];
+        assert!(find_validation_combination(&tickets, &rules).is_err());
     }
 
     #[test]
@@ -285,6 +323,29 @@ nearby tickets:
 15,1,5
 5,14,9"#;
         let result = find_departure_tickets(input);
-        assert_eq!(result, 17);
+        assert_eq!(result, Ok(17));
+    }
+
+    #[test]
+    fn test_parse_notes() {
+        assert!(parse_notes(INPUT).is_ok());
+        assert_eq!(
+            parse_notes("class: 1-3 or 5-7\n\nyour tickets:\n7").err(),
+            Some(ParseError::new(
+                3,
+                1,
+                "your tickets:",
+                "expected \"your ticket:\""
+            ))
+        );
+        assert_eq!(
+            parse_notes("class: 1-3 or 5-7\n\nyour ticket:\n7\n\nnearby tickets:\n3,4").err(),
+            Some(ParseError::new(
+                7,
+                1,
+                "3,4",
+                "expected 1 numbers in the ticket"
+            ))
+        );
     }
 }
@@ -1,164 +1,90 @@
-use itertools::iproduct;
-use std::cmp::{max, min};
-use std::collections::HashSet;
-
-pub fn count_standard_cube(input: &str) -> usize {
-    let mut cube = Cube::load_from_string(input);
-    for _ in 1..=6 {
-        cube.evolve(false);
-    }
+use advgrid::{Automaton, Life, SparseGrid};
+use advparse::ParseError;
+use advrender::Scene;
+use std::iter::once;
+
+pub fn count_standard_cube(input: &str) -> Result<usize, ParseError> {
+    let mut cube: Cube<3> = Cube::load_from_string(input)?;
 
-    cube.count_actives()
+    Ok(cube.boot())
 }
 
-pub fn count_hyper_cube(input: &str) -> usize {
-    let mut cube = Cube::load_from_string(input);
-    for _ in 1..=6 {
-        cube.evolve(true);
-    }
+pub fn count_hyper_cube(input: &str) -> Result<usize, ParseError> {
+    let mut cube: Cube<4> = Cube::load_from_string(input)?;
+
+    Ok(cube.boot())
+}
+
+// The slices of every boot cycle
+pub fn animate(input: &str) -> Result<Vec<Scene>, ParseError> {
+    let standard: Cube<3> = Cube::load_from_string(input)?;
+    let hyper: Cube<4> = Cube::load_from_string(input)?;
 
-    cube.count_actives()
+    Ok(vec![standard.scene("standard"), hyper.scene("hyper")])
 }
 
-#[derive(Eq, PartialEq, Hash)]
-struct Coord(i32, i32, i32, i32);
+const CYCLES: usize = 6;
 
-struct Cube {
-    dim_x: (i32, i32),
-    dim_y: (i32, i32),
-    dim_z: (i32, i32),
-    dim_w: (i32, i32),
-    cells: HashSet<Coord>,
+// The standard cube has 3 dimensions, the hyper one has 4
+struct Cube<const N: usize> {
+    life: Automaton<SparseGrid<bool, N>, Life>,
 }
 
-impl Cube {
-    fn new() -> Cube {
+impl<const N: usize> Cube<N> {
+    fn from_cells(cells: SparseGrid<bool, N>) -> Cube<N> {
         Cube {
-            dim_x: (0, 0),
-            dim_y: (0, 0),
-            dim_z: (0, 0),
-            dim_w: (0, 0),
-            cells: HashSet::new(),
+            life: Automaton::new(cells, (), Life::CONWAY),
         }
     }
 
-    fn is_active(&self, coord: &Coord) -> bool {
-        self.cells.contains(coord)
+    fn cells(&self) -> &SparseGrid<bool, N> {
+        self.life.state()
     }
 
-    fn set(&mut self, coord: &Coord, active: bool) {
-        match active {
-            true => {
-                let &Coord(x, y, z, w) = coord;
-                self.cells.insert(Coord(x, y, z, w));
-
-                if !(self.dim_x.0..=self.dim_x.1).contains(&x) {
-                    self.dim_x.0 = min(self.dim_x.0, x);
-                    self.dim_x.1 = max(self.dim_x.1, x);
-                }
-
-                if !(self.dim_y.0..=self.dim_y.1).contains(&y) {
-                    self.dim_y.0 = min(self.dim_y.0, y);
-                    self.dim_y.1 = max(self.dim_y.1, y);
-                }
-
-                if !(self.dim_z.0..=self.dim_z.1).contains(&z) {
-                    self.dim_z.0 = min(self.dim_z.0, z);
-                    self.dim_z.1 = max(self.dim_z.1, z);
-                }
-
-                if !(self.dim_w.0..=self.dim_w.1).contains(&w) {
-                    self.dim_w.0 = min(self.dim_w.0, w);
-                    self.dim_w.1 = max(self.dim_w.1, w);
-                }
-            }
-            false => {
-                self.cells.remove(coord);
-            }
-        };
+    fn load_from_string(input: &str) -> Result<Cube<N>, ParseError> {
+        Ok(Cube::from_cells(SparseGrid::parse(input)?))
     }
 
-    fn load_from_string(input: &str) -> Cube {
-        let mut cube = Cube::new();
-
-        input
-            .lines()
-            .map(&str::trim)
-            .enumerate()
-            .for_each(|(row, line)| {
-                line.chars().enumerate().for_each(|(col, c)| match c {
-                    '#' => cube.set(&Coord(col as i32, row as i32, 0, 0), true),
-                    _ => (),
-                })
-            });
-
-        cube
+    fn evolve(&mut self) {
+        self.life.step();
     }
 
-    fn evolve(&mut self, is_hyper: bool) {
-        let mut new_cube = Cube::new();
-
-        iproduct!(
-            self.dim_x.0 - 1..=self.dim_x.1 + 1,
-            self.dim_y.0 - 1..=self.dim_y.1 + 1,
-            self.dim_z.0 - 1..=self.dim_z.1 + 1,
-            self.dim_w.0 - 1..=self.dim_w.1 + 1
-        )
-        .filter(|&(_, _, _, w)| is_hyper || w == 0)
-        .for_each(|(x, y, z, w)| {
-            let coord = Coord(x, y, z, w);
-            let should_activate = self.evolve_cell(&coord, is_hyper);
-
-            new_cube.set(&coord, should_activate);
-        });
+    // The active cubes after the boot cycles
+    fn boot(&mut self) -> usize {
+        for _ in 0..CYCLES {
+            self.evolve();
+        }
 
-        self.cells = new_cube.cells;
-        self.dim_x = new_cube.dim_x;
-        self.dim_y = new_cube.dim_y;
-        self.dim_z = new_cube.dim_z;
-        self.dim_w = new_cube.dim_w;
+        self.count_actives()
     }
 
-    fn evolve_cell(&self, coord: &Coord, is_hyper: bool) -> bool {
-        let &Coord(cell_x, cell_y, cell_z, cell_w) = coord;
-
-        let neighbours_count = iproduct!(-1..=1, -1..=1, -1..=1, -1..=1)
-            .filter(|&(_, _, _, w)| is_hyper || w == 0)
-            .filter(|&(dx, dy, dz, dw)| !(dx == 0 && dy == 0 && dz == 0 && dw == 0)) // exclude center
-            .filter(|&(dx, dy, dz, dw)| {
-                self.is_active(&Coord(cell_x + dx, cell_y + dy, cell_z + dz, cell_w + dw))
-            })
-            .count();
+    fn count_actives(&self) -> usize {
+        self.life.population()
+    }
 
-        let current_cell = self.is_active(coord);
+    fn scene(mut self, name: &str) -> Scene {
+        let draw = |cube: &Cube<N>| {
+            format!(
+                "cycle {}/{}, {} active\n{}",
+                cube.life.generation(),
+                CYCLES,
+                cube.count_actives(),
+                cube.cells()
+            )
+        };
 
-        match current_cell {
-            true => neighbours_count == 2 || neighbours_count == 3,
-            false => neighbours_count == 3,
-        }
-    }
+        let first = draw(&self);
+        let rest = (0..CYCLES).map(move |_| {
+            self.evolve();
+            draw(&self)
+        });
 
-    fn count_actives(&self) -> usize {
-        self.cells.len()
+        Scene::new(name, once(first).chain(rest))
     }
 
     #[allow(dead_code)]
     fn print(&self) {
-        for w in self.dim_w.0..=self.dim_w.1 {
-            for z in self.dim_z.0..=self.dim_z.1 {
-                println!("z={}, w={}", z, w);
-                for y in self.dim_y.0..=self.dim_y.1 {
-                    for x in self.dim_x.0..=self.dim_x.1 {
-                        let coord = Coord(x, y, z, w);
-
-                        print!("{}", if self.is_active(&coord) { '#' } else { '.' });
-                    }
-                    println!();
-                }
-
-                println!();
-            }
-        }
+        print!("{}", self.cells());
     }
 }
 
@@ -166,87 +92,117 @@ impl Cube {
 mod tests {
     use super::*;
 
+    impl<const N: usize> Cube<N> {
+        fn new() -> Cube<N> {
+            Cube::from_cells(SparseGrid::new())
+        }
+
+        fn is_active(&self, coord: &[i64; N]) -> bool {
+            self.cells().contains(coord)
+        }
+
+        fn set(&mut self, coord: [i64; N], active: bool) {
+            self.life.update(|cells| {
+                if active {
+                    cells.insert(coord, true);
+                } else {
+                    cells.remove(&coord);
+                }
+            });
+        }
+    }
+
     const INPUT: &str = r#".#.
                         ..#
                         ###"#;
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(count_standard_cube(""), 0);
-        assert_eq!(count_hyper_cube(""), 0);
+        assert_eq!(count_standard_cube(""), Ok(0));
+        assert_eq!(count_hyper_cube(""), Ok(0));
     }
 
     #[test]
     fn test_cube_set() {
-        let mut cube = Cube::new();
-        assert!(cube.cells.is_empty());
-        assert!(!cube.is_active(&Coord(0, 0, 0, 0)));
+        let mut cube: Cube<4> = Cube::new();
+        assert!(cube.cells().is_empty());
+        assert!(!cube.is_active(&[0, 0, 0, 0]));
 
-        cube.set(&Coord(0, 0, 0, 0), true);
-        assert!(cube.is_active(&Coord(0, 0, 0, 0)));
+        cube.set([0, 0, 0, 0], true);
+        assert!(cube.is_active(&[0, 0, 0, 0]));
 
-        let coord123 = Coord(1, 2, 3, 0);
-        cube.set(&coord123, true);
-        assert_eq!(cube.dim_x, (0, 1));
-        assert_eq!(cube.dim_y, (0, 2));
-        assert_eq!(cube.dim_z, (0, 3));
+        let coord123 = [1, 2, 3, 0];
+        cube.set(coord123, true);
+        assert_eq!(cube.cells().bounds(), Some(([0, 0, 0, 0], [1, 2, 3, 0])));
 
-        cube.set(&coord123, false);
+        cube.set(coord123, false);
         assert!(!cube.is_active(&coord123));
-        assert_eq!(cube.dim_x, (0, 1));
-        assert_eq!(cube.dim_y, (0, 2));
-        assert_eq!(cube.dim_z, (0, 3));
+        assert_eq!(cube.cells().bounds(), Some(([0, 0, 0, 0], [0, 0, 0, 0])));
     }
 
     #[test]
     fn test_load_from_string() {
-        let cube = Cube::load_from_string(INPUT);
-        assert_eq!(cube.dim_x, (0, 2));
-        assert_eq!(cube.dim_y, (0, 2));
-        assert_eq!(cube.dim_z, (0, 0));
-        assert_eq!(cube.dim_w, (0, 0));
-
-        assert!(!cube.is_active(&Coord(0, 0, 0, 0)));
-        assert!(cube.is_active(&Coord(1, 0, 0, 0)));
-        assert!(!cube.is_active(&Coord(2, 0, 0, 0)));
+        let cube: Cube<4> = Cube::load_from_string(INPUT).unwrap();
+        assert_eq!(cube.cells().bounds(), Some(([0, 0, 0, 0], [2, 2, 0, 0])));
+
+        assert!(!cube.is_active(&[0, 0, 0, 0]));
+        assert!(cube.is_active(&[1, 0, 0, 0]));
+        assert!(!cube.is_active(&[2, 0, 0, 0]));
+
+        assert_eq!(
+            Cube::<3>::load_from_string(".#.\n.o#").err(),
+            Some(ParseError::new(2, 2, "o", "expected # or ."))
+        );
     }
 
     #[test]
     fn test_evolve() {
-        let mut cube = Cube::load_from_string(INPUT);
+        let mut cube: Cube<3> = Cube::load_from_string(INPUT).unwrap();
 
-        cube.evolve(false);
+        cube.evolve();
         cube.print();
 
-        assert!(cube.is_active(&Coord(0, 1, 0, 0)));
-        assert!(!cube.is_active(&Coord(1, 1, 0, 0)));
-        assert!(cube.is_active(&Coord(2, 1, 0, 0)));
+        assert!(cube.is_active(&[0, 1, 0]));
+        assert!(!cube.is_active(&[1, 1, 0]));
+        assert!(cube.is_active(&[2, 1, 0]));
 
-        assert!(!cube.is_active(&Coord(0, 2, 0, 0)));
-        assert!(cube.is_active(&Coord(1, 2, 0, 0)));
-        assert!(cube.is_active(&Coord(2, 2, 0, 0)));
+        assert!(!cube.is_active(&[0, 2, 0]));
+        assert!(cube.is_active(&[1, 2, 0]));
+        assert!(cube.is_active(&[2, 2, 0]));
 
-        assert!(!cube.is_active(&Coord(0, 3, 0, 0)));
-        assert!(cube.is_active(&Coord(1, 3, 0, 0)));
-        assert!(!cube.is_active(&Coord(2, 3, 0, 0)));
+        assert!(!cube.is_active(&[0, 3, 0]));
+        assert!(cube.is_active(&[1, 3, 0]));
+        assert!(!cube.is_active(&[2, 3, 0]));
 
         assert_eq!(cube.count_actives(), 11);
 
         for _ in 2..=6 {
-            cube.evolve(false);
+            cube.evolve();
         }
 
         assert_eq!(cube.count_actives(), 112);
     }
 
     #[test]
-    fn test_evolve_hyper_cube() {
-        let mut cube = Cube::load_from_string(INPUT);
+    fn test_animate() {
+        let scenes = animate(INPUT).unwrap();
+        assert_eq!(scenes[0].name(), "standard");
+        assert_eq!(scenes[1].name(), "hyper");
+
+        let frames: Vec<String> = animate(INPUT).unwrap().remove(0).collect();
+        assert_eq!(frames.len(), CYCLES + 1);
+        assert_eq!(frames[0], "cycle 0/6, 5 active\nz=0\n.#.\n..#\n###\n");
+        assert!(frames[1].starts_with("cycle 1/6, 11 active\nz=-1\n#..\n..#\n.#.\n"));
+        assert!(frames[6].starts_with("cycle 6/6, 112 active\n"));
+
+        assert!(animate("x").is_err());
+    }
 
-        for _ in 1..=6 {
-            cube.evolve(true);
-        }
+    #[test]
+    fn test_evolve_hyper_cube() {
+        let mut cube: Cube<4> = Cube::load_from_string(INPUT).unwrap();
 
-        assert_eq!(cube.count_actives(), 848);
+        assert_eq!(cube.boot(), 848);
+        assert_eq!(cube.life.populations()[1..3], [29, 60]);
     }
 }
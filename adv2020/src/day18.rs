@@ -1,26 +1,386 @@
-pub fn find_answer1(input: &str) -> usize {
-    0
+use advparse::{parse_lines, ParseError};
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+// Both operators are equal, the expression is evaluated from left to right
+const EQUAL: Precedence = Precedence {
+    addition: 1,
+    multiplication: 1,
+};
+
+// The addition is evaluated before the multiplication
+const ADDITION_FIRST: Precedence = Precedence {
+    addition: 2,
+    multiplication: 1,
+};
+
+pub fn find_answer1(input: &str) -> Result<u64, ParseError> {
+    sum_of_expressions(input, EQUAL)
+}
+
+pub fn find_answer2(input: &str) -> Result<u64, ParseError> {
+    sum_of_expressions(input, ADDITION_FIRST)
+}
+
+fn sum_of_expressions(input: &str, precedence: Precedence) -> Result<u64, ParseError> {
+    let expressions = parse_input(input, precedence)?;
+
+    Ok(expressions.iter().map(Expression::evaluate).sum())
+}
+
+// The higher operator binds stronger; the operators of the same precedence
+// are evaluated from left to right
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Precedence {
+    addition: u8,
+    multiplication: u8,
+}
+
+impl Precedence {
+    fn of(&self, operator: Operator) -> u8 {
+        match operator {
+            Operator::Add => self.addition,
+            Operator::Multiply => self.multiplication,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Expression {
+    Number(u64),
+    Operation(Operator, Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    fn evaluate(&self) -> u64 {
+        match self {
+            Expression::Number(number) => *number,
+            Expression::Operation(Operator::Add, left, right) => left.evaluate() + right.evaluate(),
+            Expression::Operation(Operator::Multiply, left, right) => {
+                left.evaluate() * right.evaluate()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Number(u64),
+    Operator(Operator),
+    Open,
+    Close,
+}
+
+// The text is a part of the line, so the errors know their columns
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+}
+
+// 2 * 3 + (4 * 5)
+fn tokenize(line: &str) -> Result<Vec<Token<'_>>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+
+    while let Some(c) = line[start..].chars().next() {
+        let length = match c {
+            '0'..='9' => line[start..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(line.len() - start),
+            _ => c.len_utf8(),
+        };
+        let text = &line[start..start + length];
+        start += length;
+
+        let kind = match c {
+            _ if c.is_whitespace() => continue,
+            '+' => Kind::Operator(Operator::Add),
+            '*' => Kind::Operator(Operator::Multiply),
+            '(' => Kind::Open,
+            ')' => Kind::Close,
+            '0'..='9' => Kind::Number(
+                text.parse()
+                    .map_err(|_| ParseError::in_line(line, text, "expected a smaller number"))?,
+            ),
+            _ => {
+                return Err(ParseError::in_line(
+                    line,
+                    text,
+                    "expected a number, an operator or a parenthesis",
+                ))
+            }
+        };
+
+        tokens.push(Token { kind, text });
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    line: &'a str,
+    tokens: Peekable<IntoIter<Token<'a>>>,
+    precedence: Precedence,
+}
+
+impl<'a> Parser<'a> {
+    // The operators bind at least as strong as the minimum
+    fn expression(&mut self, minimum: u8) -> Result<Expression, ParseError> {
+        let mut left = self.operand()?;
+
+        while let Some(operator) = self.next_operator(minimum) {
+            let right = self.expression(self.precedence.of(operator) + 1)?;
+            left = Expression::Operation(operator, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn next_operator(&mut self, minimum: u8) -> Option<Operator> {
+        let precedence = self.precedence;
+        let token = self.tokens.next_if(|token| {
+            matches!(token.kind, Kind::Operator(operator) if precedence.of(operator) >= minimum)
+        })?;
+
+        match token.kind {
+            Kind::Operator(operator) => Some(operator),
+            _ => None,
+        }
+    }
+
+    // A number or an expression in parentheses
+    fn operand(&mut self) -> Result<Expression, ParseError> {
+        let token = self.tokens.next().ok_or_else(|| {
+            ParseError::end_of_line(self.line, "expected a number or a parenthesis")
+        })?;
+
+        match token.kind {
+            Kind::Number(number) => Ok(Expression::Number(number)),
+            Kind::Open => {
+                let expression = self.expression(0)?;
+                self.close()?;
+
+                Ok(expression)
+            }
+            _ => Err(ParseError::in_line(
+                self.line,
+                token.text,
+                "expected a number or a parenthesis",
+            )),
+        }
+    }
+
+    fn close(&mut self) -> Result<(), ParseError> {
+        match self.tokens.next() {
+            Some(Token {
+                kind: Kind::Close, ..
+            }) => Ok(()),
+            Some(token) => Err(ParseError::in_line(
+                self.line,
+                token.text,
+                "expected an operator or )",
+            )),
+            None => Err(ParseError::end_of_line(self.line, "expected )")),
+        }
+    }
+
+    // The whole line is a single expression
+    fn parse(mut self) -> Result<Expression, ParseError> {
+        let expression = self.expression(0)?;
+
+        match self.tokens.next() {
+            Some(token) => Err(ParseError::in_line(
+                self.line,
+                token.text,
+                "expected an operator",
+            )),
+            None => Ok(expression),
+        }
+    }
 }
 
-pub fn find_answer2(input: &str) -> usize {
-    0
+fn parse_expression(line: &str, precedence: Precedence) -> Result<Expression, ParseError> {
+    let parser = Parser {
+        line,
+        tokens: tokenize(line)?.into_iter().peekable(),
+        precedence,
+    };
+
+    parser.parse()
 }
-/*
-fn parse_input<'a>(input: &'a str) -> impl Iterator<Item=Seat> + 'a {
-    input.lines()
-        .map(&str::trim)
-        .map(parse_seat)
+
+fn parse_input(input: &str, precedence: Precedence) -> Result<Vec<Expression>, ParseError> {
+    parse_lines(input, |line| parse_expression(line, precedence))
 }
-*/
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLES: [(&str, u64, u64); 6] = [
+        ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+        ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+        ("2 * 3 + (4 * 5)", 26, 46),
+        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+        ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+        (
+            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+            13632,
+            23340,
+        ),
+    ];
+
+    fn evaluate(line: &str, precedence: Precedence) -> u64 {
+        parse_expression(line, precedence).unwrap().evaluate()
+    }
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_answer1(""), 0);
-        assert_eq!(find_answer2(""), 0);
+        assert_eq!(find_answer1(""), Ok(0));
+        assert_eq!(find_answer2(""), Ok(0));
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let kinds: Vec<Kind> = tokenize("12*(3 +4)")
+            .unwrap()
+            .iter()
+            .map(|token| token.kind)
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::Number(12),
+                Kind::Operator(Operator::Multiply),
+                Kind::Open,
+                Kind::Number(3),
+                Kind::Operator(Operator::Add),
+                Kind::Number(4),
+                Kind::Close,
+            ]
+        );
+        assert_eq!(tokenize("12*(3 +4)").unwrap()[0].text, "12");
+        assert_eq!(
+            tokenize("99999999999999999999"),
+            Err(ParseError::new(
+                0,
+                1,
+                "99999999999999999999",
+                "expected a smaller number"
+            ))
+        );
+        assert_eq!(
+            tokenize("1 - 2"),
+            Err(ParseError::new(
+                0,
+                3,
+                "-",
+                "expected a number, an operator or a parenthesis"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let number = |n| Box::new(Expression::Number(n));
+
+        assert_eq!(
+            parse_expression("1 + 2 * 3", EQUAL),
+            Ok(Expression::Operation(
+                Operator::Multiply,
+                Box::new(Expression::Operation(Operator::Add, number(1), number(2))),
+                number(3)
+            ))
+        );
+        assert_eq!(
+            parse_expression("1 * 2 + 3", ADDITION_FIRST),
+            Ok(Expression::Operation(
+                Operator::Multiply,
+                number(1),
+                Box::new(Expression::Operation(Operator::Add, number(2), number(3)))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_wrong_expressions() {
+        assert_eq!(
+            parse_expression("1 + ", EQUAL),
+            Err(ParseError::new(
+                0,
+                5,
+                "",
+                "expected a number or a parenthesis"
+            ))
+        );
+        assert_eq!(
+            parse_expression("(1 + 2", EQUAL),
+            Err(ParseError::new(0, 7, "", "expected )"))
+        );
+        assert_eq!(
+            parse_expression("1 + 2)", EQUAL),
+            Err(ParseError::new(0, 6, ")", "expected an operator"))
+        );
+        assert_eq!(
+            parse_expression("(1 2)", EQUAL),
+            Err(ParseError::new(0, 4, "2", "expected an operator or )"))
+        );
+        assert_eq!(
+            parse_expression("* 2", EQUAL),
+            Err(ParseError::new(
+                0,
+                1,
+                "*",
+                "expected a number or a parenthesis"
+            ))
+        );
+        assert_eq!(
+            parse_input("1 + 2\n3 + +", EQUAL),
+            Err(ParseError::new(
+                2,
+                5,
+                "+",
+                "expected a number or a parenthesis"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        for (line, equal, addition_first) in EXAMPLES {
+            assert_eq!(evaluate(line, EQUAL), equal, "{}", line);
+            assert_eq!(evaluate(line, ADDITION_FIRST), addition_first, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_precedence() {
+        // The multiplication first is the usual arithmetic
+        let multiplication_first = Precedence {
+            addition: 1,
+            multiplication: 2,
+        };
+
+        assert_eq!(evaluate("1 + 2 * 3 + 4 * 5 + 6", multiplication_first), 33);
+        assert_eq!(evaluate("2 * (3 + 4) * 5", multiplication_first), 70);
+    }
+
+    #[test]
+    fn test_answers() {
+        let input = EXAMPLES.map(|(line, _, _)| line).join("\n");
+
+        assert_eq!(find_answer1(&input), Ok(71 + 51 + 26 + 437 + 12240 + 13632));
+        assert_eq!(
+            find_answer2(&input),
+            Ok(231 + 51 + 46 + 1445 + 669060 + 23340)
+        );
     }
 }
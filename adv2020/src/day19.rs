@@ -0,0 +1,432 @@
+use advparse::{numbered_lines, parse_number, ParseError};
+use std::collections::HashMap;
+
+pub fn count_matching_messages(input: &str) -> Result<usize, ParseError> {
+    let (grammar, messages) = parse_input(input)?;
+
+    Ok(grammar.count_matching(&messages))
+}
+
+pub fn count_matching_messages_with_loops(input: &str) -> Result<usize, ParseError> {
+    let (grammar, messages) = parse_input(input)?;
+
+    Ok(grammar.with_loops()?.count_matching(&messages))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Rule {
+    Letter(u8),
+    // Every alternative is a sequence of other rules
+    Alternatives(Vec<Vec<usize>>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Grammar {
+    rules: HashMap<usize, Rule>,
+}
+
+impl Grammar {
+    // Part 2 replaces two rules with the looping ones:
+    // 8: 42 | 42 8
+    // 11: 42 31 | 42 11 31
+    fn with_loops(mut self) -> Result<Grammar, ParseError> {
+        for rule in [42, 31] {
+            if !self.rules.contains_key(&rule) {
+                return Err(ParseError::input(format!(
+                    "the looping rules need the rule {}",
+                    rule
+                )));
+            }
+        }
+
+        self.rules
+            .insert(8, Rule::Alternatives(vec![vec![42], vec![42, 8]]));
+        self.rules
+            .insert(11, Rule::Alternatives(vec![vec![42, 31], vec![42, 11, 31]]));
+
+        match self.left_recursive() {
+            Some(rule) => Err(ParseError::input(format!(
+                "the rule {} is left-recursive",
+                rule
+            ))),
+            None => Ok(self),
+        }
+    }
+
+    // A rule, which can start with itself, e.g. 2: 1 | 2 1, would call itself at the same
+    // position forever; the rules, which start other alternatives, make the cycle
+    fn left_recursive(&self) -> Option<usize> {
+        let mut ids: Vec<usize> = self.rules.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut visited = HashMap::new();
+        ids.into_iter()
+            .find_map(|rule| self.find_cycle(rule, &mut visited))
+    }
+
+    // The visited rules are either on the current path (false) or done (true)
+    fn find_cycle(&self, rule: usize, visited: &mut HashMap<usize, bool>) -> Option<usize> {
+        match visited.get(&rule) {
+            Some(true) => return None,
+            Some(false) => return Some(rule),
+            None => (),
+        }
+
+        visited.insert(rule, false);
+        if let Some(Rule::Alternatives(alternatives)) = self.rules.get(&rule) {
+            for &first in alternatives.iter().filter_map(|sequence| sequence.first()) {
+                if let Some(cycle) = self.find_cycle(first, visited) {
+                    return Some(cycle);
+                }
+            }
+        }
+        visited.insert(rule, true);
+
+        None
+    }
+
+    fn count_matching(&self, messages: &[&str]) -> usize {
+        messages
+            .iter()
+            .filter(|message| self.matches(message))
+            .count()
+    }
+
+    // The whole message must match the rule 0
+    fn matches(&self, message: &str) -> bool {
+        self.ends(0, message.as_bytes(), 0).contains(&message.len())
+    }
+
+    // Every position, where a match of the rule, which starts at the position, can end.
+    // The loops stop, because every rule consumes at least a letter, and none is left-recursive
+    fn ends(&self, rule: usize, message: &[u8], start: usize) -> Vec<usize> {
+        match &self.rules[&rule] {
+            Rule::Letter(letter) => match message.get(start) {
+                Some(c) if c == letter => vec![start + 1],
+                _ => Vec::new(),
+            },
+            Rule::Alternatives(alternatives) => alternatives
+                .iter()
+                .flat_map(|sequence| {
+                    sequence.iter().fold(vec![start], |positions, &rule| {
+                        positions
+                            .into_iter()
+                            .flat_map(|position| self.ends(rule, message, position))
+                            .collect()
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+// The rules, a blank line and the messages
+fn parse_input(input: &str) -> Result<(Grammar, Vec<&str>), ParseError> {
+    let mut lines = numbered_lines(input);
+    let mut rules = HashMap::new();
+    let mut references = Vec::new();
+    let mut definitions = HashMap::new();
+
+    for (number, line) in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+
+        let (id, rule) = parse_rule(line).map_err(|err| err.at_line(number))?;
+        if let Rule::Alternatives(alternatives) = &rule {
+            references.extend(
+                alternatives
+                    .iter()
+                    .flatten()
+                    .map(|&other| (other, number, line)),
+            );
+        }
+
+        definitions.insert(id, (number, line));
+        if rules.insert(id, rule).is_some() {
+            return Err(
+                ParseError::in_line(line, line, "the rule is defined twice").at_line(number)
+            );
+        }
+    }
+
+    if !rules.contains_key(&0) {
+        return Err(ParseError::input("the rule 0 is missing"));
+    }
+    if let Some((rule, number, line)) = references
+        .into_iter()
+        .find(|(rule, _, _)| !rules.contains_key(rule))
+    {
+        let (_, body) = line.split_once(':').unwrap_or_default();
+        let token = body
+            .split_whitespace()
+            .find(|token| token.parse() == Ok(rule))
+            .unwrap_or(line);
+
+        return Err(
+            ParseError::in_line(line, token, format!("the rule {} is missing", rule))
+                .at_line(number),
+        );
+    }
+
+    let grammar = Grammar { rules };
+    if let Some((number, line)) = grammar
+        .left_recursive()
+        .and_then(|rule| definitions.get(&rule))
+    {
+        return Err(ParseError::in_line(line, line, "the rule is left-recursive").at_line(*number));
+    }
+
+    let messages = lines
+        .map(|(_, line)| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    Ok((grammar, messages))
+}
+
+// 1: 2 3 | 3 2
+// 4: "a"
+fn parse_rule(line: &str) -> Result<(usize, Rule), ParseError> {
+    let (id, body) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::end_of_line(line, "expected \":\""))?;
+    let id = parse_number(line, id.trim())?;
+    let body = body.trim();
+
+    if body.starts_with('"') {
+        return match body.as_bytes() {
+            [b'"', letter, b'"'] if letter.is_ascii_alphabetic() => Ok((id, Rule::Letter(*letter))),
+            _ => Err(ParseError::in_line(
+                line,
+                body,
+                "expected a letter in quotes",
+            )),
+        };
+    }
+
+    let alternatives = body
+        .split('|')
+        .map(|sequence| {
+            let rules: Vec<usize> = sequence
+                .split_whitespace()
+                .map(|rule| parse_number(line, rule))
+                .collect::<Result<_, _>>()?;
+
+            match rules.is_empty() {
+                true => Err(ParseError::in_line(
+                    line,
+                    sequence,
+                    "expected a rule number",
+                )),
+                false => Ok(rules),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((id, Rule::Alternatives(alternatives)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"0: 4 1 5
+                           1: 2 3 | 3 2
+                           2: 4 4 | 5 5
+                           3: 4 5 | 5 4
+                           4: "a"
+                           5: "b"
+
+                           ababbb
+                           bababa
+                           abbbab
+                           aaabbb
+                           aaaabbb"#;
+
+    const LOOPS: &str = r#"42: 9 14 | 10 1
+                           9: 14 27 | 1 26
+                           10: 23 14 | 28 1
+                           1: "a"
+                           11: 42 31
+                           5: 1 14 | 15 1
+                           19: 14 1 | 14 14
+                           12: 24 14 | 19 1
+                           16: 15 1 | 14 14
+                           31: 14 17 | 1 13
+                           6: 14 14 | 1 14
+                           2: 1 24 | 14 4
+                           0: 8 11
+                           13: 14 3 | 1 12
+                           15: 1 | 14
+                           17: 14 2 | 1 7
+                           23: 25 1 | 22 14
+                           28: 16 1
+                           4: 1 1
+                           20: 14 14 | 1 15
+                           3: 5 14 | 16 1
+                           27: 1 6 | 14 18
+                           14: "b"
+                           21: 14 1 | 1 14
+                           25: 1 1 | 1 14
+                           22: 14 14
+                           8: 42
+                           26: 14 22 | 1 20
+                           18: 15 15
+                           7: 14 5 | 1 21
+                           24: 14 1
+
+                           abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+                           bbabbbbaabaabba
+                           babbbbaabbbbbabbbbbbaabaaabaaa
+                           aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+                           bbbbbbbaaaabbbbaaabbabaaa
+                           bbbababbbbaaaaaaaabbababaaababaabab
+                           ababaaaaaabaaab
+                           ababaaaaabbbaba
+                           baabbaaaabbaaaababbaababb
+                           abbbbabbbbaaaababbbbbbaaaababb
+                           aaaaabbaabaaaaababaa
+                           aaaabbaaaabbaaa
+                           aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+                           babaaabbbaaabaababbaabababaaab
+                           aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
+
+    #[test]
+    fn test_empty_answers() {
+        assert!(count_matching_messages("").is_err());
+        assert!(count_matching_messages_with_loops("").is_err());
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!(parse_rule("4: \"a\""), Ok((4, Rule::Letter(b'a'))));
+        assert_eq!(
+            parse_rule("1: 2 3 | 3 2"),
+            Ok((1, Rule::Alternatives(vec![vec![2, 3], vec![3, 2]])))
+        );
+        assert_eq!(
+            parse_rule("8: 42"),
+            Ok((8, Rule::Alternatives(vec![vec![42]])))
+        );
+
+        assert_eq!(
+            parse_rule("1 2 3"),
+            Err(ParseError::new(0, 6, "", "expected \":\""))
+        );
+        assert_eq!(
+            parse_rule("4: \"ab\""),
+            Err(ParseError::new(
+                0,
+                4,
+                "\"ab\"",
+                "expected a letter in quotes"
+            ))
+        );
+        assert_eq!(
+            parse_rule("1: 2 x"),
+            Err(ParseError::new(0, 6, "x", "expected a number"))
+        );
+        assert_eq!(
+            parse_rule("1: 2 |"),
+            Err(ParseError::new(0, 7, "", "expected a rule number"))
+        );
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let (grammar, messages) = parse_input(INPUT).unwrap();
+
+        assert_eq!(grammar.rules.len(), 6);
+        assert_eq!(
+            messages,
+            vec!["ababbb", "bababa", "abbbab", "aaabbb", "aaaabbb"]
+        );
+
+        assert_eq!(
+            parse_input("0: 1\n1: \"a\"\n1: \"b\"").err(),
+            Some(ParseError::new(
+                3,
+                1,
+                "1: \"b\"",
+                "the rule is defined twice"
+            ))
+        );
+        assert_eq!(
+            parse_input("0: 1 2\n1: \"a\"").err(),
+            Some(ParseError::new(1, 6, "2", "the rule 2 is missing"))
+        );
+        assert_eq!(
+            parse_input("1: \"a\"\n\na").err(),
+            Some(ParseError::input("the rule 0 is missing"))
+        );
+    }
+
+    #[test]
+    fn test_left_recursive() {
+        assert_eq!(
+            parse_input("0: 0").err(),
+            Some(ParseError::new(1, 1, "0: 0", "the rule is left-recursive"))
+        );
+        assert_eq!(
+            parse_input("0: 2\n1: \"a\"\n2: 1 | 2 1").err(),
+            Some(ParseError::new(
+                3,
+                1,
+                "2: 1 | 2 1",
+                "the rule is left-recursive"
+            ))
+        );
+        assert_eq!(
+            parse_input("0: 1 3\n1: 3 | 2\n2: \"a\"\n3: 1 2").err(),
+            Some(ParseError::new(
+                2,
+                1,
+                "1: 3 | 2",
+                "the rule is left-recursive"
+            ))
+        );
+        // The recursion after the first rule is fine
+        assert!(parse_input("0: 1 | 1 0\n1: \"a\"").is_ok());
+
+        let (grammar, _) = parse_input("0: 8 11\n42: 8\n8: \"a\"\n31: \"b\"\n11: 42 31").unwrap();
+        assert_eq!(
+            grammar.with_loops(),
+            Err(ParseError::input("the rule 8 is left-recursive"))
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let (grammar, _) = parse_input(INPUT).unwrap();
+
+        assert!(grammar.matches("ababbb"));
+        assert!(grammar.matches("abbbab"));
+        assert!(!grammar.matches("bababa"));
+        assert!(!grammar.matches("aaabbb"));
+        // The rule matches only the beginning
+        assert!(!grammar.matches("aaaabbb"));
+        assert!(!grammar.matches(""));
+    }
+
+    #[test]
+    fn test_with_loops() {
+        let (grammar, _) = parse_input(LOOPS).unwrap();
+        let message = "babbbbaabbbbbabbbbbbaabaaabaaa";
+        assert!(!grammar.matches(message));
+        assert!(grammar.with_loops().unwrap().matches(message));
+
+        let (grammar, _) = parse_input(INPUT).unwrap();
+        assert_eq!(
+            grammar.with_loops(),
+            Err(ParseError::input("the looping rules need the rule 42"))
+        );
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(count_matching_messages(INPUT), Ok(2));
+        assert_eq!(count_matching_messages(LOOPS), Ok(3));
+        assert_eq!(count_matching_messages_with_loops(LOOPS), Ok(12));
+    }
+}
@@ -1,9 +1,17 @@
-pub fn count_simple_passwords(input: &str) -> usize {
-    parse_input(input).filter(is_password_simple).count()
+use advparse::{next_token, parse_lines, parse_number, ParseError};
+
+pub fn count_simple_passwords(input: &str) -> Result<usize, ParseError> {
+    Ok(parse_input(input)?
+        .iter()
+        .filter(|rule| is_password_simple(rule))
+        .count())
 }
 
-pub fn count_complex_passwords(input: &str) -> usize {
-    parse_input(input).filter(is_password_complex).count()
+pub fn count_complex_passwords(input: &str) -> Result<usize, ParseError> {
+    Ok(parse_input(input)?
+        .iter()
+        .filter(|rule| is_password_complex(rule))
+        .count())
 }
 
 fn is_password_simple(rule: &Rule) -> bool {
@@ -36,30 +44,51 @@ struct Rule {
     password: String,
 }
 
-fn parse_input<'a>(input: &'a str) -> impl Iterator<Item = Rule> + 'a {
-    input.lines().filter_map(|line| {
-        let v: Vec<&str> = line
-            .split(|c: char| c.is_whitespace() || c.is_ascii_punctuation())
-            .filter(|&s| !s.is_empty())
-            .collect();
-
-        if v.len() != 4 {
-            return None;
-        }
-
-        let from = v[0].parse::<usize>().ok();
-        let to = v[1].parse::<usize>().ok();
-        let letter = v[2].parse::<char>().ok();
-
-        Some(Rule {
-            from: from?,
-            to: to?,
-            letter: letter?,
-            password: String::from(v[3]),
-        })
+// 1-3 a: abcde
+fn parse_rule(line: &str) -> Result<Rule, ParseError> {
+    let mut tokens = line
+        .split(|c: char| c.is_whitespace() || c == '-' || c == ':')
+        .filter(|s| !s.is_empty());
+
+    let from = parse_number(line, next_token(line, &mut tokens, "a position")?)?;
+    let to_token = next_token(line, &mut tokens, "a position")?;
+    let to = parse_number(line, to_token)?;
+    if from == 0 || from > to {
+        return Err(ParseError::in_line(
+            line,
+            to_token,
+            "expected a range starting with 1",
+        ));
+    }
+
+    let letter = next_token(line, &mut tokens, "a letter")?;
+    let mut chars = letter.chars();
+    let letter = match (chars.next(), chars.next()) {
+        (Some(c), None) => c,
+        _ => return Err(ParseError::in_line(line, letter, "expected a letter")),
+    };
+    let password = next_token(line, &mut tokens, "a password")?;
+
+    if let Some(token) = tokens.next() {
+        return Err(ParseError::in_line(
+            line,
+            token,
+            "expected the end of the line",
+        ));
+    }
+
+    Ok(Rule {
+        from,
+        to,
+        letter,
+        password: String::from(password),
     })
 }
 
+fn parse_input(input: &str) -> Result<Vec<Rule>, ParseError> {
+    parse_lines(input, parse_rule)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,16 +99,19 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let result: Vec<Rule> = parse_input(
-            r#"17-18 f: fffffffffffffffffff
-                                    1 abc"#,
-        )
-        .collect();
+        let result: Vec<Rule> = parse_input(r#"17-18 f: fffffffffffffffffff"#).unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].from, 17);
         assert_eq!(result[0].to, 18);
         assert_eq!(result[0].letter, 'f');
         assert_eq!(result[0].password, "fffffffffffffffffff");
+
+        assert_eq!(
+            parse_input("1-3 a: abcde\n                                    1 abc").err(),
+            Some(ParseError::new(2, 39, "abc", "expected a number"))
+        );
+        assert!(parse_input("0-3 a: abcde").is_err());
+        assert!(parse_input("1-3 ab: abcde").is_err());
     }
 
     #[test]
@@ -117,11 +149,11 @@ mod tests {
 
     #[test]
     fn test_count_simple_passwords() {
-        assert_eq!(count_simple_passwords(INPUT), 2);
+        assert_eq!(count_simple_passwords(INPUT), Ok(2));
     }
 
     #[test]
     fn test_count_complex_passwords() {
-        assert_eq!(count_complex_passwords(INPUT), 1);
+        assert_eq!(count_complex_passwords(INPUT), Ok(1));
     }
 }
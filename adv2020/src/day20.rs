@@ -0,0 +1,573 @@
+use advgrid::Grid;
+use advparse::{parse_number, parse_records, ParseError};
+use std::collections::HashSet;
+use std::fmt;
+
+// The sea monster, the spaces can be anything
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+pub fn multiply_corners(input: &str) -> Result<u64, ParseError> {
+    let image = Image::assemble(parse_input(input)?)?;
+
+    Ok(image.corners().iter().product())
+}
+
+pub fn count_rough_water(input: &str) -> Result<usize, ParseError> {
+    let image = Image::assemble(parse_input(input)?)?;
+
+    image.roughness()
+}
+
+// The assembled tiles as in the puzzle, see --log
+pub fn log(input: &str) -> Result<String, ParseError> {
+    let image = Image::assemble(parse_input(input)?)?;
+
+    Ok(image.to_string())
+}
+
+struct Tile {
+    id: u64,
+    grid: Grid<bool>,
+}
+
+// The tiles are laid out in a square, row by row, every one in its orientation
+struct Image {
+    side: usize,
+    tiles: Vec<(u64, Grid<bool>)>,
+}
+
+impl Image {
+    fn assemble(tiles: Vec<Tile>) -> Result<Image, ParseError> {
+        let side = (0..=tiles.len())
+            .find(|side| side * side >= tiles.len())
+            .unwrap_or(0);
+        if tiles.is_empty() || side * side != tiles.len() {
+            return Err(ParseError::input(format!(
+                "{} tiles don't make a square",
+                tiles.len()
+            )));
+        }
+
+        let orientations: Vec<Vec<Grid<bool>>> = tiles
+            .iter()
+            .map(|tile| tile.grid.orientations().map(|(_, grid)| grid).collect())
+            .collect();
+        let mut placed = Vec::new();
+        let mut used = vec![false; tiles.len()];
+
+        if !place(&orientations, side, &mut placed, &mut used) {
+            return Err(ParseError::input("the tiles don't fit together"));
+        }
+
+        Ok(Image {
+            side,
+            tiles: placed
+                .into_iter()
+                .map(|(index, orientation)| {
+                    (tiles[index].id, orientations[index][orientation].clone())
+                })
+                .collect(),
+        })
+    }
+
+    fn corners(&self) -> [u64; 4] {
+        let last = self.side - 1;
+
+        [(0, 0), (last, 0), (0, last), (last, last)].map(|(x, y)| self.tiles[y * self.side + x].0)
+    }
+
+    // The tiles without their borders
+    fn picture(&self) -> Grid<bool> {
+        let rows = self
+            .tiles
+            .chunks(self.side)
+            .flat_map(|row| {
+                let height = row[0].1.height();
+
+                (1..height - 1).map(move |y| {
+                    row.iter()
+                        .flat_map(|(_, grid)| (1..grid.width() - 1).map(move |x| grid[(x, y)]))
+                        .collect()
+                })
+            })
+            .collect();
+
+        Grid::from_rows(rows)
+    }
+
+    // The rough water isn't a part of any sea monster; the monsters are
+    // in a single orientation of the picture
+    fn roughness(&self) -> Result<usize, ParseError> {
+        let monster: Vec<(usize, usize)> = MONSTER
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.match_indices('#').map(move |(x, _)| (x, y)))
+            .collect();
+        let (width, height) = (MONSTER[0].len(), MONSTER.len());
+
+        for (_, picture) in self.picture().orientations() {
+            let mut monsters = HashSet::new();
+
+            for y in 0..(picture.height() + 1).saturating_sub(height) {
+                for x in 0..(picture.width() + 1).saturating_sub(width) {
+                    if monster.iter().all(|&(dx, dy)| picture[(x + dx, y + dy)]) {
+                        monsters.extend(monster.iter().map(|&(dx, dy)| (x + dx, y + dy)));
+                    }
+                }
+            }
+
+            if !monsters.is_empty() {
+                return Ok(picture.count(|&water| water) - monsters.len());
+            }
+        }
+
+        Err(ParseError::input(
+            "there are no sea monsters in the picture",
+        ))
+    }
+}
+
+// The tiles as in the puzzle, with their numbers and a space between them
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.tiles.chunks(self.side) {
+            let width = row[0].1.width();
+            let ids: Vec<String> = row
+                .iter()
+                .map(|(id, _)| format!("{:<width$}", id, width = width))
+                .collect();
+            writeln!(f, "{}", ids.join(" ").trim_end())?;
+
+            let grids: Vec<Vec<String>> = row
+                .iter()
+                .map(|(_, grid)| grid.to_string().lines().map(String::from).collect())
+                .collect();
+            for y in 0..row[0].1.height() {
+                let line: Vec<&str> = grids.iter().map(|lines| lines[y].as_str()).collect();
+                writeln!(f, "{}", line.join(" "))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Places the next tile, which fits to its left and upper neighbours; backtracks,
+// if none does. The tiles are the indices and the orientations of the grids
+fn place(
+    orientations: &[Vec<Grid<bool>>],
+    side: usize,
+    placed: &mut Vec<(usize, usize)>,
+    used: &mut [bool],
+) -> bool {
+    let position = placed.len();
+    if position == side * side {
+        return true;
+    }
+
+    let grid = |(index, orientation): (usize, usize)| &orientations[index][orientation];
+    let left = (!position.is_multiple_of(side)).then(|| grid(placed[position - 1]));
+    let above = (position >= side).then(|| grid(placed[position - side]));
+
+    for index in 0..orientations.len() {
+        if used[index] {
+            continue;
+        }
+
+        for (orientation, candidate) in orientations[index].iter().enumerate() {
+            if left.is_some_and(|left| !fits_right(left, candidate))
+                || above.is_some_and(|above| !fits_below(above, candidate))
+            {
+                continue;
+            }
+
+            used[index] = true;
+            placed.push((index, orientation));
+            if place(orientations, side, placed, used) {
+                return true;
+            }
+            placed.pop();
+            used[index] = false;
+        }
+    }
+
+    false
+}
+
+fn fits_right(left: &Grid<bool>, right: &Grid<bool>) -> bool {
+    let last = left.width() - 1;
+
+    (0..left.height()).all(|y| left[(last, y)] == right[(0, y)])
+}
+
+fn fits_below(above: &Grid<bool>, below: &Grid<bool>) -> bool {
+    above.rows().last() == below.rows().next()
+}
+
+// Tile 2311:
+// ..##.#..#.
+// ...
+fn parse_input(input: &str) -> Result<Vec<Tile>, ParseError> {
+    let tiles = parse_records(input, |record| {
+        let (_, header) = record.lines().next().unwrap_or_default();
+        let id = header
+            .trim()
+            .strip_prefix("Tile ")
+            .and_then(|header| header.strip_suffix(':'))
+            .ok_or_else(|| record.error(header.trim(), "expected \"Tile <number>:\""))?;
+        let id = parse_number(header, id).map_err(|err| err.at_line(record.line))?;
+
+        // The grid starts in the second line of the record
+        let grid = Grid::parse(&record.text[header.len()..]).map_err(|err| {
+            let line = record.line + err.line - 1;
+            err.at_line(line)
+        })?;
+
+        if grid.width() != grid.height() || grid.width() < 3 {
+            return Err(record.error(header.trim(), "expected a square tile with borders"));
+        }
+
+        Ok(Tile { id, grid })
+    })?;
+
+    if let Some(tile) = tiles
+        .iter()
+        .find(|tile| tile.grid.width() != tiles[0].grid.width())
+    {
+        return Err(ParseError::input(format!(
+            "the tile {} has a different size",
+            tile.id
+        )));
+    }
+
+    Ok(tiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advgrid::Orientation;
+
+    // The example of the puzzle
+    const INPUT: &str = "\
+Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...";
+
+    // The bits of a linear congruential generator, so the puzzles are the same every time
+    fn random_bits(seed: u64) -> impl FnMut(u32) -> u64 {
+        let mut state = seed;
+
+        move |bits| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) & ((1 << bits) - 1)
+        }
+    }
+
+    // Cuts the picture into square tiles with random borders, which the neighbours share,
+    // then turns and shuffles them. Returns the input and the numbers of the corners
+    fn jigsaw(picture: &Grid<bool>, size: usize, seed: u64) -> (String, [u64; 4]) {
+        let mut random = random_bits(seed);
+        let inner = size - 2;
+        let side = picture.width() / inner;
+
+        // Every (size - 1)th row and column is a border of two tiles
+        let width = side * (size - 1) + 1;
+        let mut sheet = Grid::new(width, width, false);
+        for (x, y) in sheet.positions().collect::<Vec<_>>() {
+            let (tile_x, tile_y) = (x % (size - 1), y % (size - 1));
+
+            sheet[(x, y)] = match tile_x == 0 || tile_y == 0 {
+                true => random(1) == 1,
+                false => {
+                    picture[(
+                        x / (size - 1) * inner + tile_x - 1,
+                        y / (size - 1) * inner + tile_y - 1,
+                    )]
+                }
+            };
+        }
+
+        let mut tiles: Vec<(u64, Grid<bool>)> = (0..side * side)
+            .map(|index| {
+                let (left, top) = (index % side * (size - 1), index / side * (size - 1));
+                let rows = (top..top + size)
+                    .map(|y| (left..left + size).map(|x| sheet[(x, y)]).collect())
+                    .collect();
+                let orientation = Orientation::all().nth(random(3) as usize).unwrap();
+
+                (
+                    1000 + index as u64,
+                    Grid::from_rows(rows).oriented(orientation),
+                )
+            })
+            .collect();
+        for index in (1..tiles.len()).rev() {
+            tiles.swap(index, random(16) as usize % (index + 1));
+        }
+
+        let input = tiles
+            .iter()
+            .map(|(id, grid)| format!("Tile {}:\n{}\n", id, grid))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let last = side as u64 - 1;
+        let corners =
+            [0, last, last * side as u64, last * side as u64 + last].map(|index| 1000 + index);
+
+        (input, corners)
+    }
+
+    // Two monsters in the calm water with a few waves
+    fn sea(size: usize) -> Grid<bool> {
+        let mut sea = Grid::new(size, size, false);
+        for (y, row) in MONSTER.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                sea[(x + 1, y + 2)] = c == '#';
+                sea[(x + 3, size - 5 + y)] = c == '#';
+            }
+        }
+        sea[(0, 0)] = true;
+        sea[(size - 1, 0)] = true;
+        sea[(3, size / 2)] = true;
+
+        sea
+    }
+
+    #[test]
+    fn test_empty_answers() {
+        assert!(multiply_corners("").is_err());
+        assert!(count_rough_water("").is_err());
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let tiles = parse_input("Tile 7:\n#..\n.#.\n..#\n\nTile 9:\n...\n...\n###").unwrap();
+
+        assert_eq!(tiles.len(), 2);
+        assert_eq!(tiles[0].id, 7);
+        assert_eq!(tiles[1].id, 9);
+        assert_eq!(tiles[1].grid.count(|&cell| cell), 3);
+    }
+
+    #[test]
+    fn test_parse_wrong_input() {
+        assert_eq!(
+            parse_input("Tile 7\n#..\n.#.\n..#").err(),
+            Some(ParseError::new(
+                1,
+                1,
+                "Tile 7",
+                "expected \"Tile <number>:\""
+            ))
+        );
+        assert_eq!(
+            parse_input("Tile x:\n#..\n.#.\n..#").err(),
+            Some(ParseError::new(1, 6, "x", "expected a number"))
+        );
+        assert_eq!(
+            parse_input("Tile 7:\n#..\n.#.\n..#\n\nTile 8:\n#..\n.#x\n..#").err(),
+            Some(ParseError::new(8, 3, "x", "expected # or ."))
+        );
+        assert_eq!(
+            parse_input("Tile 7:\n#..\n.#.").err(),
+            Some(ParseError::new(
+                1,
+                1,
+                "Tile 7:",
+                "expected a square tile with borders"
+            ))
+        );
+        assert_eq!(
+            parse_input("Tile 7:\n#..\n.#.\n..#\n\nTile 8:\n#...\n.#..\n..#.\n....").err(),
+            Some(ParseError::input("the tile 8 has a different size"))
+        );
+    }
+
+    #[test]
+    fn test_assemble() {
+        let picture = sea(24);
+        let (input, corners) = jigsaw(&picture, 10, 7);
+        let image = Image::assemble(parse_input(&input).unwrap()).unwrap();
+
+        let mut found = image.corners();
+        found.sort_unstable();
+        assert_eq!(found, corners);
+        assert!(image
+            .picture()
+            .orientations()
+            .any(|(_, grid)| grid == picture));
+
+        let tiles = parse_input("Tile 1:\n###\n#.#\n###\n\nTile 2:\n...\n...\n...").unwrap();
+        assert_eq!(
+            Image::assemble(tiles).err(),
+            Some(ParseError::input("2 tiles don't make a square"))
+        );
+
+        let mut tiles = parse_input(&input).unwrap();
+        tiles[0].grid = tiles[0].grid.map(|_, &cell| !cell);
+        assert_eq!(
+            Image::assemble(tiles).err(),
+            Some(ParseError::input("the tiles don't fit together"))
+        );
+    }
+
+    #[test]
+    fn test_print() {
+        let picture = Grid::parse("#.\n.#").unwrap();
+        let (input, _) = jigsaw(&picture, 3, 1);
+        let image = Image::assemble(parse_input(&input).unwrap()).unwrap();
+        let text = image.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 10);
+        assert!(lines[0].starts_with("100"));
+        assert_eq!(lines[1].len(), 7);
+        assert_eq!(lines[4], "");
+    }
+
+    #[test]
+    fn test_log() {
+        let text = log(INPUT).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        // Three rows of three tiles, every one under the numbers and before an empty line
+        assert_eq!(lines.len(), 3 * 12);
+        assert_eq!(lines[1].len(), 3 * 10 + 2);
+        for id in [
+            "1951", "2311", "3079", "2729", "1427", "2473", "2971", "1489", "1171",
+        ] {
+            assert!(text.contains(id));
+        }
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(multiply_corners(INPUT), Ok(20899048083289));
+        assert_eq!(count_rough_water(INPUT), Ok(273));
+
+        let (input, corners) = jigsaw(&sea(24), 10, 2020);
+
+        assert_eq!(multiply_corners(&input), Ok(corners.iter().product()));
+        assert_eq!(count_rough_water(&input), Ok(3));
+
+        let (input, _) = jigsaw(&Grid::new(16, 16, false), 10, 20);
+        assert_eq!(
+            count_rough_water(&input),
+            Err(ParseError::input(
+                "there are no sea monsters in the picture"
+            ))
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::elimination::eliminate;
+use advparse::{parse_lines, ParseError};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub fn count_safe_ingredients(input: &str) -> Result<usize, ParseError> {
+    let foods = parse_input(input)?;
+    let unsafe_ingredients: BTreeSet<&str> = candidates(&foods).into_values().flatten().collect();
+
+    Ok(foods
+        .iter()
+        .flat_map(|food| &food.ingredients)
+        .filter(|ingredient| !unsafe_ingredients.contains(*ingredient))
+        .count())
+}
+
+pub fn find_dangerous_ingredients(input: &str) -> Result<String, ParseError> {
+    let foods = parse_input(input)?;
+    let dangerous = eliminate(candidates(&foods))
+        .ok_or_else(|| ParseError::input("the allergens can't be matched to the ingredients"))?;
+
+    // The allergens are sorted alphabetically, so are their ingredients
+    Ok(dangerous.into_values().collect::<Vec<_>>().join(","))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Food<'a> {
+    ingredients: Vec<&'a str>,
+    allergens: Vec<&'a str>,
+}
+
+// An allergen is in one of the ingredients, which every food with the allergen has
+fn candidates<'a>(foods: &[Food<'a>]) -> BTreeMap<&'a str, BTreeSet<&'a str>> {
+    let mut candidates: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+
+    for food in foods {
+        let ingredients: BTreeSet<&str> = food.ingredients.iter().copied().collect();
+
+        for allergen in &food.allergens {
+            candidates
+                .entry(allergen)
+                .and_modify(|candidates| {
+                    candidates.retain(|ingredient| ingredients.contains(ingredient))
+                })
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+
+    candidates
+}
+
+// mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+fn parse_food(line: &str) -> Result<Food<'_>, ParseError> {
+    let (ingredients, allergens) = match line.split_once('(') {
+        Some((ingredients, allergens)) => {
+            let allergens = allergens
+                .trim_end()
+                .strip_suffix(')')
+                .ok_or_else(|| ParseError::end_of_line(line, "expected \")\""))?;
+            let allergens = allergens
+                .trim_start()
+                .strip_prefix("contains ")
+                .ok_or_else(|| ParseError::in_line(line, allergens, "expected \"contains\""))?;
+            let allergens = allergens
+                .split(',')
+                .map(|allergen| match allergen.trim() {
+                    "" => Err(ParseError::in_line(line, allergen, "expected an allergen")),
+                    allergen => Ok(allergen),
+                })
+                .collect::<Result<_, _>>()?;
+
+            (ingredients, allergens)
+        }
+        None => (line, Vec::new()),
+    };
+
+    let ingredients: Vec<&str> = ingredients.split_whitespace().collect();
+    if ingredients.is_empty() {
+        return Err(ParseError::in_line(line, line, "expected an ingredient"));
+    }
+
+    Ok(Food {
+        ingredients,
+        allergens,
+    })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Food<'_>>, ParseError> {
+    parse_lines(input, parse_food)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+                         trh fvjkl sbzzf mxmxvkd (contains dairy)
+                         sqjhc fvjkl (contains soy)
+                         sqjhc mxmxvkd sbzzf (contains fish)";
+
+    #[test]
+    fn test_empty_answers() {
+        assert_eq!(count_safe_ingredients(""), Ok(0));
+        assert_eq!(find_dangerous_ingredients(""), Ok(String::new()));
+    }
+
+    #[test]
+    fn test_parse_food() {
+        assert_eq!(
+            parse_food("trh fvjkl sbzzf mxmxvkd (contains dairy)"),
+            Ok(Food {
+                ingredients: vec!["trh", "fvjkl", "sbzzf", "mxmxvkd"],
+                allergens: vec!["dairy"],
+            })
+        );
+        assert_eq!(
+            parse_food("sqjhc fvjkl"),
+            Ok(Food {
+                ingredients: vec!["sqjhc", "fvjkl"],
+                allergens: vec![],
+            })
+        );
+
+        assert_eq!(
+            parse_food("sqjhc (contains soy"),
+            Err(ParseError::new(0, 20, "", "expected \")\""))
+        );
+        assert_eq!(
+            parse_food("sqjhc (has soy)"),
+            Err(ParseError::new(0, 8, "has soy", "expected \"contains\""))
+        );
+        assert_eq!(
+            parse_food("sqjhc (contains soy,)"),
+            Err(ParseError::new(0, 21, "", "expected an allergen"))
+        );
+        assert_eq!(
+            parse_food("(contains soy)"),
+            Err(ParseError::new(
+                0,
+                1,
+                "(contains soy)",
+                "expected an ingredient"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_candidates() {
+        let foods = parse_input(INPUT).unwrap();
+        let candidates = candidates(&foods);
+
+        assert_eq!(candidates["dairy"], BTreeSet::from(["mxmxvkd"]));
+        assert_eq!(candidates["fish"], BTreeSet::from(["mxmxvkd", "sqjhc"]));
+        assert_eq!(candidates["soy"], BTreeSet::from(["fvjkl", "sqjhc"]));
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(count_safe_ingredients(INPUT), Ok(5));
+        assert_eq!(
+            find_dangerous_ingredients(INPUT),
+            Ok(String::from("mxmxvkd,sqjhc,fvjkl"))
+        );
+
+        assert_eq!(
+            find_dangerous_ingredients("a b (contains dairy, fish)"),
+            Err(ParseError::input(
+                "the allergens can't be matched to the ingredients"
+            ))
+        );
+    }
+}
@@ -0,0 +1,353 @@
+use advparse::{parse_number, parse_records, ParseError};
+use std::collections::{HashSet, VecDeque};
+
+type Decks = [VecDeque<usize>; 2];
+
+pub fn find_answer1(input: &str) -> Result<usize, ParseError> {
+    let decks = parse_input(input)?;
+
+    Combat::new(Rules::Plain).play(decks)
+}
+
+pub fn find_answer2(input: &str) -> Result<usize, ParseError> {
+    let decks = parse_input(input)?;
+
+    Combat::new(Rules::Recursive).play(decks)
+}
+
+// The rounds of both games as in the puzzle, see --log
+pub fn log(input: &str) -> Result<String, ParseError> {
+    let decks = parse_input(input)?;
+    let mut text = String::new();
+
+    for (title, rules) in [
+        ("Combat", Rules::Plain),
+        ("Recursive Combat", Rules::Recursive),
+    ] {
+        let mut combat = Combat::new(rules).with_log();
+        let score = combat.play(decks.clone());
+
+        text.push_str(&format!("===== {} =====\n", title));
+        for line in combat.log.iter().flatten() {
+            text.push_str(line);
+            text.push('\n');
+        }
+        // The log tells, why the game has no score
+        if let Ok(score) = score {
+            text.push_str(&format!("The score of the winner is {}\n\n", score));
+        }
+    }
+
+    Ok(text)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Rules {
+    Plain,
+    // Both players play a sub-game with their next cards, if they have enough of them
+    Recursive,
+}
+
+struct Combat {
+    rules: Rules,
+    games: usize,
+    // The rounds as in the puzzle, if they are written down
+    log: Option<Vec<String>>,
+}
+
+impl Combat {
+    fn new(rules: Rules) -> Combat {
+        Combat {
+            rules,
+            games: 0,
+            log: None,
+        }
+    }
+
+    fn with_log(mut self) -> Combat {
+        self.log = Some(Vec::new());
+        self
+    }
+
+    // The score of the winner
+    fn play(&mut self, decks: Decks) -> Result<usize, ParseError> {
+        let (winner, decks) = self.game(decks, false)?;
+        let deck = &decks[winner];
+
+        self.note(|| format!("== Post-game results ==\n{}", describe(&decks)));
+
+        Ok(deck
+            .iter()
+            .enumerate()
+            .map(|(index, card)| card * (deck.len() - index))
+            .sum())
+    }
+
+    // The winner and the decks at the end of the game. The plain game with
+    // repeated decks would go on forever, the recursive one ends with them
+    fn game(&mut self, mut decks: Decks, sub_game: bool) -> Result<(usize, Decks), ParseError> {
+        self.games += 1;
+        let game = self.games;
+        self.note(|| format!("=== Game {} ===", game));
+
+        // The highest card can't be lost in a sub-game, it's higher than the number of cards;
+        // so its player wins the sub-game sooner or later, or the decks repeat and the first does
+        let highest = |deck: &VecDeque<usize>| deck.iter().max().copied();
+        if sub_game && highest(&decks[0]) > highest(&decks[1]) {
+            self.note(|| format!("Player 1 holds the highest card and wins game {}!", game));
+            return Ok((0, decks));
+        }
+
+        let mut history = HashSet::new();
+        let mut round = 0;
+
+        while decks.iter().all(|deck| !deck.is_empty()) {
+            if !history.insert(decks.clone()) {
+                if self.rules == Rules::Plain {
+                    self.note(|| format!("The decks repeat, game {} never ends!", game));
+                    return Err(ParseError::input("the decks repeat, the game never ends"));
+                }
+
+                self.note(|| format!("The decks repeat, player 1 wins game {}!", game));
+                return Ok((0, decks));
+            }
+
+            round += 1;
+            self.note(|| {
+                format!(
+                    "\n-- Round {} (Game {}) --\n{}",
+                    round,
+                    game,
+                    describe(&decks)
+                )
+            });
+
+            let cards = [0, 1].map(|player| decks[player].pop_front().unwrap_or_default());
+            self.note(|| format!("Player 1 plays: {}\nPlayer 2 plays: {}", cards[0], cards[1]));
+
+            let recurse = self.rules == Rules::Recursive
+                && (0..2).all(|player| cards[player] <= decks[player].len());
+            let winner = if recurse {
+                self.note(|| String::from("Playing a sub-game to determine the winner...\n"));
+                let copies = [0, 1]
+                    .map(|player| decks[player].iter().take(cards[player]).copied().collect());
+                let (winner, _) = self.game(copies, true)?;
+                self.note(|| format!("\n...anyway, back to game {}.", game));
+
+                winner
+            } else {
+                usize::from(cards[1] > cards[0])
+            };
+
+            self.note(|| {
+                format!(
+                    "Player {} wins round {} of game {}!",
+                    winner + 1,
+                    round,
+                    game
+                )
+            });
+            decks[winner].push_back(cards[winner]);
+            decks[winner].push_back(cards[1 - winner]);
+        }
+
+        let winner = usize::from(decks[0].is_empty());
+        self.note(|| format!("The winner of game {} is player {}!", game, winner + 1));
+
+        Ok((winner, decks))
+    }
+
+    // The text is built only for the log, the real games are long
+    fn note<F: FnOnce() -> String>(&mut self, text: F) {
+        if let Some(log) = self.log.as_mut() {
+            log.push(text());
+        }
+    }
+}
+
+// Player 1's deck: 9, 2, 6, 3, 1
+fn describe(decks: &Decks) -> String {
+    decks
+        .iter()
+        .enumerate()
+        .map(|(player, deck)| {
+            let cards: Vec<String> = deck.iter().map(usize::to_string).collect();
+            format!("Player {}'s deck: {}", player + 1, cards.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Player 1:
+// 9
+// 2
+fn parse_input(input: &str) -> Result<Decks, ParseError> {
+    let mut decks = [VecDeque::new(), VecDeque::new()];
+    let mut dealt = HashSet::new();
+    let mut players = 0;
+
+    parse_records(input, |record| {
+        let mut lines = record.lines();
+        let (_, header) = lines.next().unwrap_or_default();
+        let expected = format!("Player {}:", players + 1);
+
+        if players == decks.len() {
+            return Err(record.error(header, "expected two players"));
+        }
+        if header.trim() != expected {
+            return Err(record.error(header, format!("expected \"{}\"", expected)));
+        }
+
+        for (number, line) in lines {
+            let card = parse_number(line, line.trim()).map_err(|err| err.at_line(number))?;
+
+            if !dealt.insert(card) {
+                return Err(
+                    ParseError::in_line(line, line.trim(), "the card is dealt twice")
+                        .at_line(number),
+                );
+            }
+            decks[players].push_back(card);
+        }
+
+        players += 1;
+        Ok(())
+    })?;
+
+    if players != decks.len() {
+        return Err(ParseError::input("expected two players"));
+    }
+
+    Ok(decks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Player 1:
+                         9
+                         2
+                         6
+                         3
+                         1
+
+                         Player 2:
+                         5
+                         8
+                         4
+                         7
+                         10";
+
+    #[test]
+    fn test_empty_answers() {
+        assert!(find_answer1("").is_err());
+        assert!(find_answer2("").is_err());
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let decks = parse_input(INPUT).unwrap();
+
+        assert_eq!(decks[0], VecDeque::from([9, 2, 6, 3, 1]));
+        assert_eq!(decks[1], VecDeque::from([5, 8, 4, 7, 10]));
+
+        assert_eq!(
+            parse_input("Player 2:\n1\n\nPlayer 1:\n2").err(),
+            Some(ParseError::new(1, 1, "Player 2:", "expected \"Player 1:\""))
+        );
+        assert_eq!(
+            parse_input("Player 1:\n1\nx").err(),
+            Some(ParseError::new(3, 1, "x", "expected a number"))
+        );
+        assert_eq!(
+            parse_input("Player 1:\n1\n\nPlayer 2:\n1").err(),
+            Some(ParseError::new(5, 1, "1", "the card is dealt twice"))
+        );
+        assert_eq!(
+            parse_input("Player 1:\n1").err(),
+            Some(ParseError::input("expected two players"))
+        );
+        assert_eq!(
+            parse_input("Player 1:\n1\n\nPlayer 2:\n2\n\nPlayer 3:\n3").err(),
+            Some(ParseError::new(7, 1, "Player 3:", "expected two players"))
+        );
+    }
+
+    #[test]
+    fn test_plain_log() {
+        let mut combat = Combat::new(Rules::Plain).with_log();
+        assert_eq!(combat.play(parse_input(INPUT).unwrap()), Ok(306));
+        let log = combat.log.unwrap();
+
+        assert_eq!(
+            log[1],
+            "\n-- Round 1 (Game 1) --\n\
+             Player 1's deck: 9, 2, 6, 3, 1\n\
+             Player 2's deck: 5, 8, 4, 7, 10"
+        );
+        assert_eq!(log[2], "Player 1 plays: 9\nPlayer 2 plays: 5");
+        assert_eq!(log[3], "Player 1 wins round 1 of game 1!");
+        assert_eq!(
+            log[log.len() - 1],
+            "== Post-game results ==\n\
+             Player 1's deck: \n\
+             Player 2's deck: 3, 2, 10, 6, 8, 5, 9, 4, 7, 1"
+        );
+        // The game ends after 29 rounds
+        assert!(log.iter().any(|line| line.starts_with("\n-- Round 29 ")));
+        assert!(!log.iter().any(|line| line.starts_with("\n-- Round 30 ")));
+    }
+
+    #[test]
+    fn test_recursive_log() {
+        let mut combat = Combat::new(Rules::Recursive).with_log();
+        assert_eq!(combat.play(parse_input(INPUT).unwrap()), Ok(291));
+        let log = combat.log.unwrap();
+
+        assert!(log.contains(&String::from("=== Game 2 ===")));
+        assert!(log.contains(&String::from("The winner of game 1 is player 2!")));
+        assert_eq!(
+            log[log.len() - 1],
+            "== Post-game results ==\n\
+             Player 1's deck: \n\
+             Player 2's deck: 7, 5, 6, 2, 4, 1, 10, 8, 9, 3"
+        );
+    }
+
+    #[test]
+    fn test_repeated_decks() {
+        let input = "Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14";
+        let mut combat = Combat::new(Rules::Recursive).with_log();
+
+        assert_eq!(combat.play(parse_input(input).unwrap()), Ok(105));
+        assert!(combat
+            .log
+            .unwrap()
+            .contains(&String::from("The decks repeat, player 1 wins game 1!")));
+
+        assert_eq!(
+            find_answer1(input),
+            Err(ParseError::input("the decks repeat, the game never ends"))
+        );
+    }
+
+    #[test]
+    fn test_log() {
+        let text = log(INPUT).unwrap();
+
+        assert!(text.starts_with("===== Combat =====\n=== Game 1 ===\n"));
+        assert!(text.contains("The score of the winner is 306\n"));
+        assert!(text.contains("===== Recursive Combat =====\n"));
+        assert!(text.ends_with("The score of the winner is 291\n\n"));
+
+        let text = log("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14").unwrap();
+        assert!(text.contains("The decks repeat, game 1 never ends!\n===== Recursive Combat"));
+    }
+
+    #[test]
+    fn test_answers() {
+        assert_eq!(find_answer1(INPUT), Ok(306));
+        assert_eq!(find_answer2(INPUT), Ok(291));
+    }
+}
@@ -0,0 +1,239 @@
+use advcore::{Param, Params};
+use advparse::{parse_lines, ParseError};
+
+// The examples of the puzzle play 10 moves too
+pub const MOVES: Param = Param::new("moves", "100", "the moves with the labeled cups");
+pub const LONG_MOVES: Param = Param::new("long-moves", "10000000", "the moves with all cups");
+pub const CUPS: Param = Param::new("cups", "1000000", "the number of all cups");
+
+pub fn find_labels(input: &str, params: &Params) -> Result<String, ParseError> {
+    let labels = parse_input(input)?;
+    let mut cups = Cups::new(&labels, labels.len());
+    cups.play(params.get(&MOVES)?);
+
+    Ok(cups.after(1).map(|label| label.to_string()).collect())
+}
+
+pub fn find_star_cups(input: &str, params: &Params) -> Result<u64, ParseError> {
+    let labels = parse_input(input)?;
+    let total = params.get(&CUPS)?;
+    if total < labels.len() {
+        return Err(ParseError::input(format!(
+            "the parameter cups can't be less than the {} labeled cups",
+            labels.len()
+        )));
+    }
+
+    let mut cups = Cups::new(&labels, total);
+    cups.play(params.get(&LONG_MOVES)?);
+
+    Ok(cups.after(1).take(2).map(u64::from).product())
+}
+
+// The circle is a linked list in an array: next[label] is the label of the cup clockwise,
+// so a move changes three links instead of shifting the cups
+struct Cups {
+    next: Vec<u32>,
+    current: u32,
+}
+
+impl Cups {
+    // The labeled cups are followed by the rest up to the total, in order
+    fn new(labels: &[u32], total: usize) -> Cups {
+        let order: Vec<u32> = labels
+            .iter()
+            .copied()
+            .chain(labels.len() as u32 + 1..=total as u32)
+            .collect();
+        let mut next = vec![0; order.len() + 1];
+
+        for (index, &label) in order.iter().enumerate() {
+            next[label as usize] = order[(index + 1) % order.len()];
+        }
+
+        Cups {
+            next,
+            current: order[0],
+        }
+    }
+
+    fn play(&mut self, moves: usize) {
+        for _ in 0..moves {
+            self.step();
+        }
+    }
+
+    // The three cups after the current one move after the destination,
+    // the cup with the next lower label, which isn't picked up
+    fn step(&mut self) {
+        let highest = self.next.len() as u32 - 1;
+        let first = self.next[self.current as usize];
+        let second = self.next[first as usize];
+        let third = self.next[second as usize];
+
+        let mut destination = self.current;
+        loop {
+            destination = if destination == 1 {
+                highest
+            } else {
+                destination - 1
+            };
+            if destination != first && destination != second && destination != third {
+                break;
+            }
+        }
+
+        self.next[self.current as usize] = self.next[third as usize];
+        self.next[third as usize] = self.next[destination as usize];
+        self.next[destination as usize] = first;
+        self.current = self.next[self.current as usize];
+    }
+
+    // The cups clockwise after the label, without it
+    fn after(&self, label: u32) -> impl Iterator<Item = u32> + '_ {
+        let mut cup = label;
+
+        std::iter::from_fn(move || {
+            cup = self.next[cup as usize];
+            (cup != label).then_some(cup)
+        })
+    }
+}
+
+// 389125467: the labels are the cups from 1 to the last one in any order
+fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    let lines = parse_lines(input, |line| {
+        let line = line.trim();
+        let labels = line
+            .char_indices()
+            .map(|(index, c)| {
+                c.to_digit(10).filter(|&label| label > 0).ok_or_else(|| {
+                    let token = &line[index..index + c.len_utf8()];
+                    ParseError::in_line(line, token, "expected a label from 1 to 9")
+                })
+            })
+            .collect::<Result<Vec<u32>, ParseError>>()?;
+
+        for (index, label) in labels.iter().enumerate() {
+            if labels[..index].contains(label) {
+                let token = &line[index..index + 1];
+                return Err(ParseError::in_line(line, token, "the cup is there twice"));
+            }
+            if *label as usize > labels.len() {
+                let token = &line[index..index + 1];
+                let reason = format!("expected a label from 1 to {}", labels.len());
+                return Err(ParseError::in_line(line, token, reason));
+            }
+        }
+
+        Ok(labels)
+    })?;
+
+    match lines.as_slice() {
+        [labels] if labels.len() >= 5 => Ok(labels.clone()),
+        [_] => Err(ParseError::input("expected at least 5 cups")),
+        _ => Err(ParseError::input("expected a single line of cups")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "389125467";
+
+    fn order(cups: &Cups) -> String {
+        let rest = cups.after(cups.current).map(|label| label.to_string());
+
+        std::iter::once(cups.current.to_string())
+            .chain(rest)
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_answers() {
+        assert!(find_labels("", &Params::new()).is_err());
+        assert!(find_star_cups("", &Params::new()).is_err());
+    }
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_input(INPUT), Ok(vec![3, 8, 9, 1, 2, 5, 4, 6, 7]));
+
+        assert_eq!(
+            parse_input("38912x467"),
+            Err(ParseError::new(1, 6, "x", "expected a label from 1 to 9"))
+        );
+        assert_eq!(
+            parse_input("389123467"),
+            Err(ParseError::new(1, 6, "3", "the cup is there twice"))
+        );
+        assert_eq!(
+            parse_input("38125469"),
+            Err(ParseError::new(1, 8, "9", "expected a label from 1 to 8"))
+        );
+        assert_eq!(
+            parse_input("3124"),
+            Err(ParseError::input("expected at least 5 cups"))
+        );
+        assert_eq!(
+            parse_input("31245\n31245"),
+            Err(ParseError::input("expected a single line of cups"))
+        );
+    }
+
+    #[test]
+    fn test_step() {
+        let mut cups = Cups::new(&parse_input(INPUT).unwrap(), 9);
+        assert_eq!(order(&cups), "389125467");
+
+        cups.step();
+        assert_eq!(order(&cups), "289154673");
+        cups.step();
+        assert_eq!(order(&cups), "546789132");
+    }
+
+    #[test]
+    fn test_more_cups() {
+        let cups = Cups::new(&parse_input(INPUT).unwrap(), 12);
+
+        assert_eq!(order(&cups), "389125467101112");
+        assert_eq!(cups.after(12).next(), Some(3));
+    }
+
+    #[test]
+    fn test_find_labels() {
+        let mut params = Params::new();
+        params.set("moves", "10");
+
+        assert_eq!(find_labels(INPUT, &params), Ok(String::from("92658374")));
+        assert_eq!(
+            find_labels(INPUT, &Params::new()),
+            Ok(String::from("67384529"))
+        );
+    }
+
+    #[test]
+    fn test_find_star_cups() {
+        let mut params = Params::new();
+        params.set("cups", "5");
+
+        assert_eq!(
+            find_star_cups(INPUT, &params),
+            Err(ParseError::input(
+                "the parameter cups can't be less than the 9 labeled cups"
+            ))
+        );
+
+        // Ten moves of the nine cups take the cups 9 and 2 after the cup 1
+        params.set("cups", "9");
+        params.set("long-moves", "10");
+        assert_eq!(find_star_cups(INPUT, &params), Ok(9 * 2));
+    }
+
+    #[test]
+    #[ignore]
+    fn test_find_star_cups_slow() {
+        assert_eq!(find_star_cups(INPUT, &Params::new()), Ok(149245887792));
+    }
+}
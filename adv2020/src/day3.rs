@@ -1,11 +1,14 @@
-pub fn count_single_slope(input: &str) -> usize {
+use advgrid::{Edges, Grid};
+use advparse::ParseError;
+
+pub fn count_single_slope(input: &str) -> Result<usize, ParseError> {
     const SINGLE: Slope = Slope { right: 3, down: 1 };
 
-    let input = parse_input(input);
-    count_trees(&input, &SINGLE)
+    let input = parse_input(input)?;
+    Ok(count_trees(&input, &SINGLE))
 }
 
-pub fn count_multiple_slopes(input: &str) -> usize {
+pub fn count_multiple_slopes(input: &str) -> Result<usize, ParseError> {
     let multiple: Vec<Slope> = vec![
         Slope { right: 1, down: 1 },
         Slope { right: 3, down: 1 },
@@ -14,29 +17,21 @@ pub fn count_multiple_slopes(input: &str) -> usize {
         Slope { right: 1, down: 2 },
     ];
 
-    let input = parse_input(input);
+    let input = parse_input(input)?;
 
-    multiple
+    Ok(multiple
         .iter()
         .map(|slope| count_trees(&input, slope))
-        .fold(1, |count, acc| count * acc)
+        .fold(1, |count, acc| count * acc))
 }
 
-fn count_trees(field: &Vec<Vec<Cell>>, slope: &Slope) -> usize {
-    let mut col = 0usize;
-
-    field
-        .iter()
+fn count_trees(field: &Grid<Cell>, slope: &Slope) -> usize {
+    (0..field.height())
+        .step_by(slope.down)
         .enumerate()
-        .filter(|&row| {
-            if row.0 % slope.down == 0 {
-                let is_tree = row.1.get(col % row.1.len()).unwrap_or(&Cell::Empty) == &Cell::Tree;
-                col += slope.right;
-
-                is_tree
-            } else {
-                false
-            }
+        .filter(|&(step, row)| {
+            let col = step * slope.right;
+            field.at(col as isize, row as isize) == Some(&Cell::Tree)
         })
         .count()
 }
@@ -52,17 +47,33 @@ struct Slope {
     down: usize,
 }
 
-fn parse_input(input: &str) -> Vec<Vec<Cell>> {
-    input
-        .lines()
-        .map(|l| l.trim().chars().map(parse_cell).collect())
-        .collect()
+// The rows repeat to the right, so they must have the same width
+fn parse_input(input: &str) -> Result<Grid<Cell>, ParseError> {
+    let field = Grid::parse(input)?;
+
+    if field.height() == 0 {
+        return Err(ParseError::input("the map is empty"));
+    }
+
+    Ok(field.with_edges(Edges::Wrapping))
 }
 
-fn parse_cell(cell: char) -> Cell {
-    match cell {
-        '#' => Cell::Tree,
-        _ => Cell::Empty,
+impl advgrid::Cell for Cell {
+    const EXPECTED: &'static str = "\".\" or \"#\"";
+
+    fn from_char(c: char) -> Option<Cell> {
+        match c {
+            '#' => Some(Cell::Tree),
+            '.' => Some(Cell::Empty),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Cell::Tree => '#',
+            Cell::Empty => '.',
+        }
     }
 }
 
@@ -94,18 +105,29 @@ mod tests {
 
     #[test]
     fn test_answers() {
-        assert_eq!(count_single_slope(""), 0);
-        assert_eq!(count_multiple_slopes(""), 0);
+        assert!(count_single_slope("").is_err());
+        assert!(count_multiple_slopes("").is_err());
     }
 
     #[test]
     fn test_parse_input() {
-        let result = parse_input("#...#");
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].len(), 5);
-        assert_eq!(result[0][0], Cell::Tree);
-        assert_eq!(result[0][3], Cell::Empty);
-        assert_eq!(result[0][4], Cell::Tree);
+        let result = parse_input("#...#").unwrap();
+        assert_eq!(result.height(), 1);
+        assert_eq!(result.width(), 5);
+        assert_eq!(result[(0, 0)], Cell::Tree);
+        assert_eq!(result[(3, 0)], Cell::Empty);
+        assert_eq!(result[(4, 0)], Cell::Tree);
+        assert_eq!(result.at(5, 0), Some(&Cell::Tree));
+        assert_eq!(result.to_string(), "#...#");
+
+        assert_eq!(
+            parse_input("#...#\n#.o.#").err(),
+            Some(ParseError::new(2, 3, "o", "expected \".\" or \"#\""))
+        );
+        assert_eq!(
+            parse_input("#...#\n#..#").err(),
+            Some(ParseError::new(2, 1, "#..#", "expected 5 cells in the row"))
+        );
     }
 
     #[test]
@@ -116,7 +138,7 @@ mod tests {
 
     #[test]
     fn test_count_trees() {
-        let field = parse_input(INPUT);
+        let field = parse_input(INPUT).unwrap();
         let count = count_trees(&field, &Slope { right: 3, down: 1 });
 
         assert_eq!(count, 7);
@@ -124,7 +146,7 @@ mod tests {
 
     #[test]
     fn test_count_trees_advanced() {
-        let field = parse_input(INPUT);
+        let field = parse_input(INPUT).unwrap();
         assert_eq!(count_trees(&field, &Slope { right: 1, down: 1 }), 2);
         assert_eq!(count_trees(&field, &Slope { right: 5, down: 1 }), 3);
         assert_eq!(count_trees(&field, &Slope { right: 1, down: 2 }), 2);
@@ -132,11 +154,11 @@ mod tests {
 
     #[test]
     fn test_count_single_slope() {
-        assert_eq!(count_single_slope(INPUT), 7);
+        assert_eq!(count_single_slope(INPUT), Ok(7));
     }
 
     #[test]
     fn test_count_multiple_slopes() {
-        assert_eq!(count_multiple_slopes(INPUT), 336);
+        assert_eq!(count_multiple_slopes(INPUT), Ok(336));
     }
 }
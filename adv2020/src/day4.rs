@@ -1,33 +1,28 @@
+use advparse::{parse_records, ParseError};
 use itertools::Itertools;
 
-pub fn count_simple_results(input: &str) -> usize {
-    let passports = parse_input(input);
-    passports.iter().filter(|pass| has_all_data(pass)).count()
+pub fn count_simple_results(input: &str) -> Result<usize, ParseError> {
+    let passports = parse_input(input)?;
+    Ok(passports.iter().filter(|pass| has_all_data(pass)).count())
 }
 
-pub fn count_advanced_results(input: &str) -> usize {
-    let passports = parse_input(input);
-    passports
+pub fn count_advanced_results(input: &str) -> Result<usize, ParseError> {
+    let passports = parse_input(input)?;
+    Ok(passports
         .iter()
         .filter(|pass| has_all_data(pass) && is_valid_advanced(pass))
-        .count()
+        .count())
 }
 
-fn parse_input(input: &str) -> Vec<String> {
-    input
-        .lines()
-        .chunk_by(|elt| elt.is_empty())
-        .into_iter()
-        .filter(|(key, _)| !key)
-        .map(|(_, group)| {
-            group
-                .collect_vec()
-                .join(" ")
-                .split_whitespace()
-                .filter(|split| !split.is_empty())
-                .join(" ")
-        })
-        .collect()
+// The values are checked later, but every field must look like key:value
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    parse_records(input, |record| {
+        if let Some(field) = record.words().find(|field| !field.contains(':')) {
+            return Err(record.error(field, "expected a field key:value"));
+        }
+
+        Ok(record.words().join(" "))
+    })
 }
 
 fn has_all_data(pass: &str) -> bool {
@@ -135,7 +130,7 @@ mod tests {
                                               hcl:#cfa07d eyr:2025 pid:166559648
                                               iyr:2011 ecl:brn hgt:59in"#
             ),
-            2
+            Ok(2)
         );
 
         assert_eq!(
@@ -154,7 +149,7 @@ mod tests {
                                                 eyr:2038 hcl:74454a iyr:2023:iyr:2023
                                                 pid:3556412378 byr:2007"#
             ),
-            0
+            Ok(0)
         );
 
         assert_eq!(
@@ -172,15 +167,20 @@ mod tests {
 
                                                 iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719"#
             ),
-            4
+            Ok(4)
         );
     }
 
     #[test]
     fn test_parse_input() {
-        let result = parse_input("abc\ndef\n\n  hij   klm");
-        assert_eq!(result[0], String::from("abc def"));
-        assert_eq!(result[1], String::from("hij klm"));
+        let result = parse_input("a:bc\nde:f\n\n  hi:j   klm:").unwrap();
+        assert_eq!(result[0], String::from("a:bc de:f"));
+        assert_eq!(result[1], String::from("hi:j klm:"));
+
+        assert_eq!(
+            parse_input("a:bc\n\nde:f ghi").err(),
+            Some(ParseError::new(3, 6, "ghi", "expected a field key:value"))
+        );
     }
 
     #[test]
@@ -1,64 +1,75 @@
+use advparse::{parse_lines, ParseError};
 use itertools::Itertools;
 
-pub fn find_maximum_seat_id(input: &str) -> usize {
-    parse_seats(input)
+pub fn find_maximum_seat_id(input: &str) -> Result<usize, ParseError> {
+    parse_seats(input)?
+        .iter()
         .map(|seat| seat.id())
         .max()
-        .unwrap_or_default()
+        .ok_or_else(|| ParseError::input("there are no boarding passes"))
 }
 
-pub fn find_your_seat(input: &str) -> usize {
-    if let Err(result) =
-        parse_seats(input)
-            .map(|seat| seat.id())
-            .sorted()
-            .try_fold(0, |acc, item| {
-                if item - acc == 1 || acc == 0 {
-                    Ok(item)
-                } else {
-                    Err(acc + 1) // next ID after the last successful
-                }
-            })
+pub fn find_your_seat(input: &str) -> Result<usize, ParseError> {
+    if let Err(result) = parse_seats(input)?
+        .iter()
+        .map(|seat| seat.id())
+        .sorted()
+        .try_fold(0, |acc, item| {
+            if item - acc == 1 || acc == 0 {
+                Ok(item)
+            } else {
+                Err(acc + 1) // next ID after the last successful
+            }
+        })
     {
-        result
+        Ok(result)
     } else {
-        0
+        Err(ParseError::input(
+            "there is no free seat between the boarding passes",
+        ))
     }
 }
 
-fn parse_seats<'a>(input: &'a str) -> impl Iterator<Item = Seat> + 'a {
-    input.lines().map(&str::trim).map(parse_seat)
+fn parse_seats(input: &str) -> Result<Vec<Seat>, ParseError> {
+    parse_lines(input, parse_seat)
 }
 
 struct Seat(usize, usize);
 
-fn parse_seat(input: &str) -> Seat {
+// FBFBBFFRLR: seven letters for the row, three for the column
+fn parse_seat(input: &str) -> Result<Seat, ParseError> {
     let mut row = (0, 127usize);
     let mut column = (0, 7usize);
+    let seat = input.trim();
+
+    if seat.len() != 10 {
+        return Err(ParseError::in_line(input, seat, "expected 10 letters"));
+    }
 
-    input
-        .chars()
-        .map(|c| c.to_ascii_uppercase())
-        .enumerate()
-        .for_each(|(position, c)| match position {
-            0..=6 => {
+    for (position, c) in seat.char_indices() {
+        let letter = &seat[position..position + c.len_utf8()];
+
+        match (position, c.to_ascii_uppercase()) {
+            (0..=6, c @ ('F' | 'B')) => {
                 let lower = (row.0, (row.1 + row.0 + 1) / 2 - 1);
                 let upper = ((row.1 + row.0 + 1) / 2, row.1);
 
                 row = if c == 'F' { lower } else { upper };
             }
 
-            7..=9 => {
+            (7..=9, c @ ('L' | 'R')) => {
                 let lower = (column.0, (column.1 + column.0 + 1) / 2 - 1);
                 let upper = ((column.1 + column.0 + 1) / 2, column.1);
 
                 column = if c == 'L' { lower } else { upper };
             }
 
-            _ => {}
-        });
+            (0..=6, _) => return Err(ParseError::in_line(input, letter, "expected F or B")),
+            _ => return Err(ParseError::in_line(input, letter, "expected L or R")),
+        }
+    }
 
-    Seat(row.0, column.0)
+    Ok(Seat(row.0, column.0))
 }
 
 impl Seat {
@@ -77,34 +88,44 @@ mod tests {
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_maximum_seat_id(""), 0);
-        assert_eq!(find_your_seat(""), 0);
+        assert!(find_maximum_seat_id("").is_err());
+        assert!(find_your_seat("").is_err());
     }
 
     #[test]
     fn test_parse_seat() {
-        let Seat(row, col) = parse_seat("FBFBBFFRLR");
+        let Seat(row, col) = parse_seat("FBFBBFFRLR").unwrap();
         assert_eq!(row, 44);
         assert_eq!(col, 5);
 
-        let Seat(row, col) = parse_seat("FFFBBBFRRRRRRRRRRR");
+        let Seat(row, col) = parse_seat("fffbbbfrrr").unwrap();
         assert_eq!(row, 14);
         assert_eq!(col, 7);
+
+        assert!(parse_seat("FFFBBBFRRRRRRRRRRR").is_err());
+        assert_eq!(
+            parse_seat("FFFBBBFRLF").err(),
+            Some(ParseError::new(0, 10, "F", "expected L or R"))
+        );
+        assert_eq!(
+            parse_seat("FFFBLBFRLR").err(),
+            Some(ParseError::new(0, 5, "L", "expected F or B"))
+        );
     }
 
     #[test]
     fn test_parse_seats() {
-        let result = parse_seats(INPUT);
-        assert_eq!(result.count(), 3);
+        let result = parse_seats(INPUT).unwrap();
+        assert_eq!(result.len(), 3);
     }
 
     #[test]
     fn test_find_maximum_seat_id() {
-        assert_eq!(find_maximum_seat_id(INPUT), 820);
+        assert_eq!(find_maximum_seat_id(INPUT), Ok(820));
     }
 
     #[test]
     fn test_find_your_seat() {
-        assert_eq!(find_your_seat("BBFFBBFRLL\nBBFFBBFRRL"), 821);
+        assert_eq!(find_your_seat("BBFFBBFRLL\nBBFFBBFRRL"), Ok(821));
     }
 }
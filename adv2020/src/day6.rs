@@ -1,86 +1,59 @@
+use advparse::{parse_records, ParseError};
 use std::collections::{HashMap, HashSet};
 
-pub fn find_answer1(input: &str) -> usize {
-    let mut result = 0usize;
-
-    let mut set: HashSet<char> = HashSet::new();
-
-    for line in input.lines() {
-        if line.is_empty() {
-            result += set.len();
-            set.clear();
-        } else {
-            line.chars().for_each(|c: char| {
-                set.insert(c);
-            })
-        }
-    }
-
-    result + set.len()
+pub fn find_answer1(input: &str) -> Result<usize, ParseError> {
+    let groups = parse_groups(input)?;
+
+    Ok(groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .flat_map(|answers| answers.chars())
+                .collect::<HashSet<char>>()
+                .len()
+        })
+        .sum())
 }
 
-pub fn find_answer2(input: &str) -> usize {
-    let mut result = 0usize;
-
-    let mut map_stats: HashMap<char, usize> = HashMap::new();
-    let mut lines = 0usize;
-
-    for line in input.lines() {
-        if line.is_empty() {
-            result += map_stats.iter().filter(|&(_, v)| *v == lines).count();
-            lines = 0;
-            map_stats.clear();
-        } else {
-            line.chars().for_each(|c: char| {
-                if map_stats.contains_key(&c) {
-                    let value = map_stats.get(&c).unwrap();
-                    let value = *value + 1;
-                    map_stats.insert(c, value);
-                } else {
-                    map_stats.insert(c, 1);
-                }
-            });
-
-            lines += 1;
-        }
-    }
-
-    result + map_stats.iter().filter(|&(_, v)| *v == lines).count()
-}
-/*
-struct AggregateIterator<'a, T: Iterator<Item=&'a str> + 'a> {
-    input: T,
+pub fn find_answer2(input: &str) -> Result<usize, ParseError> {
+    let groups = parse_groups(input)?;
+
+    Ok(groups
+        .iter()
+        .map(|group| {
+            let mut map_stats: HashMap<char, usize> = HashMap::new();
+            group
+                .iter()
+                .flat_map(|answers| answers.chars())
+                .for_each(|c| *map_stats.entry(c).or_default() += 1);
+
+            map_stats.values().filter(|&&v| v == group.len()).count()
+        })
+        .sum())
 }
 
-impl AggregateIterator<T> {
-    fn new(input: &str) -> AggregateIterator<T> {
-        AggregateIterator {
-            input: input.lines()
-        }
-    }
+// Every person of a group answers "yes" with the letters of the questions a-z
+fn parse_groups(input: &str) -> Result<Vec<Vec<&str>>, ParseError> {
+    parse_records(input, |record| {
+        record
+            .lines()
+            .map(|(number, line)| {
+                let line = line.trim();
+                match line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+                    Some((i, c)) => Err(ParseError::in_line(
+                        line,
+                        &line[i..i + c.len_utf8()],
+                        "expected a question a-z",
+                    )
+                    .at_line(number)),
+                    None => Ok(line),
+                }
+            })
+            .collect()
+    })
 }
 
-impl Iterator for AggregateIterator<T> {
-    type Item = String;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut vec: Vec<String> = Vec::new();
-
-        for str in self.input.map(&str::trim) {
-            if str.is_empty {
-                break;
-            }
-            vec.append(str);
-        }
-
-        if vec.is_empty() {
-            None
-        } else {
-            Some(vec.join(" "))
-        }
-    }
-}*/
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,8 +78,12 @@ a
 
 b"#
             ),
-            11
+            Ok(11)
+        );
+        assert_eq!(find_answer2(""), Ok(0));
+        assert_eq!(
+            find_answer2("abc\n\na1"),
+            Err(ParseError::new(3, 2, "1", "expected a question a-z"))
         );
-        assert_eq!(find_answer2(""), 0);
     }
 }
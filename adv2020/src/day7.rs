@@ -1,30 +1,10 @@
-use itertools::Itertools;
+use advparse::{expect_token, next_token, parse_lines, parse_number, ParseError};
 use std::collections::HashMap;
 
-pub fn find_answer1(input: &str) -> usize {
-    let mut rules: HashMap<String, HashMap<String, usize>> = HashMap::new();
-
-    input.lines().for_each(|line| {
-        let line_split: Vec<&str> = line.split("contain").collect();
-        let what = line_split[0].split_whitespace().take(2).join(" ");
-        let mut map: HashMap<String, usize> = HashMap::new();
-        let bags_split: Vec<&str> = line_split[1].split(", ").collect();
-        if !bags_split.contains(&"no other") {
-            for bag in bags_split {
-                let bag = bag.split_whitespace().take(3).join(" ");
-                let num = bag
-                    .split_whitespace()
-                    .next()
-                    .unwrap()
-                    .parse::<usize>()
-                    .unwrap_or_default();
-                let color = bag.split_whitespace().skip(1).take(2).join(" ");
-                map.insert(color, num);
-            }
-        }
+type Rules = HashMap<String, HashMap<String, usize>>;
 
-        rules.insert(what, map);
-    });
+pub fn find_answer1(input: &str) -> Result<usize, ParseError> {
+    let rules = parse_rules(input)?;
 
     let mut result = 0;
 
@@ -33,88 +13,128 @@ pub fn find_answer1(input: &str) -> usize {
             result += 1;
         }
     }
-    result
+    Ok(result)
 }
 
-fn contains(rules: &HashMap<String, HashMap<String, usize>>, key: &String, what: &String) -> bool {
-    if rules.contains_key(key) {
-        let nested_bags = rules.get(key).unwrap();
-        if nested_bags.is_empty() {
-            return false;
-        } else {
-            let mut result = false;
-
-            for (k, _) in nested_bags {
-                if nested_bags.contains_key(what) {
-                    return true;
-                }
-
-                result = result | contains(rules, k, what);
-
-                if result {
-                    return true;
+// light red bags contain 1 bright white bag, 2 muted yellow bags.
+fn parse_rule(line: &str) -> Result<(String, HashMap<String, usize>), ParseError> {
+    let (what, contents) = line
+        .split_once(" contain ")
+        .ok_or_else(|| ParseError::in_line(line, line.trim(), "expected \"contain\""))?;
+
+    let mut tokens = what.split_whitespace();
+    let what = parse_color(line, &mut tokens)?;
+    expect_token(line, &mut tokens, "bags")?;
+
+    let mut map: HashMap<String, usize> = HashMap::new();
+    let contents = contents.trim().trim_end_matches('.');
+    if contents != "no other bags" {
+        for bag in contents.split(", ") {
+            let mut tokens = bag.split_whitespace();
+            let num = parse_number(line, next_token(line, &mut tokens, "a count")?)?;
+            let color = parse_color(line, &mut tokens)?;
+            match next_token(line, &mut tokens, "\"bag\" or \"bags\"")? {
+                "bag" | "bags" => (),
+                token => {
+                    return Err(ParseError::in_line(
+                        line,
+                        token,
+                        "expected \"bag\" or \"bags\"",
+                    ))
                 }
             }
 
-            return result;
+            map.insert(color, num);
         }
     }
 
-    false
+    Ok((what, map))
 }
 
-fn count_bags(rules: &HashMap<String, HashMap<String, usize>>, what: &String) -> usize {
-    if !rules.contains_key(what) {
-        return 0;
+// Two words, e.g. shiny gold
+fn parse_color<'a, I>(line: &str, tokens: &mut I) -> Result<String, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let shade = next_token(line, tokens, "a color")?;
+    let color = next_token(line, tokens, "a color")?;
+
+    Ok(format!("{} {}", shade, color))
+}
+
+// A bag can't contain itself, not even inside other bags, or there would be no end of them
+fn parse_rules(input: &str) -> Result<Rules, ParseError> {
+    let rules: Rules = parse_lines(input, parse_rule)?.into_iter().collect();
+    let mut colors: Vec<&String> = rules.keys().collect();
+    colors.sort_unstable();
+
+    let mut visited = HashMap::new();
+    if let Some(color) = colors
+        .into_iter()
+        .find_map(|color| find_cycle(&rules, color, &mut visited))
+    {
+        return Err(ParseError::input(format!(
+            "the {} bag contains itself",
+            color
+        )));
     }
 
-    let bags = rules.get(what).unwrap();
-    if bags.is_empty() {
-        return 0;
+    Ok(rules)
+}
+
+// The visited bags are either on the current path (false) or done (true)
+fn find_cycle<'a>(
+    rules: &'a Rules,
+    color: &'a String,
+    visited: &mut HashMap<&'a String, bool>,
+) -> Option<&'a String> {
+    match visited.get(color) {
+        Some(true) => return None,
+        Some(false) => return Some(color),
+        None => (),
     }
 
-    let mut result = 0usize;
-    for (small_bag, count) in bags {
-        result += count_bags(rules, small_bag) * count + count;
+    visited.insert(color, false);
+    for inner in rules.get(color).into_iter().flat_map(|bags| bags.keys()) {
+        if let Some(cycle) = find_cycle(rules, inner, visited) {
+            return Some(cycle);
+        }
     }
+    visited.insert(color, true);
 
-    result
+    None
 }
 
-pub fn find_answer2(input: &str) -> usize {
-    let mut rules: HashMap<String, HashMap<String, usize>> = HashMap::new();
-
-    input.lines().for_each(|line| {
-        let line_split: Vec<&str> = line.split("contain").collect();
-        let what = line_split[0].split_whitespace().take(2).join(" ");
-        let mut map: HashMap<String, usize> = HashMap::new();
-        let bags_split: Vec<&str> = line_split[1].split(", ").collect();
-        if !bags_split.contains(&"no other") {
-            for bag in bags_split {
-                let bag = bag.split_whitespace().take(3).join(" ");
-                let num = bag
-                    .split_whitespace()
-                    .next()
-                    .unwrap()
-                    .parse::<usize>()
-                    .unwrap_or_default();
-                let color = bag.split_whitespace().skip(1).take(2).join(" ");
-                map.insert(color, num);
-            }
+fn contains(rules: &Rules, key: &String, what: &String) -> bool {
+    match rules.get(key) {
+        Some(nested_bags) => {
+            nested_bags.contains_key(what) || nested_bags.keys().any(|k| contains(rules, k, what))
         }
+        None => false,
+    }
+}
 
-        rules.insert(what, map);
-    });
+// None, if there are too many bags to count
+fn count_bags(rules: &Rules, what: &String) -> Option<usize> {
+    let Some(bags) = rules.get(what) else {
+        return Some(0);
+    };
 
-    count_bags(&rules, &String::from("shiny gold"))
+    let mut result = 0usize;
+    for (small_bag, count) in bags {
+        let inside = count_bags(rules, small_bag)?.checked_mul(*count)?;
+        result = result.checked_add(inside)?.checked_add(*count)?;
+    }
+
+    Some(result)
 }
-/*
-fn parse_input<'a>(input: &'a str) -> impl Iterator<Item=Seat> + 'a {
-    input.lines()
-        .map(&str::trim)
-        .map(parse_seat)
+
+pub fn find_answer2(input: &str) -> Result<usize, ParseError> {
+    let rules = parse_rules(input)?;
+
+    count_bags(&rules, &String::from("shiny gold"))
+        .ok_or_else(|| ParseError::input("there are too many bags to count"))
 }
-*/
 
 #[cfg(test)]
 mod tests {
@@ -122,7 +142,65 @@ mod tests {
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_answer1(""), 0);
-        assert_eq!(find_answer2(""), 0);
+        assert_eq!(find_answer1(""), Ok(0));
+        assert_eq!(find_answer2(""), Ok(0));
+    }
+
+    #[test]
+    fn test_answers() {
+        let input = r#"light red bags contain 1 bright white bag, 2 muted yellow bags.
+                       dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+                       bright white bags contain 1 shiny gold bag.
+                       muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+                       shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+                       dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+                       vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+                       faded blue bags contain no other bags.
+                       dotted black bags contain no other bags."#;
+
+        assert_eq!(find_answer1(input), Ok(4));
+        assert_eq!(find_answer2(input), Ok(32));
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!(
+            parse_rule("faded blue bags contain 3 dark red boxes."),
+            Err(ParseError::new(
+                0,
+                36,
+                "boxes",
+                "expected \"bag\" or \"bags\""
+            ))
+        );
+        assert_eq!(
+            parse_rule("faded blue bags contain two dark bags."),
+            Err(ParseError::new(0, 25, "two", "expected a number"))
+        );
+        assert!(parse_rule("faded blue bags").is_err());
+    }
+
+    #[test]
+    fn test_wrong_rules() {
+        assert_eq!(
+            find_answer1("dark red bags contain 1 dark red bag."),
+            Err(ParseError::input("the dark red bag contains itself"))
+        );
+        assert_eq!(
+            find_answer2(
+                "shiny gold bags contain 2 dark red bags.\n\
+                 dark red bags contain 1 light red bag.\n\
+                 light red bags contain 3 dark red bags."
+            ),
+            Err(ParseError::input("the dark red bag contains itself"))
+        );
+        assert_eq!(
+            find_answer2(
+                "shiny gold bags contain 4294967296 dark red bags.\n\
+                 dark red bags contain 4294967296 light red bags.\n\
+                 light red bags contain no other bags."
+            ),
+            Err(ParseError::input("there are too many bags to count"))
+        );
     }
 }
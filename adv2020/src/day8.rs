@@ -1,17 +1,18 @@
+use advparse::{next_token, parse_lines, parse_number, ParseError};
 use std::collections::HashSet;
 
-pub fn execute_first_program(input: &str) -> i32 {
-    let program = parse_program(input);
+pub fn execute_first_program(input: &str) -> Result<i32, ParseError> {
+    let program = parse_program(input)?;
 
     if let Execution::Infinite(result) = execute_program(&program) {
-        result
+        Ok(result)
     } else {
-        0
+        Err(ParseError::input("the program doesn't loop"))
     }
 }
 
-pub fn execute_second_program(input: &str) -> i32 {
-    let mut program = parse_program(input);
+pub fn execute_second_program(input: &str) -> Result<i32, ParseError> {
+    let mut program = parse_program(input)?;
 
     for i in 0..program.len() {
         if let Operation::ACC(_) = program[i] {
@@ -21,12 +22,15 @@ pub fn execute_second_program(input: &str) -> i32 {
         program[i] = swap_statement(&program[i]);
 
         if let Execution::Finished(result) = execute_program(&program) {
-            return result;
+            return Ok(result);
         }
 
         program[i] = swap_statement(&program[i]);
     }
-    0
+
+    Err(ParseError::input(
+        "no swapped instruction lets the program finish",
+    ))
 }
 
 fn swap_statement(statement: &Operation) -> Operation {
@@ -44,30 +48,30 @@ enum Operation {
     JMP(i32),
 }
 
-fn parse_line(input: &str) -> Option<Operation> {
-    let input = input.trim().to_ascii_lowercase();
-    let tokens: Vec<&str> = input.split_whitespace().collect();
-
-    if tokens.len() != 2 {
-        return None;
+// acc +1
+fn parse_line(input: &str) -> Result<Operation, ParseError> {
+    let mut tokens = input.split_whitespace();
+
+    let command = next_token(input, &mut tokens, "an operation")?;
+    let value = parse_number(input, next_token(input, &mut tokens, "an argument")?)?;
+    if let Some(token) = tokens.next() {
+        return Err(ParseError::in_line(
+            input,
+            token,
+            "expected the end of the line",
+        ));
     }
 
-    let command = tokens[0];
-    let value_str = tokens[1];
-    if let Ok(value) = value_str.parse::<i32>() {
-        match command {
-            "acc" => Some(Operation::ACC(value)),
-            "nop" => Some(Operation::NOP(value)),
-            "jmp" => Some(Operation::JMP(value)),
-            _ => None,
-        }
-    } else {
-        None
+    match command.to_ascii_lowercase().as_str() {
+        "acc" => Ok(Operation::ACC(value)),
+        "nop" => Ok(Operation::NOP(value)),
+        "jmp" => Ok(Operation::JMP(value)),
+        _ => Err(ParseError::in_line(input, command, "unknown operation")),
     }
 }
 
-fn parse_program(input: &str) -> Vec<Operation> {
-    input.lines().filter_map(parse_line).collect()
+fn parse_program(input: &str) -> Result<Vec<Operation>, ParseError> {
+    parse_lines(input, parse_line)
 }
 
 fn execute_program(program: &Vec<Operation>) -> Execution {
@@ -132,18 +136,27 @@ acc +6"#;
 
     #[test]
     fn test_parse_line() {
-        assert_eq!(parse_line("acc +1"), Some(Operation::ACC(1)));
-        assert_eq!(parse_line("jmp +4"), Some(Operation::JMP(4)));
-        assert_eq!(parse_line("nop -2"), Some(Operation::NOP(-2)));
-        assert_eq!(parse_line("nop"), None);
-        assert_eq!(parse_line("nop nop nop"), None);
-        assert_eq!(parse_line("nop nop"), None);
-        assert_eq!(parse_line("abc -3"), None);
+        assert_eq!(parse_line("acc +1"), Ok(Operation::ACC(1)));
+        assert_eq!(parse_line("jmp +4"), Ok(Operation::JMP(4)));
+        assert_eq!(parse_line("nop -2"), Ok(Operation::NOP(-2)));
+        assert_eq!(
+            parse_line("nop"),
+            Err(ParseError::new(0, 4, "", "expected an argument"))
+        );
+        assert!(parse_line("nop nop nop").is_err());
+        assert_eq!(
+            parse_line("nop nop"),
+            Err(ParseError::new(0, 5, "nop", "expected a number"))
+        );
+        assert_eq!(
+            parse_line("abc -3"),
+            Err(ParseError::new(0, 1, "abc", "unknown operation"))
+        );
     }
 
     #[test]
     fn test_parse_program() {
-        let program = parse_program(INPUT_PROGRAM);
+        let program = parse_program(INPUT_PROGRAM).unwrap();
         assert_eq!(program.len(), 9);
         assert_eq!(program[0], Operation::NOP(0));
         assert_eq!(program[8], Operation::ACC(6));
@@ -151,22 +164,22 @@ acc +6"#;
 
     #[test]
     fn test_execute_program() {
-        let program = parse_program(INPUT_PROGRAM);
+        let program = parse_program(INPUT_PROGRAM).unwrap();
         assert_eq!(execute_program(&program), Execution::Infinite(5));
 
-        let program = parse_program("jmp 1000");
+        let program = parse_program("jmp 1000").unwrap();
         assert_eq!(execute_program(&program), Execution::Finished(0));
 
-        let program = parse_program("jmp -1000");
+        let program = parse_program("jmp -1000").unwrap();
         assert_eq!(execute_program(&program), Execution::None);
     }
 
     #[test]
     fn test_execute_programs() {
-        assert_eq!(execute_first_program(INPUT_PROGRAM), 5);
-        assert_eq!(execute_second_program(INPUT_PROGRAM), 8);
+        assert_eq!(execute_first_program(INPUT_PROGRAM), Ok(5));
+        assert_eq!(execute_second_program(INPUT_PROGRAM), Ok(8));
 
-        assert_eq!(execute_first_program(""), 0);
-        assert_eq!(execute_second_program(""), 0);
+        assert!(execute_first_program("").is_err());
+        assert!(execute_second_program("").is_err());
     }
 }
@@ -1,35 +1,36 @@
+use advcore::{Param, Params};
+use advparse::{parse_numbers, ParseError};
 use itertools::Itertools;
 
-pub fn find_weak_number_25(input: &str) -> usize {
-    let input: Vec<usize> = parse_input(input);
+// The example of the puzzle has a preamble of 5
+pub const PREAMBLE: Param = Param::new("preamble", "25", "the length of the preamble");
 
-    find_weak_number(&input, 25).unwrap_or((0, 0)).0
+pub fn weak_number(input: &[usize], params: &Params) -> Result<usize, ParseError> {
+    Ok(find_weak_number(input, params.get(&PREAMBLE)?)
+        .ok_or_else(no_weak_number)?
+        .0)
 }
 
-pub fn find_sum_of_any_numbers(input: &str) -> usize {
-    let input: Vec<usize> = parse_input(input);
-
-    if let Some((weak_number, weak_number_pos)) = find_weak_number(&input, 25) {
-        (3..weak_number_pos)
-            .find_map(|window_size| {
-                find_possible_sum(&input[0..weak_number_pos], window_size, weak_number)
-            })
-            .unwrap_or_default()
-    } else {
-        0
-    }
+pub fn sum_of_any_numbers(input: &[usize], params: &Params) -> Result<usize, ParseError> {
+    let (weak_number, weak_number_pos) =
+        find_weak_number(input, params.get(&PREAMBLE)?).ok_or_else(no_weak_number)?;
+
+    (3..weak_number_pos)
+        .find_map(|window_size| {
+            find_possible_sum(&input[0..weak_number_pos], window_size, weak_number)
+        })
+        .ok_or_else(|| ParseError::input(format!("no contiguous set sums to {}", weak_number)))
 }
 
-fn parse_input(input: &str) -> Vec<usize> {
-    input
-        .lines()
-        .map(&str::trim)
-        .map(&str::parse::<usize>)
-        .filter_map(Result::ok)
-        .collect()
+fn no_weak_number() -> ParseError {
+    ParseError::input("every number is a sum of two previous ones")
 }
 
-fn find_weak_number(input: &Vec<usize>, preamble: usize) -> Option<(usize, usize)> {
+pub fn parse_input(input: &str) -> Result<Vec<usize>, ParseError> {
+    parse_numbers(input)
+}
+
+fn find_weak_number(input: &[usize], preamble: usize) -> Option<(usize, usize)> {
     for num in preamble..input.len() {
         let last_numbers = &input[num.saturating_sub(preamble)..num];
 
@@ -69,16 +70,34 @@ mod tests {
 
     #[test]
     fn test_empty_answers() {
-        assert_eq!(find_weak_number_25(""), 0);
-        assert_eq!(find_sum_of_any_numbers(""), 0);
+        assert!(weak_number(&parse_input("").unwrap(), &Params::new()).is_err());
+        assert!(sum_of_any_numbers(&parse_input("").unwrap(), &Params::new()).is_err());
+    }
+
+    #[test]
+    fn test_example_preamble() {
+        let numbers = parse_input(
+            "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576",
+        )
+        .unwrap();
+        let mut params = Params::new();
+        params.set("preamble", "5");
+
+        assert_eq!(weak_number(&numbers, &params), Ok(127));
+        assert_eq!(sum_of_any_numbers(&numbers, &params), Ok(62));
     }
 
     #[test]
     fn test_parse_input() {
-        let result = parse_input("  1   \n2   ");
+        let result = parse_input("  1   \n2   ").unwrap();
         assert_eq!(result.len(), 2);
         assert_eq!(result[0], 1);
         assert_eq!(result[1], 2);
+
+        assert_eq!(
+            parse_input("1\n-2"),
+            Err(ParseError::new(2, 1, "-2", "expected a number"))
+        );
     }
 
     #[test]
@@ -0,0 +1,66 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+// Every key gets one of its candidates, and no two keys get the same one, e.g. the ticket
+// fields of day 16 and the allergens of day 21. A key with a single candidate left takes it
+// and removes it from the others, until every key has its own; None, if that gets stuck
+pub fn eliminate<K, V>(mut candidates: BTreeMap<K, BTreeSet<V>>) -> Option<BTreeMap<K, V>>
+where
+    K: Ord + Clone,
+    V: Ord + Clone,
+{
+    let mut resolved = BTreeMap::new();
+
+    while !candidates.is_empty() {
+        let (key, value) = candidates
+            .iter()
+            .find(|(_, values)| values.len() == 1)
+            .and_then(|(key, values)| Some((key.clone(), values.first()?.clone())))?;
+
+        candidates.remove(&key);
+        for values in candidates.values_mut() {
+            values.remove(&value);
+        }
+        resolved.insert(key, value);
+    }
+
+    Some(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(input: &[(&'static str, &[u8])]) -> BTreeMap<&'static str, BTreeSet<u8>> {
+        input
+            .iter()
+            .map(|&(key, values)| (key, values.iter().copied().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_eliminate() {
+        let resolved = eliminate(candidates(&[
+            ("a", &[1, 2, 3]),
+            ("b", &[2]),
+            ("c", &[2, 3]),
+        ]));
+
+        assert_eq!(
+            resolved,
+            Some(BTreeMap::from([("a", 1), ("b", 2), ("c", 3)]))
+        );
+        assert_eq!(eliminate(candidates(&[])), Some(BTreeMap::new()));
+    }
+
+    #[test]
+    fn test_eliminate_stuck() {
+        // Both keys could take either value
+        assert_eq!(
+            eliminate(candidates(&[("a", &[1, 2]), ("b", &[1, 2])])),
+            None
+        );
+        // Two keys want the same single value
+        assert_eq!(eliminate(candidates(&[("a", &[1]), ("b", &[1])])), None);
+        assert_eq!(eliminate(candidates(&[("a", &[])])), None);
+    }
+}
@@ -8,7 +8,13 @@ mod day14;
 mod day15;
 mod day16;
 mod day17;
+mod day18;
+mod day19;
 mod day2;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
 mod day3;
 mod day4;
 mod day5;
@@ -17,124 +23,226 @@ mod day7;
 mod day8;
 mod day9;
 /* </editor-fold> */
+mod elimination;
+
+use advcore::{Answer, Day, ParsedDay, Registry, SolveError};
+use advrender::{Animator, Logger, Renderer};
+
+pub const YEAR: u16 = 2020;
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::with_params(
+        YEAR,
+        1,
+        &[day1::DESIRED_SUM],
+        |input, params| day1::find_expenses(input, params).map(Into::into),
+        |input, params| day1::find_more_expenses(input, params).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        2,
+        |input| day2::count_simple_passwords(input).map(Into::into),
+        |input| day2::count_complex_passwords(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        3,
+        |input| day3::count_single_slope(input).map(Into::into),
+        |input| day3::count_multiple_slopes(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        4,
+        |input| day4::count_simple_results(input).map(Into::into),
+        |input| day4::count_advanced_results(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        5,
+        |input| day5::find_maximum_seat_id(input).map(Into::into),
+        |input| day5::find_your_seat(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        6,
+        |input| day6::find_answer1(input).map(Into::into),
+        |input| day6::find_answer2(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        7,
+        |input| day7::find_answer1(input).map(Into::into),
+        |input| day7::find_answer2(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        8,
+        |input| day8::execute_first_program(input).map(Into::into),
+        |input| day8::execute_second_program(input).map(Into::into),
+    ));
+    registry.register(ParsedDay::with_params(
+        YEAR,
+        9,
+        &[day9::PREAMBLE],
+        day9::parse_input,
+        |numbers, params| day9::weak_number(numbers, params).map(Into::into),
+        |numbers, params| day9::sum_of_any_numbers(numbers, params).map(Into::into),
+    ));
+    registry.register(ParsedDay::new(
+        YEAR,
+        10,
+        day10::parse_as_sorted,
+        |adapters| Ok(day10::one_by_three(adapters).into()),
+        |adapters| Ok(day10::count_possible_valid_chains(adapters).into()),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        11,
+        |input| day11::find_places_direct(input).map(Into::into),
+        |input| day11::find_seats_visible_only(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        12,
+        |input| day12::find_answer1(input).map(Into::into),
+        |input| day12::find_answer2(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        13,
+        |input| day13::find_earliest_bus(input).map(Into::into),
+        |input| day13::find_earliest_timestamp(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        14,
+        |input| day14::find_answer1(input).map(Into::into),
+        |input| day14::find_answer2(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        15,
+        |input| day15::find_number_2020(input).map(Into::into),
+        |input| day15::find_number_30000000(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        16,
+        |input| day16::find_invalid_tickets(input).map(Into::into),
+        |input| day16::find_departure_tickets(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        17,
+        |input| day17::count_standard_cube(input).map(Into::into),
+        |input| day17::count_hyper_cube(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        18,
+        |input| day18::find_answer1(input).map(Into::into),
+        |input| day18::find_answer2(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        19,
+        |input| day19::count_matching_messages(input).map(Into::into),
+        |input| day19::count_matching_messages_with_loops(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        20,
+        |input| day20::multiply_corners(input).map(Into::into),
+        |input| day20::count_rough_water(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        21,
+        |input| day21::count_safe_ingredients(input).map(Into::into),
+        |input| day21::find_dangerous_ingredients(input).map(Into::into),
+    ));
+    registry.register(Day::new(
+        YEAR,
+        22,
+        |input| day22::find_answer1(input).map(Into::into),
+        |input| day22::find_answer2(input).map(Into::into),
+    ));
+    registry.register(Day::with_params(
+        YEAR,
+        23,
+        &[day23::MOVES, day23::LONG_MOVES, day23::CUPS],
+        |input, params| day23::find_labels(input, params).map(Into::into),
+        |input, params| day23::find_star_cups(input, params).map(Into::into),
+    ));
+}
+
+// The days, which can be drawn
+pub fn renderers() -> Vec<(u8, Renderer)> {
+    vec![(11, |input, _| day11::render(input))]
+}
+
+// The days, which can be animated in the terminal
+pub fn animators() -> Vec<(u8, Animator)> {
+    vec![
+        (11, |input, _| day11::animate(input)),
+        (17, |input, _| day17::animate(input)),
+    ]
+}
+
+// The days, which can print their log
+pub fn loggers() -> Vec<(u8, Logger)> {
+    vec![
+        (20, |input, _| day20::log(input)),
+        (22, |input, _| day22::log(input)),
+    ]
+}
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    register(&mut registry);
+
+    registry
+}
+
+pub fn solve(day: u8, input: &str) -> Result<(Answer, Answer), SolveError> {
+    registry().solve(YEAR, day, input)
+}
 
 pub fn print_answers(day: u8, input: String) {
-    // Trim is mandatory for one-line inputs
-    let input = input.as_str().trim();
-    let prefix = format!("Day {} / 2020:", day);
-    match day {
-        1 => println!(
-            "{} {} and {}",
-            prefix,
-            day1::find_expenses(input),
-            day1::find_more_expenses(input)
-        ),
-        2 => println!(
-            "{} {} and {}",
-            prefix,
-            day2::count_simple_passwords(input),
-            day2::count_complex_passwords(input)
-        ),
-        3 => println!(
-            "{} {} and {}",
-            prefix,
-            day3::count_single_slope(input),
-            day3::count_multiple_slopes(input)
-        ),
-        4 => println!(
-            "{} {} and {}",
-            prefix,
-            day4::count_simple_results(input),
-            day4::count_advanced_results(input)
-        ),
-        5 => println!(
-            "{} {} and {}",
-            prefix,
-            day5::find_maximum_seat_id(input),
-            day5::find_your_seat(input)
-        ),
-        6 => println!(
-            "{} {} and {}",
-            prefix,
-            day6::find_answer1(input),
-            day6::find_answer2(input)
-        ),
-        7 => println!(
-            "{} {} and {}",
-            prefix,
-            day7::find_answer1(input),
-            day7::find_answer2(input)
-        ),
-        8 => println!(
-            "{} {} and {}",
-            prefix,
-            day8::execute_first_program(input),
-            day8::execute_second_program(input)
-        ),
-        9 => println!(
-            "{} {} and {}",
-            prefix,
-            day9::find_weak_number_25(input),
-            day9::find_sum_of_any_numbers(input)
-        ),
-        10 => println!(
-            "{} {} and {}",
-            prefix,
-            day10::find_one_by_three(input),
-            day10::find_all_combinations(input)
-        ),
-        11 => println!(
-            "{} {} and {}",
-            prefix,
-            day11::find_places_direct(input),
-            day11::find_seats_visible_only(input)
-        ),
-        12 => println!(
-            "{} {} and {}",
-            prefix,
-            day12::find_answer1(input),
-            day12::find_answer2(input)
-        ),
-        13 => println!(
-            "{} {} and {}",
-            prefix,
-            day13::find_earliest_bus(input),
-            day13::find_earliest_timestamp(input)
-        ),
-        14 => println!(
-            "{} {} and {}",
-            prefix,
-            day14::find_answer1(input),
-            day14::find_answer2(input)
-        ),
-        15 => println!(
-            "{} {} and {}",
-            prefix,
-            day15::find_number_2020(input),
-            day15::find_number_30000000(input)
-        ),
-        16 => println!(
-            "{} {} and {}",
-            prefix,
-            day16::find_invalid_tickets(input),
-            day16::find_departure_tickets(input)
-        ),
-        17 => println!(
-            "{} {} and {}",
-            prefix,
-            day17::count_standard_cube(input),
-            day17::count_hyper_cube(input)
-        ),
-        // 9 => println!("{} {} and {}", prefix, day9::find_answer1(input), day9::find_answer2(input)),
-        _ => {
-            eprintln!("2020: I don't know the answer for day {} :(", day)
-        }
-    }
+    registry().print_answers(YEAR, day, input.as_str());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advparse::ParseError;
+
+    #[test]
+    fn test_registry() {
+        let registry = registry();
+
+        assert_eq!(registry.len(), 23);
+        assert_eq!(registry.years(), vec![YEAR]);
+        assert!(registry.get(YEAR, 1).is_some());
+        assert!(registry.get(YEAR, 26).is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve(5, "BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n"),
+            Ok((Answer::Unsigned(820), Answer::Unsigned(120)))
+        );
+        assert_eq!(solve(26, ""), Err(SolveError::UnknownDay(YEAR, 26)));
+        assert_eq!(
+            solve(5, "BFFFBBFRRR\nFFFBXBFRRR\n"),
+            Err(SolveError::Input(
+                YEAR,
+                5,
+                ParseError::new(2, 5, "X", "expected F or B")
+            ))
+        );
+    }
 
     #[test]
     fn test_print_answers() {
@@ -0,0 +1,85 @@
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Text(String),
+
+    // e.g. the second part of the last day in the calendar
+    Nothing,
+}
+
+impl Answer {
+    pub fn is_nothing(&self) -> bool {
+        matches!(self, Answer::Nothing)
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $target:ty, $($source:ty),+) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )+
+    };
+}
+
+answer_from!(Signed, i64, i8, i16, i32, i64, isize);
+answer_from!(Unsigned, u64, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(String::from(value))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Text(value) => f.write_str(value),
+            Answer::Nothing => f.write_str("-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_from() {
+        assert_eq!(Answer::from(-1i16), Answer::Signed(-1));
+        assert_eq!(Answer::from(42usize), Answer::Unsigned(42));
+        assert_eq!(Answer::from("abc"), Answer::Text(String::from("abc")));
+        assert_eq!(
+            Answer::from(String::from("abc")),
+            Answer::Text(String::from("abc"))
+        );
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::Signed(-7).to_string(), "-7");
+        assert_eq!(Answer::Unsigned(7).to_string(), "7");
+        assert_eq!(Answer::from("hxbxxyzz").to_string(), "hxbxxyzz");
+        assert_eq!(Answer::Nothing.to_string(), "-");
+    }
+
+    #[test]
+    fn test_is_nothing() {
+        assert!(Answer::Nothing.is_nothing());
+        assert!(!Answer::Unsigned(0).is_nothing());
+    }
+}
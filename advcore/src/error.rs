@@ -0,0 +1,47 @@
+use advparse::ParseError;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    UnknownYear(u16),
+    UnknownDay(u16, u8),
+    Input(u16, u8, ParseError),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::UnknownYear(year) => write!(f, "There are no solutions for {}", year),
+            SolveError::UnknownDay(year, day) => {
+                write!(f, "{}: I don't know the answer for day {} :(", year, day)
+            }
+            SolveError::Input(year, day, err) => {
+                write!(f, "Day {} / {}: the input is wrong, {}", day, year, err)
+            }
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_error_display() {
+        assert_eq!(
+            SolveError::UnknownYear(2016).to_string(),
+            "There are no solutions for 2016"
+        );
+        assert_eq!(
+            SolveError::UnknownDay(2020, 26).to_string(),
+            "2020: I don't know the answer for day 26 :("
+        );
+        assert_eq!(
+            SolveError::Input(2020, 12, ParseError::input("there are no numbers")).to_string(),
+            "Day 12 / 2020: the input is wrong, there are no numbers"
+        );
+    }
+}
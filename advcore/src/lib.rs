@@ -0,0 +1,14 @@
+mod answer;
+mod error;
+mod params;
+mod registry;
+mod solution;
+mod timing;
+
+pub use advparse::ParseError;
+pub use answer::Answer;
+pub use error::SolveError;
+pub use params::{Param, Params};
+pub use registry::Registry;
+pub use solution::{unsolved, Day, ParsedDay, Run, Solution};
+pub use timing::{timed, Elapsed, Timings};
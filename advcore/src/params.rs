@@ -0,0 +1,95 @@
+use crate::ParseError;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+// A named constant of a day with its default value, e.g. the 150 litres of 2015 day 17
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub about: &'static str,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: &'static str, about: &'static str) -> Param {
+        Param {
+            name,
+            default,
+            about,
+        }
+    }
+}
+
+// The values given with --param name=value; the other parameters keep their defaults
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values.insert(String::from(name), String::from(value));
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn value(&self, param: &Param) -> &str {
+        self.values
+            .get(param.name)
+            .map_or(param.default, String::as_str)
+    }
+
+    pub fn get<T: FromStr>(&self, param: &Param) -> Result<T, ParseError> {
+        let value = self.value(param);
+
+        value.parse().map_err(|_| {
+            ParseError::input(format!(
+                "the parameter {} can't be \"{}\"",
+                param.name, value
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VOLUME: Param = Param::new("volume", "150", "the litres of eggnog");
+
+    #[test]
+    fn test_defaults() {
+        let params = Params::new();
+
+        assert!(params.is_empty());
+        assert_eq!(params.value(&VOLUME), "150");
+        assert_eq!(params.get::<usize>(&VOLUME), Ok(150));
+    }
+
+    #[test]
+    fn test_set() {
+        let mut params = Params::new();
+        params.set("volume", "25");
+        params.set("other", "1");
+
+        assert_eq!(params.get::<u8>(&VOLUME), Ok(25));
+        assert_eq!(params.names().collect::<Vec<_>>(), vec!["other", "volume"]);
+
+        params.set("volume", "-1");
+        assert_eq!(
+            params.get::<usize>(&VOLUME),
+            Err(ParseError::input("the parameter volume can't be \"-1\""))
+        );
+        assert_eq!(params.get::<i32>(&VOLUME), Ok(-1));
+    }
+}
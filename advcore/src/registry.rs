@@ -0,0 +1,302 @@
+use crate::{Answer, Params, Run, Solution, SolveError, Timings};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Default)]
+pub struct Registry {
+    solutions: BTreeMap<(u16, u8), Box<dyn Solution>>,
+    params: Params,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    // A solution for the same year and day replaces the previous one
+    pub fn register<S: Solution + 'static>(&mut self, solution: S) {
+        self.solutions
+            .insert((solution.year(), solution.day()), Box::new(solution));
+    }
+
+    // The overridden parameters are passed to every day, which declares them
+    pub fn set_params(&mut self, params: Params) {
+        self.params = params;
+    }
+
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    // Every parameter must belong to one of the selected days, otherwise a typo
+    // would silently solve the puzzle with the default
+    pub fn check_params(&self, puzzles: &[(u16, u8)], params: &Params) -> Result<(), String> {
+        let known: BTreeSet<&str> = puzzles
+            .iter()
+            .filter_map(|&(year, day)| self.get(year, day))
+            .flat_map(|solution| solution.params().iter().map(|param| param.name))
+            .collect();
+
+        match params.names().find(|name| !known.contains(name)) {
+            None => Ok(()),
+            Some(name) if known.is_empty() => Err(format!(
+                "The selected days have no parameters, {} is unknown",
+                name
+            )),
+            Some(name) => Err(format!(
+                "The selected days have no parameter {}, expected one of: {}",
+                name,
+                known.into_iter().collect::<Vec<_>>().join(", ")
+            )),
+        }
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&dyn Solution> {
+        self.solutions
+            .get(&(year, day))
+            .map(|solution| solution.as_ref())
+    }
+
+    // Sorted by year, then by day
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Solution> {
+        self.solutions.values().map(|solution| solution.as_ref())
+    }
+
+    pub fn days(&self, year: u16) -> impl Iterator<Item = &dyn Solution> {
+        self.solutions
+            .range((year, u8::MIN)..=(year, u8::MAX))
+            .map(|(_, solution)| solution.as_ref())
+    }
+
+    pub fn years(&self) -> Vec<u16> {
+        let mut years: Vec<u16> = self.solutions.keys().map(|&(year, _)| year).collect();
+        years.dedup();
+
+        years
+    }
+
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+
+    pub fn solve(&self, year: u16, day: u8, input: &str) -> Result<(Answer, Answer), SolveError> {
+        self.run(year, day, input).map(|run| (run.part1, run.part2))
+    }
+
+    pub fn run(&self, year: u16, day: u8, input: &str) -> Result<Run, SolveError> {
+        let solution = self.get(year, day).ok_or_else(|| {
+            if self.days(year).next().is_none() {
+                SolveError::UnknownYear(year)
+            } else {
+                SolveError::UnknownDay(year, day)
+            }
+        })?;
+
+        // Trim is mandatory for one-line inputs
+        let input = input.trim();
+
+        solution
+            .run(input, &self.params)
+            .map_err(|err| SolveError::Input(year, day, err))
+    }
+
+    // Returns the timings of the day, if it was solved
+    pub fn print_answers(&self, year: u16, day: u8, input: &str) -> Option<Timings> {
+        match self.run(year, day, input) {
+            Ok(run) => {
+                match run.part2 {
+                    Answer::Nothing => println!("Day {} / {}: {}", day, year, run.part1),
+                    _ => println!("Day {} / {}: {} and {}", day, year, run.part1, run.part2),
+                }
+
+                Some(run.timings)
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{unsolved, Answer, Day, Param};
+
+    fn build_registry() -> Registry {
+        let mut registry = Registry::new();
+        registry.register(Day::new(2020, 2, |_| Ok(22.into()), unsolved));
+        registry.register(Day::new(2015, 1, |_| Ok(11.into()), |_| Ok(12.into())));
+        registry.register(Day::new(2020, 1, |_| Ok(21.into()), unsolved));
+
+        registry
+    }
+
+    #[test]
+    fn test_empty_registry() {
+        let registry = Registry::new();
+        assert!(registry.is_empty());
+        assert!(registry.get(2015, 1).is_none());
+        assert!(registry.years().is_empty());
+    }
+
+    #[test]
+    fn test_get() {
+        let registry = build_registry();
+        assert_eq!(registry.len(), 3);
+
+        let solution = registry.get(2015, 1).unwrap();
+        assert_eq!(solution.part1("", &Params::new()), Ok(Answer::Signed(11)));
+        assert_eq!(solution.part2("", &Params::new()), Ok(Answer::Signed(12)));
+
+        assert!(registry.get(2015, 2).is_none());
+    }
+
+    #[test]
+    fn test_register_replaces() {
+        let mut registry = build_registry();
+        registry.register(Day::new(2015, 1, |_| Ok(0.into()), unsolved));
+
+        assert_eq!(registry.len(), 3);
+        assert_eq!(
+            registry.get(2015, 1).unwrap().part1("", &Params::new()),
+            Ok(Answer::Signed(0))
+        );
+    }
+
+    #[test]
+    fn test_iter_sorted() {
+        let registry = build_registry();
+        let keys: Vec<(u16, u8)> = registry.iter().map(|s| (s.year(), s.day())).collect();
+
+        assert_eq!(keys, vec![(2015, 1), (2020, 1), (2020, 2)]);
+    }
+
+    #[test]
+    fn test_days_and_years() {
+        let registry = build_registry();
+
+        assert_eq!(registry.years(), vec![2015, 2020]);
+        assert_eq!(registry.days(2020).count(), 2);
+        assert_eq!(registry.days(2016).count(), 0);
+    }
+
+    #[test]
+    fn test_solve() {
+        let registry = build_registry();
+
+        assert_eq!(
+            registry.solve(2015, 1, ""),
+            Ok((Answer::Signed(11), Answer::Signed(12)))
+        );
+        assert_eq!(
+            registry.solve(2020, 2, ""),
+            Ok((Answer::Signed(22), Answer::Nothing))
+        );
+        assert_eq!(
+            registry.solve(2020, 3, ""),
+            Err(SolveError::UnknownDay(2020, 3))
+        );
+        assert_eq!(
+            registry.solve(2016, 1, ""),
+            Err(SolveError::UnknownYear(2016))
+        );
+    }
+
+    #[test]
+    fn test_solve_trims_input() {
+        let mut registry = Registry::new();
+        registry.register(Day::new(2015, 1, |input| Ok(input.into()), unsolved));
+
+        assert_eq!(
+            registry.solve(2015, 1, "  abc\n"),
+            Ok((Answer::from("abc"), Answer::Nothing))
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let registry = build_registry();
+
+        let run = registry.run(2015, 1, "").unwrap();
+        assert_eq!(run.part1, Answer::Signed(11));
+        assert_eq!(run.part2, Answer::Signed(12));
+        assert!(registry.run(2020, 3, "").is_err());
+    }
+
+    #[test]
+    fn test_params() {
+        const SUM: Param = Param::new("sum", "2020", "the sum of the entries");
+
+        let mut registry = build_registry();
+        registry.register(Day::with_params(
+            2020,
+            3,
+            &[SUM],
+            |_, params| params.get::<u32>(&SUM).map(Into::into),
+            |input, _| unsolved(input),
+        ));
+        assert!(registry.params().is_empty());
+        assert_eq!(
+            registry.solve(2020, 3, ""),
+            Ok((Answer::Unsigned(2020), Answer::Nothing))
+        );
+
+        let mut params = Params::new();
+        params.set("sum", "5");
+        registry.set_params(params);
+        assert_eq!(registry.params().names().collect::<Vec<_>>(), vec!["sum"]);
+        assert_eq!(
+            registry.solve(2020, 3, ""),
+            Ok((Answer::Unsigned(5), Answer::Nothing))
+        );
+    }
+
+    #[test]
+    fn test_check_params() {
+        const SUM: Param = Param::new("sum", "2020", "the sum of the entries");
+        const COUNT: Param = Param::new("count", "2", "the count of the entries");
+
+        let mut registry = build_registry();
+        registry.register(Day::with_params(
+            2020,
+            3,
+            &[SUM, COUNT],
+            |_, _| Ok(Answer::Nothing),
+            |_, _| Ok(Answer::Nothing),
+        ));
+
+        let mut params = Params::new();
+        params.set("sum", "5");
+        assert_eq!(
+            registry.check_params(&[(2020, 1), (2020, 3)], &params),
+            Ok(())
+        );
+        assert_eq!(
+            registry.check_params(&[(2020, 1)], &params),
+            Err(String::from(
+                "The selected days have no parameters, sum is unknown"
+            ))
+        );
+
+        params.set("summ", "5");
+        assert_eq!(
+            registry.check_params(&[(2020, 3)], &params),
+            Err(String::from(
+                "The selected days have no parameter summ, expected one of: count, sum"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_print_answers() {
+        let registry = build_registry();
+        assert!(registry.print_answers(2015, 1, "").is_some());
+        assert!(registry.print_answers(2020, 1, "").is_some());
+        assert!(registry.print_answers(2020, 3, "").is_none());
+    }
+}
@@ -0,0 +1,260 @@
+use crate::timing::timed;
+use crate::{Answer, Param, Params, ParseError, Timings};
+
+pub trait Solution: Send + Sync {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    // The parameters, which can be overridden, see Params
+    fn params(&self) -> &[Param] {
+        &[]
+    }
+
+    fn part1(&self, input: &str, params: &Params) -> Result<Answer, ParseError>;
+
+    fn part2(&self, input: &str, params: &Params) -> Result<Answer, ParseError>;
+
+    // Solves both parts and measures each step
+    fn run(&self, input: &str, params: &Params) -> Result<Run, ParseError> {
+        let (part1, part1_time) = timed(|| self.part1(input, params));
+        let (part2, part2_time) = timed(|| self.part2(input, params));
+
+        Ok(Run {
+            part1: part1?,
+            part2: part2?,
+            timings: Timings {
+                part1: part1_time,
+                part2: part2_time,
+                ..Timings::default()
+            },
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub part1: Answer,
+    pub part2: Answer,
+    pub timings: Timings,
+}
+
+// Most parts don't have any parameters
+enum Part<I: ?Sized> {
+    Plain(fn(&I) -> Result<Answer, ParseError>),
+    WithParams(fn(&I, &Params) -> Result<Answer, ParseError>),
+}
+
+impl<I: ?Sized> Part<I> {
+    fn solve(&self, input: &I, params: &Params) -> Result<Answer, ParseError> {
+        match self {
+            Part::Plain(part) => part(input),
+            Part::WithParams(part) => part(input, params),
+        }
+    }
+}
+
+// A solution assembled from two plain functions, so a day module doesn't need its own type.
+pub struct Day {
+    year: u16,
+    day: u8,
+    params: &'static [Param],
+    part1: Part<str>,
+    part2: Part<str>,
+}
+
+impl Day {
+    pub fn new(
+        year: u16,
+        day: u8,
+        part1: fn(&str) -> Result<Answer, ParseError>,
+        part2: fn(&str) -> Result<Answer, ParseError>,
+    ) -> Day {
+        Day {
+            year,
+            day,
+            params: &[],
+            part1: Part::Plain(part1),
+            part2: Part::Plain(part2),
+        }
+    }
+
+    pub fn with_params(
+        year: u16,
+        day: u8,
+        params: &'static [Param],
+        part1: fn(&str, &Params) -> Result<Answer, ParseError>,
+        part2: fn(&str, &Params) -> Result<Answer, ParseError>,
+    ) -> Day {
+        Day {
+            year,
+            day,
+            params,
+            part1: Part::WithParams(part1),
+            part2: Part::WithParams(part2),
+        }
+    }
+}
+
+impl Solution for Day {
+    fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn params(&self) -> &[Param] {
+        self.params
+    }
+
+    fn part1(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        self.part1.solve(input, params)
+    }
+
+    fn part2(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        self.part2.solve(input, params)
+    }
+}
+
+// A solution, which parses the input once for both parts, so the parsing can be timed on its own
+pub struct ParsedDay<T> {
+    year: u16,
+    day: u8,
+    params: &'static [Param],
+    parse: fn(&str) -> Result<T, ParseError>,
+    part1: Part<T>,
+    part2: Part<T>,
+}
+
+impl<T> ParsedDay<T> {
+    pub fn new(
+        year: u16,
+        day: u8,
+        parse: fn(&str) -> Result<T, ParseError>,
+        part1: fn(&T) -> Result<Answer, ParseError>,
+        part2: fn(&T) -> Result<Answer, ParseError>,
+    ) -> ParsedDay<T> {
+        ParsedDay {
+            year,
+            day,
+            params: &[],
+            parse,
+            part1: Part::Plain(part1),
+            part2: Part::Plain(part2),
+        }
+    }
+
+    pub fn with_params(
+        year: u16,
+        day: u8,
+        params: &'static [Param],
+        parse: fn(&str) -> Result<T, ParseError>,
+        part1: fn(&T, &Params) -> Result<Answer, ParseError>,
+        part2: fn(&T, &Params) -> Result<Answer, ParseError>,
+    ) -> ParsedDay<T> {
+        ParsedDay {
+            year,
+            day,
+            params,
+            parse,
+            part1: Part::WithParams(part1),
+            part2: Part::WithParams(part2),
+        }
+    }
+}
+
+impl<T> Solution for ParsedDay<T> {
+    fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn params(&self) -> &[Param] {
+        self.params
+    }
+
+    fn part1(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        self.part1.solve(&(self.parse)(input)?, params)
+    }
+
+    fn part2(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        self.part2.solve(&(self.parse)(input)?, params)
+    }
+
+    fn run(&self, input: &str, params: &Params) -> Result<Run, ParseError> {
+        let (parsed, parse_time) = timed(|| (self.parse)(input));
+        let parsed = parsed?;
+        let (part1, part1_time) = timed(|| self.part1.solve(&parsed, params));
+        let (part2, part2_time) = timed(|| self.part2.solve(&parsed, params));
+
+        Ok(Run {
+            part1: part1?,
+            part2: part2?,
+            timings: Timings {
+                parse: Some(parse_time),
+                part1: part1_time,
+                part2: part2_time,
+            },
+        })
+    }
+}
+
+pub fn unsolved(_input: &str) -> Result<Answer, ParseError> {
+    Ok(Answer::Nothing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advparse::parse_numbers;
+
+    #[test]
+    fn test_day() {
+        let day = Day::new(2015, 1, |input| Ok(input.len().into()), unsolved);
+
+        assert_eq!(day.year(), 2015);
+        assert_eq!(day.day(), 1);
+        assert_eq!(day.part1("(((", &Params::new()), Ok(Answer::Unsigned(3)));
+        assert_eq!(day.part2("(((", &Params::new()), Ok(Answer::Nothing));
+
+        let run = day.run("(((", &Params::new()).unwrap();
+        assert_eq!(run.part1, Answer::Unsigned(3));
+        assert_eq!(run.part2, Answer::Nothing);
+        assert_eq!(run.timings.parse, None);
+    }
+
+    #[test]
+    fn test_parsed_day() {
+        let day = ParsedDay::new(
+            2020,
+            1,
+            parse_numbers::<u32>,
+            |numbers| Ok(numbers.len().into()),
+            |numbers| Ok(numbers.iter().sum::<u32>().into()),
+        );
+
+        assert_eq!(day.year(), 2020);
+        assert_eq!(day.day(), 1);
+        assert_eq!(
+            day.part1("1721\n979", &Params::new()),
+            Ok(Answer::Unsigned(2))
+        );
+        assert_eq!(
+            day.part2("1721\n979", &Params::new()),
+            Ok(Answer::Unsigned(2700))
+        );
+
+        let run = day.run("1721\n979", &Params::new()).unwrap();
+        assert_eq!(run.part1, Answer::Unsigned(2));
+        assert_eq!(run.part2, Answer::Unsigned(2700));
+
+        let err = ParseError::new(2, 1, "97x9", "expected a number");
+        assert_eq!(day.part1("1721\n97x9", &Params::new()), Err(err.clone()));
+        assert_eq!(day.run("1721\n97x9", &Params::new()), Err(err));
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// Parse is None for the days, which parse the input inside each part
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Option<Duration>,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.parse.unwrap_or_default() + self.part1 + self.part2
+    }
+}
+
+impl std::ops::AddAssign for Timings {
+    fn add_assign(&mut self, other: Timings) {
+        self.parse = match (self.parse, other.parse) {
+            (None, None) => None,
+            (parse, other) => Some(parse.unwrap_or_default() + other.unwrap_or_default()),
+        };
+        self.part1 += other.part1;
+        self.part2 += other.part2;
+    }
+}
+
+pub fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+
+    (result, start.elapsed())
+}
+
+// Human-readable duration: 850 ns, 12.3 µs, 4.56 ms, 2.51 s
+pub struct Elapsed(pub Duration);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.0.as_nanos();
+        let text = if nanos < 1_000 {
+            format!("{} ns", nanos)
+        } else if nanos < 1_000_000 {
+            format!("{:.1} µs", nanos as f64 / 1e3)
+        } else if nanos < 1_000_000_000 {
+            format!("{:.2} ms", nanos as f64 / 1e6)
+        } else {
+            format!("{:.2} s", self.0.as_secs_f64())
+        };
+
+        // Padding, so the elapsed times can be aligned in a table
+        f.pad(text.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total() {
+        let mut timings = Timings {
+            parse: Some(Duration::from_millis(1)),
+            part1: Duration::from_millis(2),
+            part2: Duration::from_millis(3),
+        };
+        assert_eq!(timings.total(), Duration::from_millis(6));
+
+        timings += timings;
+        assert_eq!(timings.part2, Duration::from_millis(6));
+        assert_eq!(timings.total(), Duration::from_millis(12));
+
+        let mut unparsed = Timings {
+            part1: Duration::from_millis(2),
+            ..Timings::default()
+        };
+        assert_eq!(unparsed.total(), Duration::from_millis(2));
+        unparsed += unparsed;
+        assert_eq!(unparsed.parse, None);
+        unparsed += timings;
+        assert_eq!(unparsed.parse, Some(Duration::from_millis(2)));
+    }
+
+    #[test]
+    fn test_timed() {
+        let (result, elapsed) = timed(|| 2 + 2);
+        assert_eq!(result, 4);
+        assert!(elapsed < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_elapsed() {
+        assert_eq!(Elapsed(Duration::from_nanos(850)).to_string(), "850 ns");
+        assert_eq!(Elapsed(Duration::from_nanos(12_345)).to_string(), "12.3 µs");
+        assert_eq!(Elapsed(Duration::from_micros(4_560)).to_string(), "4.56 ms");
+        assert_eq!(Elapsed(Duration::from_millis(2_512)).to_string(), "2.51 s");
+        assert_eq!(format!("{:>8}", Elapsed(Duration::ZERO)), "    0 ns");
+    }
+}
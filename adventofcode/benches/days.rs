@@ -0,0 +1,57 @@
+// Benchmarks every registered day, which has an input in inputs/<year>/day<NN>.txt
+// (or in $ADVENT_INPUTS/<year>/day<NN>.txt), e.g.:
+// cargo bench -p adventofcode -- 2015/day04
+use advcore::{Params, Registry};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+fn inputs_root() -> PathBuf {
+    env::var_os("ADVENT_INPUTS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs"))
+}
+
+fn read_input(root: &Path, year: u16, day: u8) -> Option<String> {
+    let path = root
+        .join(year.to_string())
+        .join(format!("day{:02}.txt", day));
+    fs::read_to_string(path).ok()
+}
+
+fn bench_days(c: &mut Criterion) {
+    let mut registry = Registry::new();
+    adv2015::register(&mut registry);
+    adv2020::register(&mut registry);
+
+    let root = inputs_root();
+    let params = Params::new();
+
+    for solution in registry.iter() {
+        let (year, day) = (solution.year(), solution.day());
+        let input = match read_input(&root, year, day) {
+            Some(input) => input,
+            None => continue,
+        };
+        let input = input.trim();
+
+        let mut group = c.benchmark_group(format!("{}/day{:02}", year, day));
+        // A few days take seconds per iteration
+        group.sample_size(10);
+        group.measurement_time(Duration::from_secs(5));
+
+        group.bench_function("part1", |b| {
+            b.iter(|| solution.part1(black_box(input), &params))
+        });
+        group.bench_function("part2", |b| {
+            b.iter(|| solution.part2(black_box(input), &params))
+        });
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
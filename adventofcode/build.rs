@@ -0,0 +1,53 @@
+// Generates a test for every file of the example catalogue, e.g. examples/2015/day18.toml
+// becomes examples_2015_day18, see examples.rs
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples");
+    println!("cargo:rerun-if-changed={}", root.display());
+
+    let mut days = Vec::new();
+    for year_dir in fs::read_dir(&root).into_iter().flatten().flatten() {
+        let year = match year_dir
+            .file_name()
+            .to_str()
+            .and_then(|year| year.parse::<u16>().ok())
+        {
+            Some(year) => year,
+            None => continue,
+        };
+
+        for file in fs::read_dir(year_dir.path())
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            let day = file.file_name().to_str().and_then(|name| {
+                name.strip_prefix("day")?
+                    .strip_suffix(".toml")?
+                    .parse::<u8>()
+                    .ok()
+            });
+
+            if let Some(day) = day {
+                days.push((year, day));
+            }
+        }
+    }
+    days.sort_unstable();
+
+    let tests: String = days
+        .iter()
+        .map(|(year, day)| {
+            format!(
+                "#[test]\nfn examples_{}_day{:02}() {{\n    super::check_file({}, {});\n}}\n",
+                year, day, year, day
+            )
+        })
+        .collect();
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
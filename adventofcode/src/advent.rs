@@ -1,10 +1,23 @@
 use core::fmt;
+use reqwest::blocking::Response;
 use reqwest::header::{HeaderMap, HeaderValue, InvalidHeaderValue};
+use reqwest::StatusCode;
 use std::fmt::Display;
 
+pub const BASE_URL: &str = "https://adventofcode.com";
+
 pub enum HttpError {
     WrongHeader(InvalidHeaderValue),
     WrongClient(reqwest::Error),
+
+    // The session cookie is missing, expired or belongs to nobody
+    Unauthorized,
+
+    // The puzzle is not unlocked yet (or doesn't exist at all)
+    NotReleased,
+    RateLimited,
+    ServerError(u16),
+    UnexpectedStatus(u16),
 }
 
 impl From<InvalidHeaderValue> for HttpError {
@@ -23,12 +36,33 @@ impl Display for HttpError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             HttpError::WrongHeader(_) => f.write_str("Header doesn't exist"),
-            HttpError::WrongClient(_) => f.write_str("Couldn't send the HTTP request"),
+            HttpError::WrongClient(err) => write!(f, "Couldn't send the HTTP request: {}", err),
+            HttpError::Unauthorized => {
+                f.write_str("The session is not valid, update ADVENT_SESSION and try again")
+            }
+            HttpError::NotReleased => f.write_str("The puzzle is not released yet"),
+            HttpError::RateLimited => {
+                f.write_str("Too many requests, wait a little bit and try again")
+            }
+            HttpError::ServerError(status) => {
+                write!(f, "adventofcode.com has failed with the status {}", status)
+            }
+            HttpError::UnexpectedStatus(status) => {
+                write!(f, "Unexpected response with the status {}", status)
+            }
         }
     }
 }
 
-pub fn get_input(day: u8, year: u16, session: &str) -> Result<String, HttpError> {
+pub fn get_input(base_url: &str, day: u8, year: u16, session: &str) -> Result<String, HttpError> {
+    let response = build_client(session)?
+        .get(format!("{}/{}/day/{}/input", base_url, year, day).as_str())
+        .send()?;
+
+    check_response(response)
+}
+
+pub fn build_client(session: &str) -> Result<reqwest::blocking::Client, HttpError> {
     let cookie = format!("session={}", session);
     let header_value = HeaderValue::from_str(cookie.as_str())?;
     let mut headers = HeaderMap::new();
@@ -38,28 +72,102 @@ pub fn get_input(day: u8, year: u16, session: &str) -> Result<String, HttpError>
         .default_headers(headers)
         .build()?;
 
-    let body = client
-        .get(format!("https://adventofcode.com/{}/day/{}/input", year, day).as_str())
-        .send()?
-        .text()?;
+    Ok(client)
+}
+
+// Returns the body of a successful response, otherwise explains, why it has failed
+pub fn check_response(response: Response) -> Result<String, HttpError> {
+    let status = response.status();
+    let body = response.text()?;
 
-    Ok(body)
+    match status {
+        _ if body.contains("Please log in") => Err(HttpError::Unauthorized),
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(HttpError::Unauthorized)
+        }
+        StatusCode::NOT_FOUND => Err(HttpError::NotReleased),
+        _ if body.contains("before it unlocks") => Err(HttpError::NotReleased),
+        StatusCode::TOO_MANY_REQUESTS => Err(HttpError::RateLimited),
+        _ if status.is_server_error() => Err(HttpError::ServerError(status.as_u16())),
+        _ if !status.is_success() => Err(HttpError::UnexpectedStatus(status.as_u16())),
+        _ => Ok(body),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stub_server::StubServer;
+
+    fn get_stub_input(status: &str, body: &str) -> (Result<String, HttpError>, String) {
+        let server = StubServer::start(status, body);
+        let input = get_input(server.url().as_str(), 1, 2015, "5361");
+
+        (input, server.request())
+    }
 
     #[test]
     fn test_get_input() {
-        let input = get_input(1, 2015, "");
-        assert!(input.is_ok(), "The input response is not successful");
+        let (input, request) = get_stub_input("200 OK", "(()))\n");
+        assert_eq!(input.ok(), Some(String::from("(()))\n")));
 
-        let response = input.unwrap_or(String::from("fail"));
         assert!(
-            response.contains("Please log in"),
-            "The response is unexpected: {}",
-            response
+            request.starts_with("GET /2015/day/1/input "),
+            "The request is unexpected: {}",
+            request
+        );
+        assert!(
+            request.to_lowercase().contains("cookie: session=5361"),
+            "The cookie is missing: {}",
+            request
+        );
+    }
+
+    #[test]
+    fn test_get_input_unauthorized() {
+        let (input, _) = get_stub_input(
+            "400 Bad Request",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+        );
+        assert!(matches!(input, Err(HttpError::Unauthorized)));
+
+        let (input, _) = get_stub_input("200 OK", "Please log in");
+        assert!(matches!(input, Err(HttpError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_get_input_not_released() {
+        let (input, _) = get_stub_input(
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        assert!(matches!(input, Err(HttpError::NotReleased)));
+    }
+
+    #[test]
+    fn test_get_input_rate_limited() {
+        let (input, _) = get_stub_input("429 Too Many Requests", "");
+        assert!(matches!(input, Err(HttpError::RateLimited)));
+    }
+
+    #[test]
+    fn test_get_input_server_error() {
+        let (input, _) = get_stub_input("500 Internal Server Error", "");
+        assert!(matches!(input, Err(HttpError::ServerError(500))));
+
+        let (input, _) = get_stub_input("418 I'm a teapot", "");
+        assert!(matches!(input, Err(HttpError::UnexpectedStatus(418))));
+    }
+
+    #[test]
+    fn test_http_error_display() {
+        assert_eq!(
+            HttpError::ServerError(503).to_string(),
+            "adventofcode.com has failed with the status 503"
+        );
+        assert_eq!(
+            HttpError::NotReleased.to_string(),
+            "The puzzle is not released yet"
         );
     }
 }
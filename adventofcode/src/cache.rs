@@ -0,0 +1,136 @@
+use crate::advent;
+use crate::input;
+use crate::input::InputError;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CACHE_DIR_NAME: &str = "adventofcode";
+
+pub struct InputCache {
+    root: PathBuf,
+}
+
+impl InputCache {
+    pub fn new<P: Into<PathBuf>>(root: P) -> InputCache {
+        InputCache { root: root.into() }
+    }
+
+    // $XDG_CACHE_HOME/adventofcode, ~/.cache/adventofcode or %LOCALAPPDATA%\adventofcode
+    pub fn default_root() -> Option<PathBuf> {
+        let non_empty = |key: &str| env::var_os(key).filter(|value| !value.is_empty());
+
+        non_empty("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| non_empty("HOME").map(|home| Path::new(&home).join(".cache")))
+            .or_else(|| non_empty("LOCALAPPDATA").map(PathBuf::from))
+            .map(|cache| cache.join(CACHE_DIR_NAME))
+    }
+
+    pub fn path(&self, day: u8, year: u16) -> PathBuf {
+        input::day_path(&self.root, day, year)
+    }
+
+    pub fn read(&self, day: u8, year: u16) -> Option<String> {
+        fs::read_to_string(self.path(day, year)).ok()
+    }
+
+    pub fn write(&self, day: u8, year: u16, input: &str) -> io::Result<()> {
+        let path = self.path(day, year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, input)
+    }
+
+    // Reads the cached input, or downloads and caches it if it's missing or refresh is requested
+    pub fn get_input(
+        &self,
+        base_url: &str,
+        day: u8,
+        year: u16,
+        session: Option<&str>,
+        refresh: bool,
+    ) -> Result<String, InputError> {
+        if !refresh {
+            if let Some(input) = self.read(day, year) {
+                return Ok(input);
+            }
+        }
+
+        let session = session.ok_or(InputError::NoSession)?;
+        let input = advent::get_input(base_url, day, year, session)?;
+        self.write(day, year, input.as_str())?;
+
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    fn temp_cache(name: &str) -> InputCache {
+        let root = env::temp_dir().join(format!("advent-cache-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        InputCache::new(root)
+    }
+
+    #[test]
+    fn test_path() {
+        let cache = InputCache::new("cache");
+
+        assert_eq!(
+            cache.path(7, 2015),
+            Path::new("cache").join("2015").join("day07.txt")
+        );
+        assert_eq!(
+            cache.path(25, 2020),
+            Path::new("cache").join("2020").join("day25.txt")
+        );
+    }
+
+    #[test]
+    fn test_read_write() {
+        let cache = temp_cache("read-write");
+        assert!(cache.read(1, 2015).is_none());
+
+        cache.write(1, 2015, "(()").unwrap();
+        assert_eq!(cache.read(1, 2015), Some(String::from("(()")));
+        assert!(cache.read(2, 2015).is_none());
+
+        fs::remove_dir_all(&cache.root).unwrap();
+    }
+
+    #[test]
+    fn test_get_input_cached() {
+        let cache = temp_cache("cached");
+        cache.write(3, 2020, "..#").unwrap();
+
+        let input = cache.get_input(advent::BASE_URL, 3, 2020, None, false);
+        assert_eq!(input.ok(), Some(String::from("..#")));
+
+        fs::remove_dir_all(&cache.root).unwrap();
+    }
+
+    #[test]
+    fn test_get_input_without_session() {
+        let cache = temp_cache("no-session");
+        cache.write(3, 2020, "..#").unwrap();
+
+        assert!(matches!(
+            cache.get_input(advent::BASE_URL, 4, 2020, None, false),
+            Err(InputError::NoSession)
+        ));
+        assert!(matches!(
+            cache.get_input(advent::BASE_URL, 3, 2020, None, true),
+            Err(InputError::NoSession)
+        ));
+
+        fs::remove_dir_all(&cache.root).unwrap();
+    }
+}
@@ -0,0 +1,352 @@
+use advcore::{Params, Registry};
+use core::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+// The examples of the puzzles, e.g. examples/2015/day18.toml
+pub const EXAMPLES_DIR: &str = "examples";
+
+pub enum ExampleError {
+    Io(PathBuf, io::Error),
+    Format(PathBuf, String),
+}
+
+impl Display for ExampleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExampleError::Io(path, err) => {
+                write!(f, "Couldn't read the examples {}: {}", path.display(), err)
+            }
+            ExampleError::Format(path, err) => {
+                write!(f, "The examples {} are malformed: {}", path.display(), err)
+            }
+        }
+    }
+}
+
+// An input from the puzzle text with its answers; a part without an answer isn't checked:
+// [[example]]
+// name = "lights"
+// input = """..."""
+// params = { size = "6", steps = "4" }
+// part1 = "4"
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub params: Params,
+    pub answers: Vec<(u8, String)>,
+}
+
+// The answer of a part of an example, or why it couldn't be solved
+pub struct Checked {
+    pub name: String,
+    pub part: u8,
+    pub expected: String,
+    pub actual: Result<String, String>,
+}
+
+impl Checked {
+    pub fn passed(&self) -> bool {
+        self.actual.as_deref() == Ok(self.expected.as_str())
+    }
+}
+
+impl Display for Checked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, part {}: ", self.name, self.part)?;
+
+        match &self.actual {
+            Ok(answer) if self.passed() => write!(f, "{} ok", answer),
+            Ok(answer) => write!(f, "{} FAILED, expected {}", answer, self.expected),
+            Err(err) => write!(f, "FAILED, expected {}: {}", self.expected, err),
+        }
+    }
+}
+
+pub fn path(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(year.to_string())
+        .join(format!("day{:02}.toml", day))
+}
+
+// A day without a file has no examples
+pub fn load(root: &Path, year: u16, day: u8) -> Result<Vec<Example>, ExampleError> {
+    let path = path(root, year, day);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(ExampleError::Io(path, err)),
+    };
+
+    parse_examples(content.as_str()).map_err(|err| ExampleError::Format(path, err))
+}
+
+// The parameters of an example must be declared by the day
+pub fn check(
+    registry: &Registry,
+    year: u16,
+    day: u8,
+    examples: &[Example],
+) -> Result<Vec<Checked>, String> {
+    let solution = registry
+        .get(year, day)
+        .ok_or_else(|| format!("Day {} / {} is not solved yet", day, year))?;
+    let mut checked = Vec::new();
+
+    for example in examples {
+        if let Some(name) = example
+            .params
+            .names()
+            .find(|&name| !solution.params().iter().any(|param| param.name == name))
+        {
+            return Err(format!(
+                "The example {} has the parameter {}, which day {} / {} doesn't have",
+                example.name, name, day, year
+            ));
+        }
+
+        // Trim is mandatory for one-line inputs, see Registry::run
+        let input = example.input.trim();
+
+        for (part, expected) in &example.answers {
+            let answer = match part {
+                1 => solution.part1(input, &example.params),
+                _ => solution.part2(input, &example.params),
+            };
+
+            checked.push(Checked {
+                name: example.name.clone(),
+                part: *part,
+                expected: expected.clone(),
+                actual: answer
+                    .map(|answer| answer.to_string())
+                    .map_err(|err| err.to_string()),
+            });
+        }
+    }
+
+    Ok(checked)
+}
+
+fn parse_examples(content: &str) -> Result<Vec<Example>, String> {
+    let table = content.parse::<Table>().map_err(|err| err.to_string())?;
+    let mut examples = Vec::new();
+
+    for (key, value) in &table {
+        if key != "example" {
+            return Err(format!("unexpected key {}", key));
+        }
+
+        let items = value
+            .as_array()
+            .ok_or_else(|| String::from("example is not an array of tables"))?;
+        for (index, item) in items.iter().enumerate() {
+            let item = item
+                .as_table()
+                .ok_or_else(|| format!("example {} is not a table", index + 1))?;
+            examples.push(parse_example(index, item)?);
+        }
+    }
+
+    Ok(examples)
+}
+
+fn parse_example(index: usize, table: &Table) -> Result<Example, String> {
+    let name = match table.get("name") {
+        Some(name) => string(name).ok_or_else(|| format!("example {}.name", index + 1))?,
+        None => format!("example {}", index + 1),
+    };
+    let mut example = Example {
+        input: String::new(),
+        params: Params::new(),
+        answers: Vec::new(),
+        name,
+    };
+
+    for (key, value) in table {
+        match key.as_str() {
+            "name" => (),
+            "input" => {
+                example.input = string(value)
+                    .ok_or_else(|| format!("{}.input is not a string", example.name))?;
+            }
+            "params" => {
+                let params = value
+                    .as_table()
+                    .ok_or_else(|| format!("{}.params is not a table", example.name))?;
+
+                for (param, value) in params {
+                    let value = string(value).ok_or_else(|| {
+                        format!("{}.params.{} is not a string", example.name, param)
+                    })?;
+                    example.params.set(param, value.as_str());
+                }
+            }
+            "part1" | "part2" => {
+                let answer = string(value)
+                    .ok_or_else(|| format!("{}.{} is not a string", example.name, key))?;
+                example.answers.push((key[4..].parse().unwrap(), answer));
+            }
+            _ => return Err(format!("unexpected key {}.{}", example.name, key)),
+        }
+    }
+
+    if example.answers.is_empty() {
+        return Err(format!("{} has no answers", example.name));
+    }
+
+    Ok(example)
+}
+
+// The answers and parameters may be written as numbers too
+fn string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(value) => Some(value.clone()),
+        Value::Integer(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    // The examples of every day, which has a file in examples/<year>,
+    // e.g. the test examples_2015_day18
+    mod catalogue {
+        include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+    }
+
+    fn check_file(year: u16, day: u8) {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(EXAMPLES_DIR);
+        let examples = load(&root, year, day).unwrap_or_else(|e| panic!("{}", e));
+        let registry = adv_registry();
+
+        let checked = check(&registry, year, day, &examples).unwrap_or_else(|e| panic!("{}", e));
+        let failed: Vec<String> = checked
+            .iter()
+            .filter(|checked| !checked.passed())
+            .map(|checked| checked.to_string())
+            .collect();
+
+        assert!(!checked.is_empty());
+        assert!(failed.is_empty(), "{}", failed.join("\n"));
+    }
+
+    fn adv_registry() -> Registry {
+        let mut registry = Registry::new();
+        adv2015::register(&mut registry);
+        adv2020::register(&mut registry);
+
+        registry
+    }
+
+    #[test]
+    fn test_parse_examples() {
+        let examples = parse_examples(
+            r#"[[example]]
+               name = "short"
+               input = "16\n10"
+               part1 = "35"
+               part2 = 8
+
+               [[example]]
+               input = "1"
+               params = { preamble = 5, size = "6" }
+               part2 = "19208""#,
+        )
+        .unwrap();
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "short");
+        assert_eq!(examples[0].input, "16\n10");
+        assert_eq!(
+            examples[0].answers,
+            vec![(1, String::from("35")), (2, String::from("8"))]
+        );
+        assert_eq!(examples[1].name, "example 2");
+        assert_eq!(examples[1].answers, vec![(2, String::from("19208"))]);
+
+        let mut params = Params::new();
+        params.set("preamble", "5");
+        params.set("size", "6");
+        assert_eq!(examples[1].params, params);
+    }
+
+    #[test]
+    fn test_parse_malformed_examples() {
+        assert!(parse_examples("[example]\ninput = \"1\"\npart1 = \"1\"").is_err());
+        assert!(parse_examples("[[sample]]\ninput = \"1\"\npart1 = \"1\"").is_err());
+        assert!(parse_examples("[[example]]\ninput = \"1\"").is_err());
+        assert!(parse_examples("[[example]]\ninput = 1.5\npart1 = \"1\"").is_err());
+        assert!(parse_examples("[[example]]\npart3 = \"1\"").is_err());
+        assert!(parse_examples("[[example]]\nparams = 5\npart1 = \"1\"").is_err());
+        assert!(parse_examples("[[example").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let registry = adv_registry();
+        let examples = parse_examples(
+            r#"[[example]]
+               name = "right"
+               input = "(())"
+               part1 = "0"
+
+               [[example]]
+               name = "wrong"
+               input = "))("
+               part1 = "1"
+               part2 = "2""#,
+        )
+        .unwrap();
+
+        let checked = check(&registry, 2015, 1, &examples).unwrap();
+        assert_eq!(checked.len(), 3);
+        assert!(checked[0].passed());
+        assert_eq!(checked[0].to_string(), "right, part 1: 0 ok");
+        assert_eq!(
+            checked[1].to_string(),
+            "wrong, part 1: -1 FAILED, expected 1"
+        );
+        assert_eq!(
+            checked[2].to_string(),
+            "wrong, part 2: 1 FAILED, expected 2"
+        );
+
+        assert!(check(&registry, 2020, 25, &examples).is_err());
+
+        let examples =
+            parse_examples("[[example]]\ninput = \"(\"\nparams = { size = \"6\" }\npart1 = \"1\"")
+                .unwrap();
+        assert!(check(&registry, 2015, 1, &examples).is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        let root = env::temp_dir().join(format!("advent-examples-{}", process::id()));
+        assert!(load(&root, 2015, 1).ok().unwrap().is_empty());
+
+        fs::create_dir_all(root.join("2015")).unwrap();
+        fs::write(
+            path(&root, 2015, 1),
+            "[[example]]\ninput = \"(\"\npart1 = 1",
+        )
+        .unwrap();
+        assert_eq!(load(&root, 2015, 1).ok().unwrap().len(), 1);
+
+        fs::write(path(&root, 2015, 2), "[[example]]").unwrap();
+        assert!(matches!(
+            load(&root, 2015, 2),
+            Err(ExampleError::Format(_, _))
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,130 @@
+use crate::advent::HttpError;
+use core::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// Inputs placed by hand, e.g. inputs/2015/day07.txt
+pub const LOCAL_INPUTS_DIR: &str = "inputs";
+
+pub enum InputError {
+    NoSession,
+    Http(HttpError),
+    Cache(io::Error),
+    File(PathBuf, io::Error),
+    Stdin(io::Error),
+}
+
+impl From<HttpError> for InputError {
+    fn from(err: HttpError) -> InputError {
+        InputError::Http(err)
+    }
+}
+
+impl From<io::Error> for InputError {
+    fn from(err: io::Error) -> InputError {
+        InputError::Cache(err)
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NoSession => f.write_str(
+                "The input is neither local nor cached, and ADVENT_SESSION is not defined",
+            ),
+            InputError::Http(err) => err.fmt(f),
+            InputError::Cache(err) => write!(f, "Couldn't write to the cache: {}", err),
+            InputError::File(path, err) => {
+                write!(f, "Couldn't read the file {}: {}", path.display(), err)
+            }
+            InputError::Stdin(err) => write!(f, "Couldn't read the standard input: {}", err),
+        }
+    }
+}
+
+pub enum Source {
+    // --input <path>
+    File(PathBuf),
+
+    // --stdin
+    Stdin,
+
+    // inputs/<year>/day<NN>.txt, then the cache, then adventofcode.com
+    Calendar,
+}
+
+pub fn day_path(root: &Path, day: u8, year: u16) -> PathBuf {
+    root.join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+pub fn read_local(root: &Path, day: u8, year: u16) -> Option<String> {
+    fs::read_to_string(day_path(root, day, year)).ok()
+}
+
+pub fn read_file(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|err| InputError::File(path.to_path_buf(), err))
+}
+
+pub fn read_stdin() -> Result<String, InputError> {
+    read_from(io::stdin().lock())
+}
+
+fn read_from<R: Read>(mut reader: R) -> Result<String, InputError> {
+    let mut input = String::new();
+    reader
+        .read_to_string(&mut input)
+        .map_err(InputError::Stdin)?;
+
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn test_day_path() {
+        assert_eq!(
+            day_path(Path::new(LOCAL_INPUTS_DIR), 7, 2015),
+            Path::new("inputs").join("2015").join("day07.txt")
+        );
+    }
+
+    #[test]
+    fn test_read_local_and_file() {
+        let root = env::temp_dir().join(format!("advent-inputs-{}", process::id()));
+        let path = day_path(&root, 1, 2015);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "(()").unwrap();
+
+        assert_eq!(read_local(&root, 1, 2015), Some(String::from("(()")));
+        assert!(read_local(&root, 2, 2015).is_none());
+        assert_eq!(read_file(&path).ok(), Some(String::from("(()")));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        let result = read_file(Path::new("no/such/day99.txt"));
+
+        match result {
+            Err(err @ InputError::File(_, _)) => {
+                assert!(err.to_string().starts_with("Couldn't read the file"))
+            }
+            _ => panic!("Expected a file error"),
+        }
+    }
+
+    #[test]
+    fn test_read_from() {
+        let input = read_from("1721\n979\n".as_bytes());
+        assert_eq!(input.ok(), Some(String::from("1721\n979\n")));
+    }
+}
@@ -0,0 +1,223 @@
+use core::fmt;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+// Accepted answers, e.g. answers/2015.toml
+pub const ANSWERS_DIR: &str = "answers";
+
+pub enum LedgerError {
+    Io(PathBuf, io::Error),
+    Format(PathBuf, String),
+}
+
+impl Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Io(path, err) => {
+                write!(f, "Couldn't access the ledger {}: {}", path.display(), err)
+            }
+            LedgerError::Format(path, err) => {
+                write!(f, "The ledger {} is malformed: {}", path.display(), err)
+            }
+        }
+    }
+}
+
+// The answers of a single year:
+// [day01]
+// part1 = "280"
+// part2 = "1797"
+pub struct Ledger {
+    path: PathBuf,
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Same,
+    Changed(String),
+    NotRecorded,
+}
+
+impl Ledger {
+    pub fn path(root: &Path, year: u16) -> PathBuf {
+        root.join(format!("{}.toml", year))
+    }
+
+    // A missing file is an empty ledger
+    pub fn load(root: &Path, year: u16) -> Result<Ledger, LedgerError> {
+        let path = Ledger::path(root, year);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(LedgerError::Io(path, err)),
+        };
+
+        let answers = parse_answers(content.as_str())
+            .map_err(|err| LedgerError::Format(path.clone(), err))?;
+
+        Ok(Ledger { path, answers })
+    }
+
+    pub fn save(&self) -> Result<(), LedgerError> {
+        let write = || -> io::Result<()> {
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&self.path, self.to_string())
+        };
+
+        write().map_err(|err| LedgerError::Io(self.path.clone(), err))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    // Any part of the day has an answer
+    pub fn recorded(&self, day: u8) -> bool {
+        [1, 2].iter().any(|&part| self.get(day, part).is_some())
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: &str) {
+        self.answers.insert((day, part), String::from(answer));
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Check {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Check::Same,
+            Some(expected) => Check::Changed(String::from(expected)),
+            None => Check::NotRecorded,
+        }
+    }
+}
+
+impl Display for Ledger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut table = Table::new();
+        for ((day, part), answer) in &self.answers {
+            let day_table = table
+                .entry(format!("day{:02}", day))
+                .or_insert_with(|| Value::Table(Table::new()));
+
+            if let Value::Table(day_table) = day_table {
+                day_table.insert(format!("part{}", part), Value::String(answer.clone()));
+            }
+        }
+
+        table.fmt(f)
+    }
+}
+
+fn parse_answers(content: &str) -> Result<BTreeMap<(u8, u8), String>, String> {
+    let table = content.parse::<Table>().map_err(|err| err.to_string())?;
+    let mut answers = BTreeMap::new();
+
+    for (day_key, parts) in table {
+        let day = day_key
+            .strip_prefix("day")
+            .and_then(|day| day.parse::<u8>().ok())
+            .ok_or_else(|| format!("unexpected key {}", day_key))?;
+        let parts = parts
+            .as_table()
+            .ok_or_else(|| format!("{} is not a table", day_key))?;
+
+        for (part_key, answer) in parts {
+            let part = part_key
+                .strip_prefix("part")
+                .and_then(|part| part.parse::<u8>().ok())
+                .ok_or_else(|| format!("unexpected key {}.{}", day_key, part_key))?;
+            let answer = match answer {
+                Value::String(answer) => answer.clone(),
+                Value::Integer(answer) => answer.to_string(),
+                _ => return Err(format!("{}.{} is not a string", day_key, part_key)),
+            };
+
+            answers.insert((day, part), answer);
+        }
+    }
+
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers(
+            r#"[day01]
+               part1 = "280"
+               part2 = 1797
+
+               [day11]
+               part1 = "hxbxxyzz""#,
+        )
+        .ok()
+        .unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers[&(1, 1)], "280");
+        assert_eq!(answers[&(1, 2)], "1797");
+        assert_eq!(answers[&(11, 1)], "hxbxxyzz");
+    }
+
+    #[test]
+    fn test_parse_malformed_answers() {
+        assert!(parse_answers("[first]\npart1 = \"1\"").is_err());
+        assert!(parse_answers("[day01]\nfirst = \"1\"").is_err());
+        assert!(parse_answers("[day01]\npart1 = [1, 2]").is_err());
+        assert!(parse_answers("day01 = 1").is_err());
+        assert!(parse_answers("[day01").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let mut ledger = Ledger {
+            path: PathBuf::new(),
+            answers: BTreeMap::new(),
+        };
+        ledger.record(7, 1, "956");
+
+        assert_eq!(ledger.check(7, 1, "956"), Check::Same);
+        assert_eq!(
+            ledger.check(7, 1, "957"),
+            Check::Changed(String::from("956"))
+        );
+        assert_eq!(ledger.check(7, 2, "40149"), Check::NotRecorded);
+        assert!(ledger.recorded(7));
+        assert!(!ledger.recorded(8));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let root = env::temp_dir().join(format!("advent-answers-{}", process::id()));
+
+        let mut ledger = Ledger::load(&root, 2015).ok().unwrap();
+        assert!(ledger.get(1, 1).is_none());
+
+        ledger.record(11, 1, "hxbxxyzz");
+        ledger.record(1, 2, "1797");
+        ledger.record(1, 1, "280");
+        ledger.save().ok().unwrap();
+
+        let content = fs::read_to_string(Ledger::path(&root, 2015)).unwrap();
+        assert_eq!(
+            content,
+            "[day01]\npart1 = \"280\"\npart2 = \"1797\"\n\n[day11]\npart1 = \"hxbxxyzz\"\n"
+        );
+
+        let ledger = Ledger::load(&root, 2015).ok().unwrap();
+        assert_eq!(ledger.get(1, 1), Some("280"));
+        assert_eq!(ledger.get(11, 1), Some("hxbxxyzz"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,7 +1,35 @@
 mod advent;
+mod cache;
+mod examples;
+mod input;
+mod ledger;
+mod player;
+mod render;
+mod report;
+mod scaffold;
+mod selection;
+#[cfg(test)]
+mod stub_server;
+mod submit;
+mod timing;
 
-use clap::{Arg, Command};
+use advcore::{Params, Registry, SolveError};
+use advrender::Scene;
+use cache::InputCache;
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use input::{InputError, Source};
+use ledger::{Check, Ledger, LedgerError};
+use player::{Finish, Player};
+use render::{Animators, Loggers, RenderFailure, Renderers};
+use report::{Format, Record};
+use selection::Years;
 use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use submit::Verdict;
+use timing::TimingTable;
 
 fn main() {
     let matches = Command::new("Advent 2015, 2020!")
@@ -12,7 +40,8 @@ fn main() {
                 .short('d')
                 .long("day")
                 .required(false)
-                .help("A day of the advent"),
+                .value_parser(selection::parse_days)
+                .help("Days of the advent, e.g. 7 or 1-5,9 (default: all solved days)"),
         )
         .arg(
             Arg::new("year")
@@ -20,52 +49,672 @@ fn main() {
                 .long("year")
                 .required(false)
                 .default_value("2020")
-                .help("A year of the calendar"),
+                .value_parser(selection::parse_years)
+                .help("Years of the calendar, e.g. 2015, 2015,2020 or all"),
+        )
+        .arg(
+            Arg::new("cache")
+                .long("cache")
+                .required(false)
+                .global(true)
+                .value_parser(value_parser!(PathBuf))
+                .help("A directory for downloaded inputs (default: $XDG_CACHE_HOME/adventofcode)"),
+        )
+        .arg(
+            Arg::new("refresh")
+                .long("refresh")
+                .action(ArgAction::SetTrue)
+                .help("Download the inputs again, even if they are cached"),
+        )
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .required(false)
+                .value_parser(value_parser!(PathBuf))
+                .requires("day")
+                .conflicts_with("stdin")
+                .help("Solve the day using the input from the file"),
+        )
+        .arg(
+            Arg::new("stdin")
+                .long("stdin")
+                .action(ArgAction::SetTrue)
+                .requires("day")
+                .help("Solve the day using the input from the standard input"),
+        )
+        .arg(
+            Arg::new("time")
+                .long("time")
+                .action(ArgAction::SetTrue)
+                .help("Measure parsing and both parts, and print a summary table"),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .default_value("text")
+                .value_parser(
+                    PossibleValuesParser::new(["text", "json", "csv"])
+                        .try_map(|format| format.parse::<Format>()),
+                )
+                .help("The output of the answers: text, json or csv"),
+        )
+        .arg(
+            Arg::new("render")
+                .long("render")
+                .value_parser(value_parser!(PathBuf))
+                .help("Draw the grids of the days into the directory, e.g. 2015/day18/lights.gif"),
+        )
+        .arg(
+            Arg::new("render-format")
+                .long("render-format")
+                .default_value("png")
+                .requires("render")
+                .value_parser(
+                    PossibleValuesParser::new(["png", "ppm"])
+                        .try_map(|format| format.parse::<advrender::Format>()),
+                )
+                .help("The format of the single frames: png or ppm"),
+        )
+        .arg(
+            Arg::new("animate")
+                .long("animate")
+                .action(ArgAction::SetTrue)
+                // The commands are read from the standard input while playing
+                .conflicts_with_all(["format", "stdin"])
+                .help("Play the simulations of the days in the terminal"),
+        )
+        .arg(
+            Arg::new("fps")
+                .long("fps")
+                .default_value("10")
+                .requires("animate")
+                .value_parser(value_parser!(u32).range(1..=120))
+                .help("The frames per second of --animate, + and - change it while playing"),
+        )
+        .arg(
+            Arg::new("log")
+                .long("log")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("format")
+                .help("Print how the days find their answers, e.g. the rounds of 2020 day 22"),
+        )
+        .arg(
+            Arg::new("param")
+                .long("param")
+                .action(ArgAction::Append)
+                .value_parser(selection::parse_param)
+                .help("Override a constant of the puzzle, e.g. steps=4 (repeatable)"),
+        )
+        .arg(
+            Arg::new("base-url")
+                .long("base-url")
+                .global(true)
+                .default_value(advent::BASE_URL)
+                .help("The address of Advent of Code, e.g. a local stand-in"),
+        )
+        .subcommand(
+            Command::new("submit")
+                .about("Submits an answer for a part of the day")
+                .arg(
+                    Arg::new("day")
+                        .short('d')
+                        .long("day")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .help("A day of the advent"),
+                )
+                .arg(
+                    Arg::new("year")
+                        .short('y')
+                        .long("year")
+                        .required(true)
+                        .value_parser(value_parser!(u16))
+                        .help("A year of the calendar"),
+                )
+                .arg(
+                    Arg::new("part")
+                        .short('p')
+                        .long("part")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=2))
+                        .help("A part of the puzzle: 1 or 2"),
+                )
+                .arg(
+                    Arg::new("answer")
+                        .required(true)
+                        .help("The answer to submit"),
+                ),
+        )
+        .subcommand(
+            Command::new("examples")
+                .about("Solves the examples of the day and compares them with their answers")
+                .arg(
+                    Arg::new("day")
+                        .short('d')
+                        .long("day")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .help("A day of the advent"),
+                )
+                .arg(
+                    Arg::new("year")
+                        .short('y')
+                        .long("year")
+                        .default_value("2020")
+                        .value_parser(value_parser!(u16))
+                        .help("A year of the calendar"),
+                ),
+        )
+        .subcommand(
+            Command::new("new")
+                .about("Creates a day from a template and registers it, the year crate too")
+                .arg(
+                    Arg::new("day")
+                        .short('d')
+                        .long("day")
+                        .required(true)
+                        .value_parser(value_parser!(u8).range(1..=25))
+                        .help("A day of the advent"),
+                )
+                .arg(
+                    Arg::new("year")
+                        .short('y')
+                        .long("year")
+                        .required(true)
+                        .value_parser(value_parser!(u16).range(2015..=9999))
+                        .help("A year of the calendar"),
+                ),
+        )
+        .subcommand(
+            Command::new("verify")
+                .about("Solves the days again and compares the answers with the ledger")
+                .arg(
+                    Arg::new("year")
+                        .short('y')
+                        .long("year")
+                        .required(false)
+                        .value_parser(value_parser!(u16))
+                        .help("A year of the calendar (default: all years)"),
+                )
+                .arg(
+                    Arg::new("record")
+                        .long("record")
+                        .action(ArgAction::SetTrue)
+                        .help("Record the answers, which are not in the ledger yet"),
+                ),
         )
         .get_matches();
 
-    let day_num = matches.get_one::<u8>("day");
-    let year_num = matches.get_one::<u16>("year").unwrap();
+    let base_url = matches.get_one::<String>("base-url").unwrap();
+    let cache = InputCache::new(
+        matches
+            .get_one::<PathBuf>("cache")
+            .cloned()
+            .or_else(InputCache::default_root)
+            .unwrap_or_else(|| env::temp_dir().join("adventofcode")),
+    );
+
+    match matches.subcommand() {
+        Some(("submit", submit_matches)) => {
+            if !submit(submit_matches, base_url) {
+                process::exit(1);
+            }
+            return;
+        }
+        Some(("examples", examples_matches)) => {
+            if !run_examples(examples_matches) {
+                process::exit(1);
+            }
+            return;
+        }
+        Some(("new", new_matches)) => {
+            if !new_day(new_matches) {
+                process::exit(1);
+            }
+            return;
+        }
+        Some(("verify", verify_matches)) => {
+            if !verify(verify_matches, &cache, base_url) {
+                process::exit(1);
+            }
+            return;
+        }
+        _ => (),
+    }
+
+    let days = matches.get_one::<Vec<u8>>("day");
+
+    let refresh = matches.get_flag("refresh");
+    let time = matches.get_flag("time");
+    let format = *matches.get_one::<Format>("format").unwrap();
+    let render_dir = matches.get_one::<PathBuf>("render");
+    let render_format = *matches
+        .get_one::<advrender::Format>("render-format")
+        .unwrap();
+
+    let source = if let Some(path) = matches.get_one::<PathBuf>("input") {
+        Source::File(path.clone())
+    } else if matches.get_flag("stdin") {
+        Source::Stdin
+    } else {
+        Source::Calendar
+    };
+
+    let mut registry = build_registry();
+    let renderers = Renderers::new();
+    let animators = Animators::new();
+    let loggers = Loggers::new();
+    let log = matches.get_flag("log");
+    let mut player = matches.get_flag("animate").then(|| {
+        Player::new(
+            *matches.get_one::<u32>("fps").unwrap(),
+            player::stdin_controls(),
+        )
+    });
+
+    let years = match matches.get_one::<Years>("year").unwrap() {
+        Years::All => registry.years(),
+        Years::List(years) => years.clone(),
+    };
+    if let Some(year) = years
+        .iter()
+        .find(|&&year| registry.days(year).next().is_none())
+    {
+        eprintln!("{}", SolveError::UnknownYear(*year));
+        process::exit(1);
+    }
+
+    // Without --day all solved days of the year
+    let puzzles: Vec<(u16, u8)> = years
+        .iter()
+        .flat_map(|&year| match days {
+            Some(days) => days.iter().map(|&day| (year, day)).collect::<Vec<_>>(),
+            None => registry.days(year).map(|s| (year, s.day())).collect(),
+        })
+        .collect();
+    if !matches!(source, Source::Calendar) && puzzles.len() != 1 {
+        eprintln!("An input from a file or the standard input solves a single day");
+        process::exit(1);
+    }
+
+    let mut params = Params::new();
+    for (name, value) in matches
+        .get_many::<(String, String)>("param")
+        .into_iter()
+        .flatten()
+    {
+        params.set(name, value);
+    }
+    if let Err(e) = registry.check_params(&puzzles, &params) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+    registry.set_params(params);
+
+    // The session is required only for inputs, which are neither local nor cached
+    let session = env::var("ADVENT_SESSION").ok();
+    let mut timing_table = TimingTable::new();
+    let mut records: Vec<Record> = Vec::new();
+
+    let mut failed = false;
+
+    for (year, day) in puzzles {
+        if registry.get(year, day).is_none() {
+            let e = SolveError::UnknownDay(year, day);
+            eprintln!("{}", e);
+            records.push(Record::failed(year, day, e.to_string()));
+            failed = true;
+            continue;
+        }
+
+        let input = match &source {
+            Source::File(path) => input::read_file(path),
+            Source::Stdin => input::read_stdin(),
+            Source::Calendar => {
+                get_calendar_input(&cache, base_url, day, year, session.as_deref(), refresh)
+            }
+        };
+        let input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!(
+                    "Couldn't get input value for day {} / {}. Error: {}",
+                    day, year, e
+                );
+                records.push(Record::failed(year, day, e.to_string()));
+                failed = true;
+                continue;
+            }
+        };
+
+        match format {
+            Format::Text => match registry.print_answers(year, day, input.as_str()) {
+                Some(timings) => timing_table.push(year, day, timings),
+                None => failed = true,
+            },
+            _ => match registry.run(year, day, input.as_str()) {
+                Ok(run) => records.extend(Record::from_run(year, day, &run)),
+                Err(e) => {
+                    records.push(Record::failed(year, day, e.to_string()));
+                    failed = true;
+                }
+            },
+        }
+
+        // Without --day only the days, which can be drawn or animated
+        let render = days.is_some() || renderers.contains(year, day);
+        if let Some(root) = render_dir.filter(|_| render) {
+            match renderers.render(
+                root,
+                render_format,
+                year,
+                day,
+                input.as_str(),
+                registry.params(),
+            ) {
+                Ok(written) => eprintln!(
+                    "Day {} / {}: rendered {} files to {}",
+                    day,
+                    year,
+                    written.len(),
+                    render::day_dir(root, year, day).display()
+                ),
+                Err(e @ RenderFailure::Unsupported(_, _)) => eprintln!("{}", e),
+                Err(e) => {
+                    eprintln!("Day {} / {}: {}", day, year, e);
+                    failed = true;
+                }
+            }
+        }
+
+        if log && (days.is_some() || loggers.contains(year, day)) {
+            match loggers.log(year, day, input.as_str(), registry.params()) {
+                Ok(text) => print!("{}", text),
+                Err(e @ RenderFailure::NoLog(_, _)) => eprintln!("{}", e),
+                Err(e) => {
+                    eprintln!("Day {} / {}: {}", day, year, e);
+                    failed = true;
+                }
+            }
+        }
+
+        let animate = days.is_some() || animators.contains(year, day);
+        if let Some(player) = player.as_mut().filter(|_| animate) {
+            match animators.scenes(year, day, input.as_str(), registry.params()) {
+                Ok(scenes) => {
+                    if play(player, scenes) == Finish::Quit {
+                        break;
+                    }
+                }
+                Err(e @ RenderFailure::Unsupported(_, _)) => eprintln!("{}", e),
+                Err(e) => {
+                    eprintln!("Day {} / {}: {}", day, year, e);
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    match format {
+        Format::Text => {
+            if time && !timing_table.is_empty() {
+                print!("{}", timing_table);
+            }
+
+            println!("Done");
+        }
+        Format::Json => println!("{}", report::to_json(&records)),
+        Format::Csv => print!("{}", report::to_csv(&records)),
+    }
+
+    // Some days are unknown, or their inputs couldn't be downloaded, read or solved
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn play(player: &mut Player, scenes: Vec<Scene>) -> Finish {
+    let stdout = io::stdout();
+
+    for scene in scenes {
+        match player.play(scene, &mut stdout.lock()) {
+            Ok(Finish::Done) => (),
+            Ok(Finish::Quit) => return Finish::Quit,
+            Err(e) => {
+                eprintln!("Couldn't animate: {}", e);
+                return Finish::Quit;
+            }
+        }
+    }
+
+    Finish::Done
+}
+
+fn get_calendar_input(
+    cache: &InputCache,
+    base_url: &str,
+    day: u8,
+    year: u16,
+    session: Option<&str>,
+    refresh: bool,
+) -> Result<String, InputError> {
+    if let Some(input) = input::read_local(Path::new(input::LOCAL_INPUTS_DIR), day, year) {
+        return Ok(input);
+    }
+
+    cache.get_input(base_url, day, year, session, refresh)
+}
+
+// Returns false, if the answer couldn't be submitted or recorded; a wrong answer is a verdict
+fn submit(matches: &ArgMatches, base_url: &str) -> bool {
+    let day = *matches.get_one::<u8>("day").unwrap();
+    let year = *matches.get_one::<u16>("year").unwrap();
+    let part = *matches.get_one::<u8>("part").unwrap();
+    let answer = matches.get_one::<String>("answer").unwrap();
 
     let key = "ADVENT_SESSION";
-    match env::var(key) {
-        Ok(session_value) => {
-            let session = session_value.as_str();
+    let session = match env::var(key) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!(
+                "Couldn't get {} key from environment variable. Description: {}",
+                key, e
+            );
+            return false;
+        }
+    };
+
+    let answer = answer.trim();
+    match submit::submit_answer(base_url, day, year, part, answer, session.as_str()) {
+        Ok(verdict) => {
+            println!("Day {} / {}, part {}: {}", day, year, part, verdict);
 
-            for day in 1..26 {
-                if let Some(day_num) = day_num {
-                    if *day_num != day {
+            if verdict == Verdict::Correct {
+                if let Err(e) = record_answer(day, year, part, answer) {
+                    eprintln!("Couldn't record the answer. Error: {}", e);
+                    return false;
+                }
+            }
+            true
+        }
+        Err(e) => {
+            eprintln!(
+                "Couldn't submit the answer for day {} / {}. Error: {}",
+                day, year, e
+            );
+            false
+        }
+    }
+}
+
+fn record_answer(day: u8, year: u16, part: u8, answer: &str) -> Result<(), LedgerError> {
+    let mut ledger = Ledger::load(Path::new(ledger::ANSWERS_DIR), year)?;
+    ledger.record(day, part, answer);
+    ledger.save()
+}
+
+// The paths are relative to the workspace, so it must be run from its root
+fn new_day(matches: &ArgMatches) -> bool {
+    let day = *matches.get_one::<u8>("day").unwrap();
+    let year = *matches.get_one::<u16>("year").unwrap();
+
+    match scaffold::new_day(Path::new("."), year, day) {
+        Ok(written) => {
+            for path in written {
+                println!("Written {}", path.display());
+            }
+            println!(
+                "Add the examples of the puzzle to {}",
+                examples::path(Path::new(examples::EXAMPLES_DIR), year, day).display()
+            );
+            true
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            false
+        }
+    }
+}
+
+// Returns false, if any example has a wrong answer
+fn run_examples(matches: &ArgMatches) -> bool {
+    let day = *matches.get_one::<u8>("day").unwrap();
+    let year = *matches.get_one::<u16>("year").unwrap();
+    let registry = build_registry();
+
+    let examples = match examples::load(Path::new(examples::EXAMPLES_DIR), year, day) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+    if examples.is_empty() {
+        println!(
+            "Day {} / {} has no examples in {}",
+            day,
+            year,
+            examples::path(Path::new(examples::EXAMPLES_DIR), year, day).display()
+        );
+        return true;
+    }
+
+    let checked = match examples::check(&registry, year, day, &examples) {
+        Ok(checked) => checked,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+
+    for answer in &checked {
+        println!("Day {} / {}, {}", day, year, answer);
+    }
+
+    let passed = checked.iter().filter(|answer| answer.passed()).count();
+    println!("Passed {} of {} answers", passed, checked.len());
+    passed == checked.len()
+}
+
+// Returns false, if any answer differs from the recorded one, or a recorded day can't be solved
+fn verify(matches: &ArgMatches, cache: &InputCache, base_url: &str) -> bool {
+    let registry = build_registry();
+    let record = matches.get_flag("record");
+    let years = match matches.get_one::<u16>("year") {
+        Some(year) => vec![*year],
+        None => registry.years(),
+    };
+
+    let session = env::var("ADVENT_SESSION").ok();
+    let mut changed = 0usize;
+    let mut failed = 0usize;
+    let mut verified = 0usize;
+
+    for year in years {
+        let mut ledger = match Ledger::load(Path::new(ledger::ANSWERS_DIR), year) {
+            Ok(ledger) => ledger,
+            Err(e) => {
+                eprintln!("{}", e);
+                return false;
+            }
+        };
+
+        for solution in registry.days(year) {
+            let day = solution.day();
+            // The days without answers may be skipped, the recorded ones must be solved
+            let mut fail = |reason: String| {
+                if ledger.recorded(day) {
+                    failed += 1;
+                    eprintln!("Day {} / {}: FAILED. Error: {}", day, year, reason);
+                } else {
+                    eprintln!("Day {} / {}: skipped. Error: {}", day, year, reason);
+                }
+            };
+
+            let input =
+                match get_calendar_input(cache, base_url, day, year, session.as_deref(), false) {
+                    Ok(input) => input,
+                    Err(e) => {
+                        fail(e.to_string());
                         continue;
                     }
+                };
+
+            let (part1, part2) = match registry.solve(year, day, input.as_str()) {
+                Ok(answers) => answers,
+                Err(e) => {
+                    fail(e.to_string());
+                    continue;
                 }
+            };
 
-                let input = advent::get_input(day, *year_num, session);
-                if input.is_err() {
-                    eprintln!(
-                        "Couldn't get input value for day {} / {}. Error: {}",
-                        day,
-                        year_num,
-                        input.unwrap_err()
-                    );
-                    return;
+            for (part, answer) in [(1u8, part1), (2u8, part2)] {
+                if answer.is_nothing() {
+                    continue;
                 }
 
-                print_answers(day, *year_num, input.ok().unwrap());
+                let answer = answer.to_string();
+                let status = match ledger.check(day, part, answer.as_str()) {
+                    Check::Same => String::from("ok"),
+                    Check::Changed(expected) => {
+                        changed += 1;
+                        format!("CHANGED, expected {}", expected)
+                    }
+                    Check::NotRecorded if record => {
+                        ledger.record(day, part, answer.as_str());
+                        String::from("recorded")
+                    }
+                    Check::NotRecorded => String::from("not recorded"),
+                };
+
+                verified += 1;
+                println!(
+                    "Day {} / {}, part {}: {} {}",
+                    day, year, part, answer, status
+                );
+            }
+        }
+
+        if record {
+            if let Err(e) = ledger.save() {
+                eprintln!("{}", e);
+                return false;
             }
         }
-        Err(e) => println!(
-            "Couldn't get {} key from environment variable. Description: {}",
-            key, e
-        ),
     }
 
-    println!("Done");
+    println!(
+        "Verified {} answers, {} changed, {} days failed",
+        verified, changed, failed
+    );
+    changed == 0 && failed == 0
 }
 
-#[inline]
-fn print_answers(day: u8, year_num: u16, input: String) {
-    match year_num {
-        2015 => adv2015::print_answers(day, input),
-        _ => adv2020::print_answers(day, input),
-    }
+fn build_registry() -> Registry {
+    let mut registry = Registry::new();
+    adv2015::register(&mut registry);
+    adv2020::register(&mut registry);
+
+    registry
 }
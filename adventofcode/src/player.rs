@@ -0,0 +1,216 @@
+use advrender::Scene;
+use std::io;
+use std::io::{BufRead, Write};
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Moves the cursor home and clears the screen
+const CLEAR: &str = "\x1b[H\x1b[2J";
+
+const MAX_FPS: u32 = 120;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    Pause,
+    Faster,
+    Slower,
+    Next,
+    Quit,
+}
+
+impl Control {
+    // A line of the standard input, the terminal isn't switched to the raw mode
+    pub fn parse(line: &str) -> Option<Control> {
+        match line.trim() {
+            "" | "p" => Some(Control::Pause),
+            "+" | "f" => Some(Control::Faster),
+            "-" | "s" => Some(Control::Slower),
+            "n" => Some(Control::Next),
+            "q" => Some(Control::Quit),
+            _ => None,
+        }
+    }
+}
+
+// The controls are read in the background, until the standard input is closed
+pub fn stdin_controls() -> Receiver<Control> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                return;
+            };
+            if let Some(control) = Control::parse(line.as_str()) {
+                if sender.send(control).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    receiver
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Finish {
+    // All frames are shown, or the rest is skipped
+    Done,
+    Quit,
+}
+
+// Redraws the terminal in place for every frame of a scene
+pub struct Player {
+    fps: u32,
+    paused: bool,
+    controls: Receiver<Control>,
+}
+
+impl Player {
+    pub fn new(fps: u32, controls: Receiver<Control>) -> Player {
+        Player {
+            fps: fps.clamp(1, MAX_FPS),
+            paused: false,
+            controls,
+        }
+    }
+
+    pub fn play<W: Write>(&mut self, scene: Scene, out: &mut W) -> io::Result<Finish> {
+        let name = String::from(scene.name());
+
+        for (index, frame) in scene.enumerate() {
+            self.draw(out, name.as_str(), index, frame.as_str())?;
+
+            let deadline = Instant::now() + Duration::from_secs(1) / self.fps;
+            while let Some(control) = self.wait(deadline) {
+                match control {
+                    Control::Pause => self.paused = !self.paused,
+                    Control::Faster => self.fps = (self.fps * 2).min(MAX_FPS),
+                    Control::Slower => self.fps = (self.fps / 2).max(1),
+                    Control::Next => return Ok(Finish::Done),
+                    Control::Quit => return Ok(Finish::Quit),
+                }
+
+                self.draw(out, name.as_str(), index, frame.as_str())?;
+            }
+        }
+
+        Ok(Finish::Done)
+    }
+
+    fn draw<W: Write>(&self, out: &mut W, name: &str, index: usize, frame: &str) -> io::Result<()> {
+        let state = match self.paused {
+            true => String::from("paused"),
+            false => format!("{} fps", self.fps),
+        };
+
+        write!(out, "{}{}", CLEAR, frame)?;
+        writeln!(
+            out,
+            "{}, frame {} ({}); Enter: pause, +/-: speed, n: next, q: quit",
+            name, index, state
+        )?;
+        out.flush()
+    }
+
+    // None when it's time for the next frame; a pause waits for the next control
+    fn wait(&mut self, deadline: Instant) -> Option<Control> {
+        if self.paused {
+            return match self.controls.recv() {
+                Ok(control) => Some(control),
+                // Nobody can resume it
+                Err(_) => {
+                    self.paused = false;
+                    None
+                }
+            };
+        }
+
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        match self.controls.recv_timeout(timeout) {
+            Ok(control) => Some(control),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(frames: usize) -> Scene {
+        Scene::new(
+            "count",
+            (0..frames).map(|frame| format!("frame {}\n", frame)),
+        )
+    }
+
+    #[test]
+    fn test_control_parse() {
+        assert_eq!(Control::parse("\n"), Some(Control::Pause));
+        assert_eq!(Control::parse("p"), Some(Control::Pause));
+        assert_eq!(Control::parse("+"), Some(Control::Faster));
+        assert_eq!(Control::parse("-"), Some(Control::Slower));
+        assert_eq!(Control::parse("n"), Some(Control::Next));
+        assert_eq!(Control::parse(" q "), Some(Control::Quit));
+        assert_eq!(Control::parse("x"), None);
+    }
+
+    #[test]
+    fn test_play() {
+        let (_sender, receiver) = mpsc::channel();
+        let mut player = Player::new(MAX_FPS, receiver);
+        let mut out = Vec::new();
+
+        assert_eq!(player.play(scene(3), &mut out).unwrap(), Finish::Done);
+
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches(CLEAR).count(), 3);
+        assert!(out.contains("frame 2\ncount, frame 2 (120 fps)"));
+    }
+
+    #[test]
+    fn test_controls() {
+        let (sender, receiver) = mpsc::channel();
+        let mut player = Player::new(1000, receiver);
+        assert_eq!(player.fps, MAX_FPS);
+
+        // Paused and resumed, the slower speed is shown again
+        for control in [Control::Slower, Control::Pause, Control::Pause] {
+            sender.send(control).unwrap();
+        }
+        sender.send(Control::Quit).unwrap();
+        let mut out = Vec::new();
+        assert_eq!(player.play(scene(10), &mut out).unwrap(), Finish::Quit);
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("(60 fps)"));
+        assert!(out.contains("(paused)"));
+        assert!(!out.contains("frame 1\n"));
+
+        sender.send(Control::Next).unwrap();
+        assert_eq!(
+            player.play(scene(10), &mut Vec::new()).unwrap(),
+            Finish::Done
+        );
+    }
+
+    #[test]
+    fn test_closed_controls() {
+        let (sender, receiver) = mpsc::channel();
+        sender.send(Control::Pause).unwrap();
+        drop(sender);
+
+        // The pause ends, when the controls are gone
+        let mut player = Player::new(MAX_FPS, receiver);
+        let mut out = Vec::new();
+        assert_eq!(player.play(scene(2), &mut out).unwrap(), Finish::Done);
+        assert_eq!(String::from_utf8(out).unwrap().matches(CLEAR).count(), 3);
+    }
+}
@@ -0,0 +1,280 @@
+use advcore::Params;
+use advparse::ParseError;
+use advrender::{write_animation, Animator, Format, Logger, RenderError, Renderer, Scene};
+use core::fmt;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+pub enum RenderFailure {
+    // The day has nothing to draw
+    Unsupported(u16, u8),
+    // The day has nothing to print with --log
+    NoLog(u16, u8),
+    Input(ParseError),
+    Write(RenderError),
+}
+
+impl Display for RenderFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderFailure::Unsupported(year, day) => {
+                write!(f, "Day {} / {} has nothing to draw", day, year)
+            }
+            RenderFailure::NoLog(year, day) => write!(f, "Day {} / {} has no log", day, year),
+            RenderFailure::Input(err) => write!(f, "Couldn't render the input: {}", err),
+            RenderFailure::Write(err) => err.fmt(f),
+        }
+    }
+}
+
+// The days of all years, which can be drawn with --render
+pub struct Renderers {
+    renderers: BTreeMap<(u16, u8), Renderer>,
+}
+
+impl Renderers {
+    pub fn new() -> Renderers {
+        let years = [
+            (adv2015::YEAR, adv2015::renderers()),
+            (adv2020::YEAR, adv2020::renderers()),
+        ];
+
+        Renderers {
+            renderers: years
+                .into_iter()
+                .flat_map(|(year, days)| days.into_iter().map(move |(day, f)| ((year, day), f)))
+                .collect(),
+        }
+    }
+
+    pub fn contains(&self, year: u16, day: u8) -> bool {
+        self.renderers.contains_key(&(year, day))
+    }
+
+    // The pictures are in <root>/<year>/day<NN>, e.g. render/2015/day18/lights.gif
+    pub fn render(
+        &self,
+        root: &Path,
+        format: Format,
+        year: u16,
+        day: u8,
+        input: &str,
+        params: &Params,
+    ) -> Result<Vec<PathBuf>, RenderFailure> {
+        let renderer = self
+            .renderers
+            .get(&(year, day))
+            .ok_or(RenderFailure::Unsupported(year, day))?;
+        let animations = renderer(input, params).map_err(RenderFailure::Input)?;
+        let dir = day_dir(root, year, day);
+
+        let mut written = Vec::new();
+        for animation in &animations {
+            written.extend(write_animation(&dir, animation, format).map_err(RenderFailure::Write)?);
+        }
+
+        Ok(written)
+    }
+}
+
+// The days of all years, which can be played with --animate
+pub struct Animators {
+    animators: BTreeMap<(u16, u8), Animator>,
+}
+
+impl Animators {
+    pub fn new() -> Animators {
+        let years = [
+            (adv2015::YEAR, adv2015::animators()),
+            (adv2020::YEAR, adv2020::animators()),
+        ];
+
+        Animators {
+            animators: years
+                .into_iter()
+                .flat_map(|(year, days)| days.into_iter().map(move |(day, f)| ((year, day), f)))
+                .collect(),
+        }
+    }
+
+    pub fn contains(&self, year: u16, day: u8) -> bool {
+        self.animators.contains_key(&(year, day))
+    }
+
+    pub fn scenes(
+        &self,
+        year: u16,
+        day: u8,
+        input: &str,
+        params: &Params,
+    ) -> Result<Vec<Scene>, RenderFailure> {
+        let animator = self
+            .animators
+            .get(&(year, day))
+            .ok_or(RenderFailure::Unsupported(year, day))?;
+
+        animator(input, params).map_err(RenderFailure::Input)
+    }
+}
+
+// The days of all years, which can print their log with --log
+pub struct Loggers {
+    loggers: BTreeMap<(u16, u8), Logger>,
+}
+
+impl Loggers {
+    pub fn new() -> Loggers {
+        let years = [
+            (adv2015::YEAR, adv2015::loggers()),
+            (adv2020::YEAR, adv2020::loggers()),
+        ];
+
+        Loggers {
+            loggers: years
+                .into_iter()
+                .flat_map(|(year, days)| days.into_iter().map(move |(day, f)| ((year, day), f)))
+                .collect(),
+        }
+    }
+
+    pub fn contains(&self, year: u16, day: u8) -> bool {
+        self.loggers.contains_key(&(year, day))
+    }
+
+    pub fn log(
+        &self,
+        year: u16,
+        day: u8,
+        input: &str,
+        params: &Params,
+    ) -> Result<String, RenderFailure> {
+        let logger = self
+            .loggers
+            .get(&(year, day))
+            .ok_or(RenderFailure::NoLog(year, day))?;
+
+        logger(input, params).map_err(RenderFailure::Input)
+    }
+}
+
+pub fn day_dir(root: &Path, year: u16, day: u8) -> PathBuf {
+    root.join(year.to_string()).join(format!("day{:02}", day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    #[test]
+    fn test_renderers() {
+        let renderers = Renderers::new();
+
+        assert!(renderers.contains(2015, 6));
+        assert!(renderers.contains(2015, 18));
+        assert!(renderers.contains(2020, 11));
+        assert!(!renderers.contains(2020, 1));
+    }
+
+    #[test]
+    fn test_render() {
+        let root = env::temp_dir().join(format!("advent-render-{}", process::id()));
+        let renderers = Renderers::new();
+        let params = Params::new();
+
+        let written = renderers
+            .render(
+                &root,
+                Format::Ppm,
+                2015,
+                6,
+                "turn on 0,0 through 9,9",
+                &params,
+            )
+            .unwrap_or_else(|e| panic!("{}", e));
+        let dir = day_dir(&root, 2015, 6);
+        assert_eq!(
+            written,
+            vec![dir.join("bulbs.ppm"), dir.join("brightness.ppm")]
+        );
+        assert!(written.iter().all(|path| path.is_file()));
+
+        let written = renderers
+            .render(&root, Format::Png, 2020, 11, "L.L\n#L.", &params)
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert!(written.contains(&day_dir(&root, 2020, 11).join("direct.gif")));
+
+        assert!(matches!(
+            renderers.render(&root, Format::Png, 2020, 1, "", &params),
+            Err(RenderFailure::Unsupported(2020, 1))
+        ));
+        assert!(matches!(
+            renderers.render(&root, Format::Png, 2015, 18, ".#", &params),
+            Err(RenderFailure::Input(_))
+        ));
+
+        let mut small = Params::new();
+        small.set("size", "2");
+        small.set("steps", "1");
+        let written = renderers
+            .render(&root, Format::Png, 2015, 18, ".#\n#.", &small)
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert!(written.contains(&day_dir(&root, 2015, 18).join("lights-001.png")));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_animators() {
+        let animators = Animators::new();
+        let params = Params::new();
+
+        assert!(animators.contains(2015, 3));
+        assert!(animators.contains(2015, 18));
+        assert!(animators.contains(2020, 11));
+        assert!(animators.contains(2020, 17));
+        assert!(!animators.contains(2015, 6));
+
+        let scenes = animators
+            .scenes(2015, 3, "^>", &params)
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(scenes.len(), 1);
+        assert_eq!(scenes.into_iter().next().unwrap().count(), 3);
+
+        assert!(matches!(
+            animators.scenes(2015, 6, "", &params),
+            Err(RenderFailure::Unsupported(2015, 6))
+        ));
+        assert!(matches!(
+            animators.scenes(2015, 3, "x", &params),
+            Err(RenderFailure::Input(_))
+        ));
+    }
+
+    #[test]
+    fn test_loggers() {
+        let loggers = Loggers::new();
+        let params = Params::new();
+
+        assert!(loggers.contains(2020, 20));
+        assert!(loggers.contains(2020, 22));
+        assert!(!loggers.contains(2015, 6));
+
+        let text = loggers
+            .log(2020, 22, "Player 1:\n2\n\nPlayer 2:\n1", &params)
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert!(text.contains("Player 1 wins round 1 of game 1!"));
+
+        assert!(matches!(
+            loggers.log(2015, 6, "", &params),
+            Err(RenderFailure::NoLog(2015, 6))
+        ));
+        assert!(matches!(
+            loggers.log(2020, 22, "x", &params),
+            Err(RenderFailure::Input(_))
+        ));
+    }
+}
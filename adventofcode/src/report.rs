@@ -0,0 +1,208 @@
+use advcore::Run;
+use serde_json::{json, Value};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format {}", s)),
+        }
+    }
+}
+
+// A solved part, or a day, which couldn't be solved (then part and answer are missing)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    pub error: Option<String>,
+}
+
+impl Record {
+    // The parsing is counted in the first part, if it's measured on its own
+    pub fn from_run(year: u16, day: u8, run: &Run) -> Vec<Record> {
+        let parts = [
+            (
+                1,
+                &run.part1,
+                run.timings.parse.unwrap_or_default() + run.timings.part1,
+            ),
+            (2, &run.part2, run.timings.part2),
+        ];
+
+        parts
+            .into_iter()
+            .filter(|(_, answer, _)| !answer.is_nothing())
+            .map(|(part, answer, elapsed)| Record {
+                year,
+                day,
+                part: Some(part),
+                answer: Some(answer.to_string()),
+                elapsed,
+                error: None,
+            })
+            .collect()
+    }
+
+    pub fn failed(year: u16, day: u8, error: String) -> Record {
+        Record {
+            year,
+            day,
+            part: None,
+            answer: None,
+            elapsed: Duration::ZERO,
+            error: Some(error),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part": self.part,
+            "answer": self.answer,
+            "elapsed_ns": self.elapsed.as_nanos() as u64,
+            "error": self.error,
+        })
+    }
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    let records: Vec<Value> = records.iter().map(Record::to_json).collect();
+
+    serde_json::to_string_pretty(&records).unwrap_or_default()
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut csv = String::from("year,day,part,answer,elapsed_ns,error\n");
+
+    for record in records {
+        let part = record.part.map(|part| part.to_string()).unwrap_or_default();
+        let answer = record.answer.as_deref().unwrap_or_default();
+        let error = record.error.as_deref().unwrap_or_default();
+
+        csv.push_str(
+            format!(
+                "{},{},{},{},{},{}\n",
+                record.year,
+                record.day,
+                part,
+                csv_field(answer),
+                record.elapsed.as_nanos(),
+                csv_field(error)
+            )
+            .as_str(),
+        );
+    }
+
+    csv
+}
+
+// RFC 4180: quote the fields with separators, quotes or line breaks
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advcore::{Answer, Timings};
+
+    fn records() -> Vec<Record> {
+        let run = Run {
+            part1: Answer::Unsigned(35),
+            part2: Answer::Nothing,
+            timings: Timings {
+                parse: Some(Duration::from_nanos(100)),
+                part1: Duration::from_nanos(200),
+                part2: Duration::from_nanos(5),
+            },
+        };
+
+        let mut records = Record::from_run(2020, 10, &run);
+        records.push(Record::failed(
+            2020,
+            11,
+            String::from("Too many requests, \"wait\""),
+        ));
+
+        records
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("text".parse::<Format>(), Ok(Format::Text));
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert_eq!("csv".parse::<Format>(), Ok(Format::Csv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_from_run() {
+        let records = records();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].part, Some(1));
+        assert_eq!(records[0].answer.as_deref(), Some("35"));
+        assert_eq!(records[0].elapsed, Duration::from_nanos(300));
+        assert!(records[0].error.is_none());
+        assert!(records[1].part.is_none());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&records()),
+            "year,day,part,answer,elapsed_ns,error\n\
+             2020,10,1,35,300,\n\
+             2020,11,,,0,\"Too many requests, \"\"wait\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json: Value = serde_json::from_str(to_json(&records()).as_str()).unwrap();
+
+        assert_eq!(
+            json,
+            json!([
+                {
+                    "year": 2020,
+                    "day": 10,
+                    "part": 1,
+                    "answer": "35",
+                    "elapsed_ns": 300,
+                    "error": null,
+                },
+                {
+                    "year": 2020,
+                    "day": 11,
+                    "part": null,
+                    "answer": null,
+                    "elapsed_ns": 0,
+                    "error": "Too many requests, \"wait\"",
+                }
+            ])
+        );
+    }
+}
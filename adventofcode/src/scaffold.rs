@@ -0,0 +1,441 @@
+use core::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.tmpl");
+const MANIFEST_TEMPLATE: &str = include_str!("../templates/Cargo.toml.tmpl");
+
+// The files of the workspace, which list every year crate, e.g. adv2015 and adv2020
+const YEAR_LISTS: [&str; 6] = [
+    "Cargo.toml",
+    "adventofcode/Cargo.toml",
+    "adventofcode/src/main.rs",
+    "adventofcode/src/render.rs",
+    "adventofcode/src/examples.rs",
+    "adventofcode/benches/days.rs",
+];
+
+pub enum ScaffoldError {
+    Io(PathBuf, io::Error),
+    Exists(PathBuf),
+    // The file doesn't look like the generated one any more
+    Layout(PathBuf, &'static str),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::Io(path, err) => {
+                write!(f, "Couldn't access {}: {}", path.display(), err)
+            }
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Layout(path, err) => {
+                write!(f, "Couldn't change {}: {}", path.display(), err)
+            }
+        }
+    }
+}
+
+// Creates adv<year>/src/day<N>.rs in the workspace and registers it, the year crate
+// is created and wired into the binary, if it doesn't exist yet. Returns the written files
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let crate_dir = root.join(format!("adv{}", year));
+    let day_path = crate_dir.join("src").join(format!("day{}.rs", day));
+    if day_path.exists() {
+        return Err(ScaffoldError::Exists(day_path));
+    }
+
+    let mut written = Vec::new();
+    if !crate_dir.exists() {
+        written.extend(new_year(root, year)?);
+    }
+
+    write(&day_path, &fill(DAY_TEMPLATE, year, day))?;
+    written.push(day_path);
+
+    let lib_path = crate_dir.join("src").join("lib.rs");
+    let lib = read(&lib_path)?;
+    let lib = add_module(&lib, day).ok_or(ScaffoldError::Layout(
+        lib_path.clone(),
+        "the editor-fold block of the days is missing",
+    ))?;
+    let lib = add_registration(&lib, day).ok_or(ScaffoldError::Layout(
+        lib_path.clone(),
+        "the register function is missing",
+    ))?;
+    write(&lib_path, &count_registered(&lib))?;
+    if !written.contains(&lib_path) {
+        written.push(lib_path);
+    }
+
+    Ok(written)
+}
+
+fn new_year(root: &Path, year: u16) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let crate_dir = root.join(format!("adv{}", year));
+    let manifest = crate_dir.join("Cargo.toml");
+    let lib = crate_dir.join("src").join("lib.rs");
+
+    write(&manifest, &fill(MANIFEST_TEMPLATE, year, 0))?;
+    write(&lib, &fill(LIB_TEMPLATE, year, 0))?;
+    let mut written = vec![manifest, lib];
+
+    for list in YEAR_LISTS {
+        let path = root.join(list);
+        let content = read(&path)?;
+
+        if let Some(content) = add_year(&content, year) {
+            write(&path, &content)?;
+            written.push(path);
+        }
+    }
+
+    Ok(written)
+}
+
+fn fill(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(path).map_err(|err| ScaffoldError::Io(path.to_path_buf(), err))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), ScaffoldError> {
+    let write = || -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)
+    };
+
+    write().map_err(|err| ScaffoldError::Io(path.to_path_buf(), err))
+}
+
+// The year of a line, which mentions a single year crate, e.g. "    adv2015::register(&mut registry);"
+fn year_of(line: &str) -> Option<u16> {
+    let (_, rest) = line.split_once("adv")?;
+    let year = rest.get(..4)?.parse().ok()?;
+
+    line.matches("adv")
+        .count()
+        .eq(&line.matches(&format!("adv{}", year)).count())
+        .then_some(year)
+}
+
+// Every list of year crates gets a copy of its line for the new year, in order;
+// returns None, if the content has no list or already has the year
+fn add_year(content: &str, year: u16) -> Option<String> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut result = String::new();
+    let mut changed = false;
+    let mut index = 0;
+
+    while index < lines.len() {
+        let start = index;
+        while index < lines.len() && year_of(lines[index]).is_some() {
+            index += 1;
+        }
+
+        if start == index {
+            result.push_str(lines[index]);
+            index += 1;
+            continue;
+        }
+
+        let run = &lines[start..index];
+        if run.iter().any(|line| year_of(line) == Some(year)) {
+            run.iter().for_each(|line| result.push_str(line));
+            continue;
+        }
+
+        // The new line is a copy of its neighbour in the list
+        let position = run
+            .iter()
+            .position(|line| year_of(line) > Some(year))
+            .unwrap_or(run.len());
+        let model = run[position.min(run.len() - 1)];
+        let mut line = model.replace(&format!("adv{}", year_of(model)?), &format!("adv{}", year));
+        let mut entries: Vec<String> = run.iter().map(|line| line.to_string()).collect();
+
+        // The list may be the end of a file without a new line
+        if !line.ends_with('\n') {
+            if position == run.len() {
+                entries[position - 1].push_str(line_ending(content));
+            } else {
+                line.push_str(line_ending(content));
+            }
+        }
+        entries.insert(position, line);
+        entries.iter().for_each(|entry| result.push_str(entry));
+        changed = true;
+    }
+
+    changed.then_some(result)
+}
+
+fn line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+// "mod day12;" is added to the editor-fold block, the modules are sorted by name
+fn add_module(lib: &str, day: u8) -> Option<String> {
+    let module = format!("mod day{};", day);
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("/* <editor-fold"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.starts_with("/* </editor-fold>"))?;
+
+    let position = (start + 1..end)
+        .find(|&index| lines[index].trim_end_matches(';') > module.trim_end_matches(';'))
+        .unwrap_or(end);
+    lines.insert(position, module);
+
+    Some(join(lines, lib))
+}
+
+// The registration is added in the order of the days
+fn add_registration(lib: &str, day: u8) -> Option<String> {
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub fn register(registry: &mut Registry)"))?;
+    let end = start + lines[start..].iter().position(|line| line == "}")?;
+
+    // "    registry.register(Day::new(" is followed by "        YEAR," and "        <day>,"
+    let position = (start + 1..end)
+        .find(|&index| {
+            lines[index].starts_with("    registry.register(")
+                && lines
+                    .get(index + 2)
+                    .and_then(|line| line.trim().trim_end_matches(',').parse::<u8>().ok())
+                    .is_some_and(|registered| registered > day)
+        })
+        .unwrap_or(end);
+
+    let registration = [
+        String::from("    registry.register(Day::new("),
+        String::from("        YEAR,"),
+        format!("        {},", day),
+        format!(
+            "        |input| day{}::find_answer1(input).map(Into::into),",
+            day
+        ),
+        format!(
+            "        |input| day{}::find_answer2(input).map(Into::into),",
+            day
+        ),
+        String::from("    ));"),
+    ];
+    lines.splice(position..position, registration);
+
+    Some(join(lines, lib))
+}
+
+// The test of the registry counts the days
+fn count_registered(lib: &str) -> String {
+    const COUNT: &str = "assert_eq!(registry.len(), ";
+
+    let lines: Vec<String> = lib
+        .lines()
+        .map(|line| {
+            let count = line
+                .split_once(COUNT)
+                .and_then(|(_, rest)| rest.strip_suffix(");"))
+                .and_then(|count| count.parse::<usize>().ok());
+
+            match count {
+                Some(count) => line.replace(
+                    &format!("{}{});", COUNT, count),
+                    &format!("{}{});", COUNT, count + 1),
+                ),
+                None => String::from(line),
+            }
+        })
+        .collect();
+
+    join(lines, lib)
+}
+
+// Keeps the line endings and the last new line of the original
+fn join(lines: Vec<String>, original: &str) -> String {
+    let ending = line_ending(original);
+    let mut result = lines
+        .into_iter()
+        .map(|line| line.trim_end_matches('\r').to_string())
+        .collect::<Vec<_>>()
+        .join(ending);
+
+    if original.ends_with('\n') {
+        result.push_str(ending);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    const LIB: &str = "/* <editor-fold desc=\"mod - days import\"> */
+mod day1;
+mod day10;
+mod day2;
+/* </editor-fold> */
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Day::new(
+        YEAR,
+        1,
+        |input| day1::find_answer1(input).map(Into::into),
+        unsolved,
+    ));
+    registry.register(ParsedDay::new(
+        YEAR,
+        10,
+        day10::parse,
+        |input| day10::find_answer1(input).map(Into::into),
+        |input| day10::find_answer2(input).map(Into::into),
+    ));
+}
+
+#[test]
+fn test_registry() {
+    assert_eq!(registry.len(), 3);
+}
+";
+
+    #[test]
+    fn test_add_module() {
+        let lib = add_module(LIB, 3).unwrap();
+        assert!(lib.contains("mod day2;\nmod day3;\n/* </editor-fold> */"));
+
+        let lib = add_module(LIB, 11).unwrap();
+        assert!(lib.contains("mod day10;\nmod day11;\nmod day2;"));
+
+        let lib = add_module(&fill(LIB_TEMPLATE, 2016, 0), 1).unwrap();
+        assert!(lib.starts_with("/* <editor-fold desc=\"mod - days import\"> */\nmod day1;\n/* </"));
+
+        assert!(add_module("mod day1;", 2).is_none());
+    }
+
+    #[test]
+    fn test_add_registration() {
+        let lib = add_registration(LIB, 5).unwrap();
+        assert!(lib.contains(
+            "    ));
+    registry.register(Day::new(
+        YEAR,
+        5,
+        |input| day5::find_answer1(input).map(Into::into),
+        |input| day5::find_answer2(input).map(Into::into),
+    ));
+    registry.register(ParsedDay::new(
+        YEAR,
+        10,"
+        ));
+
+        let lib = add_registration(LIB, 12).unwrap();
+        assert!(lib
+            .contains("        |input| day12::find_answer2(input).map(Into::into),\n    ));\n}\n"));
+
+        let lib = add_registration(&fill(LIB_TEMPLATE, 2016, 0), 1).unwrap();
+        assert!(lib.contains(
+            "pub fn register(registry: &mut Registry) {\n    registry.register(Day::new(\n"
+        ));
+
+        assert!(add_registration("fn main() {\n}\n", 2).is_none());
+    }
+
+    #[test]
+    fn test_count_registered() {
+        assert!(count_registered(LIB).contains("assert_eq!(registry.len(), 4);"));
+        assert_eq!(count_registered("a\r\nb"), "a\r\nb");
+    }
+
+    #[test]
+    fn test_add_year() {
+        let members = "[workspace]\r\nmembers = [\r\n    \"adv2015\",\r\n    \"adv2020\",\r\n    \"advcore\",\r\n]\r\n";
+        assert_eq!(
+            add_year(members, 2016).unwrap(),
+            "[workspace]\r\nmembers = [\r\n    \"adv2015\",\r\n    \"adv2016\",\r\n    \"adv2020\",\r\n    \"advcore\",\r\n]\r\n"
+        );
+        assert!(add_year(members, 2020).is_none());
+        assert!(add_year("[workspace]\n", 2016).is_none());
+
+        let years = "        let years = [\n            (adv2015::YEAR, adv2015::renderers()),\n        ];\n";
+        assert_eq!(
+            add_year(years, 2021).unwrap(),
+            "        let years = [\n            (adv2015::YEAR, adv2015::renderers()),\n            (adv2021::YEAR, adv2021::renderers()),\n        ];\n"
+        );
+
+        assert_eq!(
+            add_year("adv2015::register(&mut registry);", 2021).unwrap(),
+            "adv2015::register(&mut registry);\nadv2021::register(&mut registry);"
+        );
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = env::temp_dir().join(format!("advent-scaffold-{}", process::id()));
+        let workspace = "[workspace]\nmembers = [\n    \"adv2015\",\n    \"adventofcode\",\n]\n";
+        write(&root.join("Cargo.toml"), workspace).ok().unwrap();
+        for list in &YEAR_LISTS[1..] {
+            write(&root.join(list), "    adv2015::register(&mut registry);\n")
+                .ok()
+                .unwrap();
+        }
+
+        let written = new_day(&root, 2016, 3).ok().unwrap();
+        assert_eq!(written.len(), 9);
+
+        let crate_dir = root.join("adv2016");
+        let lib = fs::read_to_string(crate_dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("mod day3;"));
+        assert!(lib.contains("pub const YEAR: u16 = 2016;"));
+        assert!(lib.contains("assert_eq!(registry.len(), 1);"));
+        assert!(fs::read_to_string(crate_dir.join("src/day3.rs"))
+            .unwrap()
+            .contains("day 3 / 2016 is not solved yet"));
+        assert!(fs::read_to_string(root.join("Cargo.toml"))
+            .unwrap()
+            .contains("\"adv2015\",\n    \"adv2016\",\n    \"adventofcode\""));
+        assert!(fs::read_to_string(root.join("adventofcode/src/main.rs"))
+            .unwrap()
+            .contains("adv2016::register(&mut registry);"));
+
+        // The year exists now
+        let written = new_day(&root, 2016, 1).ok().unwrap();
+        assert_eq!(
+            written,
+            vec![crate_dir.join("src/day1.rs"), crate_dir.join("src/lib.rs")]
+        );
+        assert!(fs::read_to_string(crate_dir.join("src/lib.rs"))
+            .unwrap()
+            .contains("mod day1;\nmod day3;"));
+
+        assert!(matches!(
+            new_day(&root, 2016, 3),
+            Err(ScaffoldError::Exists(_))
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeSet;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Years {
+    All,
+    List(Vec<u16>),
+}
+
+// --day 1-5,9
+pub fn parse_days(s: &str) -> Result<Vec<u8>, String> {
+    let mut days = BTreeSet::new();
+
+    for item in s.split(',').map(str::trim) {
+        let (first, last) = match item.split_once('-') {
+            Some((first, last)) => (parse_day(first)?, parse_day(last)?),
+            None => (parse_day(item)?, parse_day(item)?),
+        };
+
+        if first > last {
+            return Err(format!("{} is an empty range of days", item));
+        }
+
+        days.extend(first..=last);
+    }
+
+    Ok(days.into_iter().collect())
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("{} is not a day of the advent (1-25)", s.trim())),
+    }
+}
+
+// --year all, --year 2015,2020
+pub fn parse_years(s: &str) -> Result<Years, String> {
+    if s.trim() == "all" {
+        return Ok(Years::All);
+    }
+
+    let mut years = BTreeSet::new();
+    for item in s.split(',').map(str::trim) {
+        let year = item
+            .parse::<u16>()
+            .map_err(|_| format!("{} is neither a year nor \"all\"", item))?;
+
+        years.insert(year);
+    }
+
+    Ok(Years::List(years.into_iter().collect()))
+}
+
+// --param steps=4
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((String::from(name.trim()), String::from(value.trim())))
+        }
+        _ => Err(format!("{} is not a parameter, expected name=value", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("7"), Ok(vec![7]));
+        assert_eq!(parse_days("1-5,9"), Ok(vec![1, 2, 3, 4, 5, 9]));
+        assert_eq!(parse_days("9, 3-4, 4"), Ok(vec![3, 4, 9]));
+        assert_eq!(parse_days("25-25"), Ok(vec![25]));
+    }
+
+    #[test]
+    fn test_parse_wrong_days() {
+        assert!(parse_days("").is_err());
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("5-1").is_err());
+        assert!(parse_days("1-").is_err());
+        assert!(parse_days("one").is_err());
+        assert!(parse_days("1,,2").is_err());
+    }
+
+    #[test]
+    fn test_parse_years() {
+        assert_eq!(parse_years("all"), Ok(Years::All));
+        assert_eq!(parse_years("2015"), Ok(Years::List(vec![2015])));
+        assert_eq!(
+            parse_years("2020,2015,2020"),
+            Ok(Years::List(vec![2015, 2020]))
+        );
+        assert!(parse_years("").is_err());
+        assert!(parse_years("2015-2020").is_err());
+    }
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(
+            parse_param("steps=4"),
+            Ok((String::from("steps"), String::from("4")))
+        );
+        assert_eq!(
+            parse_param(" hit-points = 10 "),
+            Ok((String::from("hit-points"), String::from("10")))
+        );
+        assert_eq!(
+            parse_param("sum="),
+            Ok((String::from("sum"), String::new()))
+        );
+        assert!(parse_param("steps").is_err());
+        assert!(parse_param("=4").is_err());
+    }
+}
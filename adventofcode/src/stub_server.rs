@@ -0,0 +1,62 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::thread::JoinHandle;
+
+// A stand-in for adventofcode.com, which answers a single request with the canned response
+pub struct StubServer {
+    url: String,
+    handle: JoinHandle<String>,
+}
+
+impl StubServer {
+    pub fn start(status: &str, body: &str) -> StubServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0usize;
+
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap_or_default();
+                }
+                request.push_str(line.as_str());
+            }
+
+            let mut content = vec![0u8; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str("\r\n");
+            request.push_str(String::from_utf8_lossy(&content).as_ref());
+
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        StubServer { url, handle }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    // The raw request, which the server has received
+    pub fn request(self) -> String {
+        self.handle.join().unwrap()
+    }
+}
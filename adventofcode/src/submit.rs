@@ -0,0 +1,168 @@
+use crate::advent;
+use crate::advent::HttpError;
+use core::fmt;
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+
+    // seconds left before the next attempt
+    Wait(u64),
+    AlreadySolved,
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => f.write_str("That's the right answer!"),
+            Verdict::Wrong => f.write_str("That's not the right answer"),
+            Verdict::TooHigh => f.write_str("That's not the right answer, it is too high"),
+            Verdict::TooLow => f.write_str("That's not the right answer, it is too low"),
+            Verdict::Wait(seconds) => write!(
+                f,
+                "You gave an answer too recently, wait {}s before trying again",
+                seconds
+            ),
+            Verdict::AlreadySolved => f.write_str("The level is already solved"),
+            Verdict::Unknown => f.write_str("Couldn't understand the response"),
+        }
+    }
+}
+
+pub fn submit_answer(
+    base_url: &str,
+    day: u8,
+    year: u16,
+    part: u8,
+    answer: &str,
+    session: &str,
+) -> Result<Verdict, HttpError> {
+    let level = part.to_string();
+    let response = advent::build_client(session)?
+        .post(format!("{}/{}/day/{}/answer", base_url, year, day).as_str())
+        .form(&[("level", level.as_str()), ("answer", answer)])
+        .send()?;
+
+    let body = advent::check_response(response)?;
+
+    Ok(parse_verdict(body.as_str()))
+}
+
+pub fn parse_verdict(body: &str) -> Verdict {
+    if body.contains("That's the right answer") {
+        Verdict::Correct
+    } else if body.contains("That's not the right answer") {
+        if body.contains("answer is too high") {
+            Verdict::TooHigh
+        } else if body.contains("answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if body.contains("You gave an answer too recently") {
+        Verdict::Wait(parse_wait(body).unwrap_or_default())
+    } else if body.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown
+    }
+}
+
+// "You have 1m 23s left to wait" gives 83
+fn parse_wait(body: &str) -> Option<u64> {
+    let from = body.find("You have ")? + "You have ".len();
+    let to = from + body[from..].find(" left to wait")?;
+
+    body[from..to]
+        .split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.len().checked_sub(1)?);
+            let value = value.parse::<u64>().ok()?;
+
+            match unit {
+                "h" => Some(value * 60 * 60),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub_server::StubServer;
+
+    const CORRECT: &str = "<article><p>That's the right answer!  You are one gold star closer to powering the weather machine.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.</p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article>";
+    const WRONG: &str = "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>";
+    const WAIT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.</p></article>";
+    const SOLVED: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>";
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(parse_verdict(CORRECT), Verdict::Correct);
+        assert_eq!(parse_verdict(TOO_HIGH), Verdict::TooHigh);
+        assert_eq!(parse_verdict(TOO_LOW), Verdict::TooLow);
+        assert_eq!(parse_verdict(WRONG), Verdict::Wrong);
+        assert_eq!(parse_verdict(WAIT), Verdict::Wait(83));
+        assert_eq!(parse_verdict(SOLVED), Verdict::AlreadySolved);
+        assert_eq!(parse_verdict("<html></html>"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(parse_wait("You have 35s left to wait."), Some(35));
+        assert_eq!(parse_wait("You have 2m 5s left to wait."), Some(125));
+        assert_eq!(parse_wait("You have 1h 0m 1s left to wait."), Some(3601));
+        assert_eq!(parse_wait("You have a while left to wait."), None);
+        assert_eq!(parse_wait("Please wait."), None);
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let server = StubServer::start("200 OK", CORRECT);
+        let verdict = submit_answer(server.url().as_str(), 1, 2015, 2, "1795", "5361");
+        let request = server.request();
+
+        assert_eq!(verdict.ok(), Some(Verdict::Correct));
+        assert!(
+            request.starts_with("POST /2015/day/1/answer "),
+            "The request is unexpected: {}",
+            request
+        );
+        assert!(
+            request.to_lowercase().contains("cookie: session=5361"),
+            "The cookie is missing: {}",
+            request
+        );
+        assert!(
+            request.ends_with("level=2&answer=1795"),
+            "The form is unexpected: {}",
+            request
+        );
+    }
+
+    #[test]
+    fn test_submit_answer_unauthorized() {
+        let server = StubServer::start("400 Bad Request", "Please log in");
+        let verdict = submit_answer(server.url().as_str(), 1, 2015, 1, "280", "");
+
+        assert!(matches!(verdict, Err(HttpError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_verdict_display() {
+        assert_eq!(
+            Verdict::Wait(83).to_string(),
+            "You gave an answer too recently, wait 83s before trying again"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use advcore::{Elapsed, Timings};
+use core::fmt;
+use std::fmt::Display;
+
+// The summary of --time:
+//  Year Day      Parse     Part 1     Part 2      Total
+//  2015   4        n/a   95.12 ms     2.79 s     2.89 s
+#[derive(Default)]
+pub struct TimingTable {
+    rows: Vec<(u16, u8, Timings)>,
+}
+
+impl TimingTable {
+    pub fn new() -> TimingTable {
+        TimingTable::default()
+    }
+
+    pub fn push(&mut self, year: u16, day: u8, timings: Timings) {
+        self.rows.push((year, day, timings));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn total(&self) -> Timings {
+        let mut total = Timings::default();
+        for (_, _, timings) in &self.rows {
+            total += *timings;
+        }
+
+        total
+    }
+}
+
+// The days, which parse the input inside each part, have no parse time
+fn write_row(f: &mut fmt::Formatter<'_>, year: &str, day: &str, timings: &Timings) -> fmt::Result {
+    let parse = match timings.parse {
+        Some(parse) => Elapsed(parse).to_string(),
+        None => String::from("n/a"),
+    };
+
+    writeln!(
+        f,
+        "{:>5} {:>3} {:>10} {:>10} {:>10} {:>10}",
+        year,
+        day,
+        parse,
+        Elapsed(timings.part1),
+        Elapsed(timings.part2),
+        Elapsed(timings.total())
+    )
+}
+
+impl Display for TimingTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>5} {:>3} {:>10} {:>10} {:>10} {:>10}",
+            "Year", "Day", "Parse", "Part 1", "Part 2", "Total"
+        )?;
+
+        for (year, day, timings) in &self.rows {
+            write_row(
+                f,
+                year.to_string().as_str(),
+                day.to_string().as_str(),
+                timings,
+            )?;
+        }
+
+        write_row(f, "Total", "", &self.total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_table() {
+        let mut table = TimingTable::new();
+        assert!(table.is_empty());
+
+        table.push(
+            2015,
+            4,
+            Timings {
+                parse: None,
+                part1: Duration::from_millis(95),
+                part2: Duration::from_millis(2_790),
+            },
+        );
+        table.push(
+            2020,
+            9,
+            Timings {
+                parse: Some(Duration::from_micros(40)),
+                part1: Duration::from_micros(300),
+                part2: Duration::from_micros(660),
+            },
+        );
+
+        assert_eq!(table.total().total(), Duration::from_millis(2_886));
+        assert_eq!(
+            table.to_string(),
+            [
+                " Year Day      Parse     Part 1     Part 2      Total",
+                " 2015   4        n/a   95.00 ms     2.79 s     2.88 s",
+                " 2020   9    40.0 µs   300.0 µs   660.0 µs    1.00 ms",
+                "Total        40.0 µs   95.30 ms     2.79 s     2.89 s\n",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use crate::{around, Grid, Neighbourhood, SparseGrid};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::iter::once;
+
+// The transition of a Life-style puzzle: the next state of a cell depends
+// on the cell and on how many of its neighbours are alive
+pub trait Rule<T> {
+    fn is_alive(&self, cell: &T) -> bool;
+
+    fn next(&self, cell: &T, alive_neighbours: usize) -> T;
+}
+
+// The neighbour counts which give birth to a dead cell and keep a live one alive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Life {
+    pub born: &'static [usize],
+    pub survives: &'static [usize],
+}
+
+impl Life {
+    // B3/S23, e.g. the lights of 2015 day 18 and the cubes of 2020 day 17
+    pub const CONWAY: Life = Life {
+        born: &[3],
+        survives: &[2, 3],
+    };
+}
+
+impl Rule<bool> for Life {
+    fn is_alive(&self, cell: &bool) -> bool {
+        *cell
+    }
+
+    fn next(&self, cell: &bool, alive_neighbours: usize) -> bool {
+        match cell {
+            true => self.survives.contains(&alive_neighbours),
+            false => self.born.contains(&alive_neighbours),
+        }
+    }
+}
+
+// How a cell of a dense grid finds its neighbours
+pub enum Neighbours<T> {
+    // The cells next to it
+    Adjacent(Neighbourhood),
+    // The first cell in every direction, which can't be seen through,
+    // e.g. the seats of 2020 day 11 behind the floor
+    Visible(Neighbourhood, fn(&T) -> bool),
+}
+
+// Where the cells of an automaton live
+pub trait Space: Clone + PartialEq + Hash {
+    type Cell;
+    type Neighbours;
+
+    fn next_generation<R: Rule<Self::Cell>>(&self, neighbours: &Self::Neighbours, rule: &R)
+        -> Self;
+
+    fn population<R: Rule<Self::Cell>>(&self, rule: &R) -> usize;
+}
+
+impl<T: Clone + PartialEq + Hash> Space for Grid<T> {
+    type Cell = T;
+    type Neighbours = Neighbours<T>;
+
+    fn next_generation<R: Rule<T>>(&self, neighbours: &Neighbours<T>, rule: &R) -> Grid<T> {
+        self.map(|position, cell| {
+            let alive = match neighbours {
+                Neighbours::Adjacent(neighbourhood) => self
+                    .neighbours(position, *neighbourhood)
+                    .filter(|&neighbour| rule.is_alive(neighbour))
+                    .count(),
+                Neighbours::Visible(neighbourhood, see_through) => self
+                    .line_of_sight(position, *neighbourhood, see_through)
+                    .filter(|&neighbour| rule.is_alive(neighbour))
+                    .count(),
+            };
+
+            rule.next(cell, alive)
+        })
+    }
+
+    fn population<R: Rule<T>>(&self, rule: &R) -> usize {
+        self.count(|cell| rule.is_alive(cell))
+    }
+}
+
+// The neighbours are all points around; the background must stay dead
+// next to dead cells, otherwise the grid would fill the whole space
+impl<T: Clone + Default + PartialEq + Hash, const N: usize> Space for SparseGrid<T, N> {
+    type Cell = T;
+    type Neighbours = ();
+
+    fn next_generation<R: Rule<T>>(&self, _: &(), rule: &R) -> SparseGrid<T, N> {
+        let background = T::default();
+        let mut next = SparseGrid::new();
+
+        // Only the stored cells and their neighbours can change
+        let candidates: HashSet<[i64; N]> = self
+            .points()
+            .flat_map(|&point| around(point).chain(once(point)))
+            .collect();
+
+        for point in candidates {
+            let alive = self
+                .neighbours(point)
+                .filter(|&neighbour| rule.is_alive(neighbour))
+                .count();
+            let cell = rule.next(self.get(&point).unwrap_or(&background), alive);
+
+            if cell != background {
+                next.insert(point, cell);
+            }
+        }
+
+        next
+    }
+
+    fn population<R: Rule<T>>(&self, rule: &R) -> usize {
+        self.iter().filter(|(_, cell)| rule.is_alive(cell)).count()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    // The state doesn't change any more since the generation
+    Stable { generation: usize },
+    // The state of the generation start comes back every length generations
+    Cycle { start: usize, length: usize },
+}
+
+// Runs the generations of a space and keeps the population of every one
+pub struct Automaton<S: Space, R> {
+    state: S,
+    neighbours: S::Neighbours,
+    rule: R,
+    pinned: Option<fn(&mut S)>,
+    generation: usize,
+    populations: Vec<usize>,
+}
+
+impl<S: Space, R: Rule<S::Cell>> Automaton<S, R> {
+    pub fn new(state: S, neighbours: S::Neighbours, rule: R) -> Automaton<S, R> {
+        let populations = vec![state.population(&rule)];
+
+        Automaton {
+            state,
+            neighbours,
+            rule,
+            pinned: None,
+            generation: 0,
+            populations,
+        }
+    }
+
+    // Cells which the rule doesn't decide, e.g. the stuck corner lights of
+    // 2015 day 18; they are set now and after every generation
+    pub fn with_pinned(mut self, pin: fn(&mut S)) -> Automaton<S, R> {
+        pin(&mut self.state);
+        self.pinned = Some(pin);
+        self.count_population();
+        self
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    // The population of the current generation is counted again
+    pub fn update<F: FnOnce(&mut S)>(&mut self, f: F) {
+        f(&mut self.state);
+        self.count_population();
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn population(&self) -> usize {
+        self.populations[self.generation]
+    }
+
+    // The population of every generation so far, the first one included
+    pub fn populations(&self) -> &[usize] {
+        &self.populations
+    }
+
+    fn count_population(&mut self) {
+        self.populations[self.generation] = self.state.population(&self.rule);
+    }
+
+    // Returns whether the state has changed
+    pub fn step(&mut self) -> bool {
+        let mut next = self.state.next_generation(&self.neighbours, &self.rule);
+        if let Some(pin) = self.pinned {
+            pin(&mut next);
+        }
+
+        let changed = next != self.state;
+        self.state = next;
+        self.generation += 1;
+        self.populations.push(self.state.population(&self.rule));
+
+        changed
+    }
+
+    // Returns the population after the generations
+    pub fn run(&mut self, generations: usize) -> usize {
+        for _ in 0..generations {
+            self.step();
+        }
+
+        self.population()
+    }
+
+    // Runs until a state comes back, at most limit generations; the automaton
+    // stops at the first repeated state, e.g. one after the stable generation.
+    // The hashes only find the candidates, the states themselves are compared
+    pub fn run_until_repeat(&mut self, limit: usize) -> Option<Outcome> {
+        // The states since the first generation
+        let first = self.generation;
+        let mut history = vec![self.state.clone()];
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+        seen.insert(fingerprint(&self.state), vec![self.generation]);
+
+        for _ in 0..limit {
+            if !self.step() {
+                return Some(Outcome::Stable {
+                    generation: self.generation - 1,
+                });
+            }
+
+            let generations = seen.entry(fingerprint(&self.state)).or_default();
+            if let Some(&start) = generations
+                .iter()
+                .find(|&&generation| history[generation - first] == self.state)
+            {
+                return Some(Outcome::Cycle {
+                    start,
+                    length: self.generation - start,
+                });
+            }
+
+            generations.push(self.generation);
+            history.push(self.state.clone());
+        }
+
+        None
+    }
+}
+
+fn fingerprint<S: Hash>(state: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Edges;
+
+    fn life(input: &str) -> Automaton<Grid<bool>, Life> {
+        Automaton::new(
+            Grid::parse(input).unwrap(),
+            Neighbours::Adjacent(Neighbourhood::Moore),
+            Life::CONWAY,
+        )
+    }
+
+    #[test]
+    fn test_life_rule() {
+        assert!(Life::CONWAY.next(&false, 3));
+        assert!(!Life::CONWAY.next(&false, 2));
+        assert!(Life::CONWAY.next(&true, 2));
+        assert!(!Life::CONWAY.next(&true, 4));
+        assert!(Life::CONWAY.is_alive(&true));
+    }
+
+    #[test]
+    fn test_step() {
+        let mut blinker = life(".....\n..#..\n..#..\n..#..\n.....");
+
+        assert!(blinker.step());
+        assert_eq!(
+            blinker.state().to_string(),
+            ".....\n.....\n.###.\n.....\n....."
+        );
+        assert_eq!(blinker.generation(), 1);
+        assert_eq!(blinker.population(), 3);
+
+        let mut block = life("....\n.##.\n.##.\n....");
+        assert!(!block.step());
+    }
+
+    #[test]
+    fn test_run() {
+        let mut automaton = life(".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..");
+
+        assert_eq!(automaton.run(4), 4);
+        assert_eq!(automaton.populations(), &[15, 11, 8, 4, 4]);
+        assert_eq!(
+            automaton.run_until_repeat(10),
+            Some(Outcome::Stable { generation: 4 })
+        );
+        assert_eq!(automaton.generation(), 5);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut blinker = life(".....\n..#..\n..#..\n..#..\n.....");
+        assert_eq!(
+            blinker.run_until_repeat(10),
+            Some(Outcome::Cycle {
+                start: 0,
+                length: 2
+            })
+        );
+
+        // A glider comes back to its place after it has crossed the grid
+        let glider = Grid::parse(".#....\n..#...\n###...\n......\n......\n......").unwrap();
+        let mut automaton = Automaton::new(
+            glider.with_edges(Edges::Wrapping),
+            Neighbours::Adjacent(Neighbourhood::Moore),
+            Life::CONWAY,
+        );
+        assert_eq!(
+            automaton.run_until_repeat(100),
+            Some(Outcome::Cycle {
+                start: 0,
+                length: 24
+            })
+        );
+        assert!(automaton.populations().iter().all(|&count| count == 5));
+
+        let mut short = life(".#....\n..#...\n###...\n......\n......\n......");
+        assert_eq!(short.run_until_repeat(3), None);
+    }
+
+    #[test]
+    fn test_colliding_hashes() {
+        // A counter modulo 3, whose states all have the same hash
+        #[derive(Clone, PartialEq)]
+        struct Counter(usize);
+        impl Hash for Counter {
+            fn hash<H: Hasher>(&self, _: &mut H) {}
+        }
+        impl Space for Counter {
+            type Cell = usize;
+            type Neighbours = ();
+
+            fn next_generation<R: Rule<usize>>(&self, _: &(), _: &R) -> Counter {
+                Counter((self.0 + 1) % 3)
+            }
+
+            fn population<R: Rule<usize>>(&self, _: &R) -> usize {
+                self.0
+            }
+        }
+        struct Count;
+        impl Rule<usize> for Count {
+            fn is_alive(&self, _: &usize) -> bool {
+                true
+            }
+
+            fn next(&self, cell: &usize, _: usize) -> usize {
+                *cell
+            }
+        }
+
+        let mut automaton = Automaton::new(Counter(0), (), Count);
+        assert_eq!(
+            automaton.run_until_repeat(10),
+            Some(Outcome::Cycle {
+                start: 0,
+                length: 3
+            })
+        );
+        assert_eq!(automaton.populations(), &[0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn test_pinned() {
+        let corners = |grid: &mut Grid<bool>| {
+            grid[(0, 0)] = true;
+            grid[(2, 2)] = true;
+        };
+        let mut automaton = life("...\n...\n...").with_pinned(corners);
+        assert_eq!(automaton.population(), 2);
+        assert_eq!(automaton.run(3), 2);
+
+        automaton.update(|grid| grid[(1, 1)] = true);
+        assert_eq!(automaton.population(), 3);
+        assert_eq!(automaton.run(1), 3);
+    }
+
+    #[test]
+    fn test_visible_neighbours() {
+        // A live cell survives only if it sees no other one
+        struct Lonely;
+        impl Rule<bool> for Lonely {
+            fn is_alive(&self, cell: &bool) -> bool {
+                *cell
+            }
+
+            fn next(&self, cell: &bool, alive_neighbours: usize) -> bool {
+                *cell && alive_neighbours == 0
+            }
+        }
+
+        let grid = Grid::parse("#...#\n.....\n..#..").unwrap();
+        let mut adjacent = Automaton::new(
+            grid.clone(),
+            Neighbours::Adjacent(Neighbourhood::Orthogonal),
+            Lonely,
+        );
+        let mut visible = Automaton::new(
+            grid,
+            Neighbours::Visible(Neighbourhood::Orthogonal, |&cell| !cell),
+            Lonely,
+        );
+
+        assert_eq!(adjacent.run(1), 3);
+        assert_eq!(visible.run(1), 1);
+    }
+
+    #[test]
+    fn test_sparse() {
+        let cubes: SparseGrid<bool, 3> = SparseGrid::parse(".#.\n..#\n###").unwrap();
+        let mut automaton = Automaton::new(cubes, (), Life::CONWAY);
+
+        automaton.step();
+        assert_eq!(automaton.population(), 11);
+        assert!(automaton.state().contains(&[0, 1, -1]));
+        assert!(!automaton.state().contains(&[1, 1, 0]));
+        assert_eq!(automaton.run(5), 112);
+
+        let block: SparseGrid<bool, 2> = SparseGrid::parse("##\n##").unwrap();
+        let mut automaton = Automaton::new(block, (), Life::CONWAY);
+        assert_eq!(
+            automaton.run_until_repeat(10),
+            Some(Outcome::Stable { generation: 0 })
+        );
+    }
+}
@@ -0,0 +1,44 @@
+// A cell, which is read from the puzzle input and printed back the same way
+pub trait Cell: Sized {
+    // For the parse errors, e.g. "# or ."
+    const EXPECTED: &'static str;
+
+    fn from_char(c: char) -> Option<Self>;
+
+    fn to_char(&self) -> char;
+}
+
+// The usual light, cube or tree: # is on and . is off
+impl Cell for bool {
+    const EXPECTED: &'static str = "# or .";
+
+    fn from_char(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bool_cell() {
+        assert_eq!(bool::from_char('#'), Some(true));
+        assert_eq!(bool::from_char('.'), Some(false));
+        assert_eq!(bool::from_char('L'), None);
+        assert_eq!(true.to_char(), '#');
+        assert_eq!(false.to_char(), '.');
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{Cell, Neighbourhood};
+use advparse::{parse_grid, ParseError};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// What is behind the last row or column
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Edges {
+    // Nothing, e.g. the lights of 2015 day 18
+    Bounded,
+    // The grid repeats, e.g. the trees of 2020 day 3
+    Wrapping,
+}
+
+// A rectangle of cells; x is the column and y is the row
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    edges: Edges,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![cell; width * height],
+            edges: Edges::Bounded,
+        }
+    }
+
+    // The rows must have the same width, parse_grid checks it
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "the rows of a grid must have the same width"
+        );
+
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+            edges: Edges::Bounded,
+        }
+    }
+
+    pub fn with_edges(self, edges: Edges) -> Grid<T> {
+        Grid { edges, ..self }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn edges(&self) -> Edges {
+        self.edges
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    // Any position, also outside of the grid; it wraps with Edges::Wrapping
+    pub fn at(&self, x: isize, y: isize) -> Option<&T> {
+        let (x, y) = self.position(x, y)?;
+        self.get(x, y)
+    }
+
+    // The position after a step, or None behind a bounded edge
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        self.position(x as isize + dx, y as isize + dy)
+    }
+
+    fn position(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+
+        match self.edges {
+            Edges::Bounded => match (usize::try_from(x), usize::try_from(y)) {
+                (Ok(x), Ok(y)) if x < self.width && y < self.height => Some((x, y)),
+                _ => None,
+            },
+            Edges::Wrapping => Some((
+                x.rem_euclid(self.width as isize) as usize,
+                y.rem_euclid(self.height as isize) as usize,
+            )),
+        }
+    }
+
+    pub fn neighbours(
+        &self,
+        position: (usize, usize),
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = &T> + '_ {
+        neighbourhood
+            .offsets()
+            .iter()
+            .filter_map(move |&offset| self.step(position, offset))
+            .map(move |(x, y)| &self[(x, y)])
+    }
+
+    // The first cell in every direction, which can't be seen through,
+    // e.g. count_visible_neighbours of 2020 day 11 skips the floor
+    pub fn line_of_sight<'a, F>(
+        &'a self,
+        position: (usize, usize),
+        neighbourhood: Neighbourhood,
+        see_through: F,
+    ) -> impl Iterator<Item = &'a T> + 'a
+    where
+        F: Fn(&T) -> bool + 'a,
+    {
+        neighbourhood.offsets().iter().filter_map(move |&offset| {
+            let mut current = self.step(position, offset)?;
+
+            // A wrapping grid is looked around only once
+            while current != position {
+                let cell = &self[current];
+                if !see_through(cell) {
+                    return Some(cell);
+                }
+                current = self.step(current, offset)?;
+            }
+
+            None
+        })
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| (i % width, i / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks doesn't accept 0
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn count<F: Fn(&T) -> bool>(&self, predicate: F) -> usize {
+        self.cells.iter().filter(|&cell| predicate(cell)).count()
+    }
+
+    pub fn fill(&mut self, cell: T)
+    where
+        T: Clone,
+    {
+        self.cells.fill(cell);
+    }
+
+    // A grid of the same size and edges, e.g. the next generation
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut((usize, usize), &T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .iter()
+                .map(|(position, cell)| f(position, cell))
+                .collect(),
+            edges: self.edges,
+        }
+    }
+}
+
+impl<T: Cell> Grid<T> {
+    pub fn parse(input: &str) -> Result<Grid<T>, ParseError> {
+        let rows = parse_grid(input, T::EXPECTED, T::from_char)?;
+
+        Ok(Grid::from_rows(rows))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the grid", x, y))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the grid", x, y))
+    }
+}
+
+// The same text as in the input, so Grid::parse reads it back
+impl<T: Cell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell.to_char())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = ".#.\n..#\n###";
+
+    #[test]
+    fn test_new() {
+        let grid = Grid::new(3, 2, 7u8);
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.edges(), Edges::Bounded);
+        assert_eq!(grid.count(|&cell| cell == 7), 6);
+        assert_eq!(grid.get(2, 1), Some(&7));
+        assert_eq!(grid.get(3, 1), None);
+        assert_eq!(grid.get(0, 2), None);
+    }
+
+    #[test]
+    fn test_parse_display() {
+        let grid: Grid<bool> = Grid::parse(INPUT).unwrap();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert!(grid[(1, 0)]);
+        assert!(!grid[(0, 1)]);
+        assert_eq!(grid.to_string(), INPUT);
+        assert_eq!(Grid::<bool>::parse(&grid.to_string()), Ok(grid));
+
+        assert!(Grid::<bool>::parse(".#\n.L").is_err());
+        assert_eq!(Grid::<bool>::parse("").unwrap().to_string(), "");
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut grid = Grid::new(2, 2, false);
+        grid[(1, 0)] = true;
+        *grid.get_mut(0, 1).unwrap() = true;
+
+        assert_eq!(grid.to_string(), ".#\n#.");
+        assert!(grid.get_mut(2, 0).is_none());
+
+        grid.fill(false);
+        assert_eq!(grid.count(|&cell| cell), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_outside() {
+        let grid = Grid::new(2, 2, false);
+        let _ = grid[(2, 2)];
+    }
+
+    #[test]
+    fn test_at() {
+        let grid: Grid<bool> = Grid::parse(INPUT).unwrap();
+        assert_eq!(grid.at(-1, 0), None);
+        assert_eq!(grid.at(1, 0), Some(&true));
+        assert_eq!(grid.at(4, 0), None);
+
+        let grid = grid.with_edges(Edges::Wrapping);
+        assert_eq!(grid.at(-1, 0), Some(&false));
+        assert_eq!(grid.at(4, 0), Some(&true));
+        assert_eq!(grid.at(4, -3), Some(&true));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid: Grid<bool> = Grid::parse(INPUT).unwrap();
+
+        let count = |grid: &Grid<bool>, position, neighbourhood| {
+            grid.neighbours(position, neighbourhood)
+                .filter(|&&cell| cell)
+                .count()
+        };
+        assert_eq!(count(&grid, (1, 1), Neighbourhood::Moore), 5);
+        assert_eq!(count(&grid, (1, 1), Neighbourhood::Orthogonal), 3);
+        assert_eq!(count(&grid, (1, 1), Neighbourhood::Diagonal), 2);
+        assert_eq!(grid.neighbours((0, 0), Neighbourhood::Moore).count(), 3);
+
+        let grid = grid.with_edges(Edges::Wrapping);
+        assert_eq!(grid.neighbours((0, 0), Neighbourhood::Moore).count(), 8);
+        assert_eq!(count(&grid, (0, 0), Neighbourhood::Moore), 5);
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        let grid: Grid<bool> = Grid::parse("#...#\n.....\n#.#..\n.....\n....#").unwrap();
+
+        let seen = grid
+            .line_of_sight((0, 0), Neighbourhood::Moore, |&cell| !cell)
+            .count();
+        assert_eq!(seen, 3);
+
+        let seen = grid
+            .line_of_sight((2, 2), Neighbourhood::Orthogonal, |&cell| !cell)
+            .count();
+        assert_eq!(seen, 1);
+
+        let grid = grid.with_edges(Edges::Wrapping);
+        let seen = grid
+            .line_of_sight((2, 2), Neighbourhood::Orthogonal, |&cell| !cell)
+            .count();
+        assert_eq!(seen, 2);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid: Grid<bool> = Grid::parse(INPUT).unwrap();
+        let flipped = grid.map(|_, &cell| !cell);
+        assert_eq!(flipped.to_string(), "#.#\n##.\n...");
+
+        let positions = grid.map(|position, _| position);
+        assert_eq!(positions[(2, 1)], (2, 1));
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, &cell)| cell)
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]
+        );
+    }
+}
@@ -0,0 +1,13 @@
+mod automaton;
+mod cell;
+mod dense;
+mod neighbours;
+mod orientation;
+mod sparse;
+
+pub use automaton::{Automaton, Life, Neighbours, Outcome, Rule, Space};
+pub use cell::Cell;
+pub use dense::{Edges, Grid};
+pub use neighbours::Neighbourhood;
+pub use orientation::Orientation;
+pub use sparse::{around, SparseGrid};
@@ -0,0 +1,52 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    // Up, down, left and right
+    Orthogonal,
+    // The four corners
+    Diagonal,
+    // All eight cells around
+    Moore,
+}
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const DIAGONAL: [(isize, isize); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl Neighbourhood {
+    // The steps (dx, dy) to the neighbours; y grows downwards like the rows of the input
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Orthogonal => &ORTHOGONAL,
+            Neighbourhood::Diagonal => &DIAGONAL,
+            Neighbourhood::Moore => &MOORE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(Neighbourhood::Orthogonal.offsets().len(), 4);
+        assert_eq!(Neighbourhood::Diagonal.offsets().len(), 4);
+        assert_eq!(Neighbourhood::Moore.offsets().len(), 8);
+
+        assert!(Neighbourhood::Orthogonal
+            .offsets()
+            .iter()
+            .chain(Neighbourhood::Diagonal.offsets())
+            .all(|offset| Neighbourhood::Moore.offsets().contains(offset)));
+        assert!(!Neighbourhood::Moore.offsets().contains(&(0, 0)));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::Grid;
+
+// One of the eight ways to lay a square on the table, e.g. a tile of 2020 day 20:
+// the grid is flipped left to right first and then turned clockwise
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub flipped: bool,
+    // Quarter turns, 0-3
+    pub turns: u8,
+}
+
+impl Orientation {
+    pub const IDENTITY: Orientation = Orientation {
+        flipped: false,
+        turns: 0,
+    };
+
+    pub fn all() -> impl Iterator<Item = Orientation> {
+        [false, true]
+            .into_iter()
+            .flat_map(|flipped| (0..4).map(move |turns| Orientation { flipped, turns }))
+    }
+
+    // Where the cell of a grid of the size comes to
+    pub fn apply(&self, (x, y): (usize, usize), (width, height): (usize, usize)) -> (usize, usize) {
+        let (mut x, mut y) = (x, y);
+        let (mut width, mut height) = (width, height);
+
+        if self.flipped {
+            x = width - 1 - x;
+        }
+        for _ in 0..self.turns % 4 {
+            (x, y) = (height - 1 - y, x);
+            (width, height) = (height, width);
+        }
+
+        (x, y)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Left to right
+    pub fn flipped(&self) -> Grid<T> {
+        let rows = self
+            .rows()
+            .map(|row| row.iter().rev().cloned().collect())
+            .collect();
+
+        Grid::from_rows(rows).with_edges(self.edges())
+    }
+
+    // A quarter turn clockwise: the first column, from the bottom up, becomes the first row
+    pub fn rotated(&self) -> Grid<T> {
+        let rows = (0..self.width())
+            .map(|x| {
+                (0..self.height())
+                    .rev()
+                    .map(|y| self[(x, y)].clone())
+                    .collect()
+            })
+            .collect();
+
+        Grid::from_rows(rows).with_edges(self.edges())
+    }
+
+    pub fn oriented(&self, orientation: Orientation) -> Grid<T> {
+        let mut grid = match orientation.flipped {
+            true => self.flipped(),
+            false => self.clone(),
+        };
+        for _ in 0..orientation.turns % 4 {
+            grid = grid.rotated();
+        }
+
+        grid
+    }
+
+    // The grid in all eight orientations, in the order of Orientation::all
+    pub fn orientations(&self) -> impl Iterator<Item = (Orientation, Grid<T>)> + '_ {
+        Orientation::all().map(move |orientation| (orientation, self.oriented(orientation)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Edges;
+    use std::collections::HashSet;
+
+    fn grid(input: &str) -> Grid<bool> {
+        Grid::parse(input).unwrap()
+    }
+
+    #[test]
+    fn test_flipped() {
+        assert_eq!(grid("#..\n##.").flipped(), grid("..#\n.##"));
+    }
+
+    #[test]
+    fn test_rotated() {
+        let rotated = grid("#..\n##.").with_edges(Edges::Wrapping).rotated();
+
+        assert_eq!(rotated, grid("##\n#.\n..").with_edges(Edges::Wrapping));
+        assert_eq!(rotated.rotated().rotated().rotated().rotated(), rotated);
+    }
+
+    #[test]
+    fn test_orientations() {
+        let l = grid("#..\n#..\n##.");
+        let orientations: Vec<(Orientation, Grid<bool>)> = l.orientations().collect();
+
+        assert_eq!(orientations.len(), 8);
+        assert_eq!(orientations[0], (Orientation::IDENTITY, l.clone()));
+        // The L isn't symmetric, so every orientation is different
+        assert_eq!(
+            orientations
+                .iter()
+                .map(|(_, grid)| grid.clone())
+                .collect::<HashSet<_>>()
+                .len(),
+            8
+        );
+
+        // The flip and the half turn are the same as the flip upside down
+        let upside_down = Orientation {
+            flipped: true,
+            turns: 2,
+        };
+        assert_eq!(l.oriented(upside_down), grid("##.\n#..\n#.."));
+    }
+
+    #[test]
+    fn test_apply() {
+        let l = grid("#..\n#..\n##.\n...");
+        let size = (l.width(), l.height());
+
+        for (orientation, oriented) in l.orientations() {
+            for ((x, y), cell) in l.iter() {
+                let (x, y) = orientation.apply((x, y), size);
+                assert_eq!(oriented[(x, y)], *cell, "{:?}", orientation);
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use crate::Cell;
+use advparse::{parse_grid, ParseError};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+// Cells at any point of N dimensions, e.g. the 4D cubes of 2020 day 17;
+// only the cells different from the default one (the background) are stored
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T, const N: usize> {
+    cells: HashMap<[i64; N], T>,
+}
+
+impl<T, const N: usize> Default for SparseGrid<T, N> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+// The same cells give the same hash, whatever the order of the map
+impl<T: Hash, const N: usize> Hash for SparseGrid<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut cells: Vec<_> = self.cells.iter().collect();
+        cells.sort_unstable_by_key(|&(point, _)| point);
+        cells.hash(state);
+    }
+}
+
+// All points around, the diagonal ones too (3^N - 1)
+pub fn around<const N: usize>(point: [i64; N]) -> impl Iterator<Item = [i64; N]> {
+    (0..3usize.pow(N as u32))
+        .map(move |mut index| {
+            let mut neighbour = point;
+            for coordinate in neighbour.iter_mut() {
+                *coordinate += (index % 3) as i64 - 1;
+                index /= 3;
+            }
+
+            neighbour
+        })
+        .filter(move |&neighbour| neighbour != point)
+}
+
+impl<T, const N: usize> SparseGrid<T, N> {
+    pub fn new() -> SparseGrid<T, N> {
+        SparseGrid::default()
+    }
+
+    pub fn get(&self, point: &[i64; N]) -> Option<&T> {
+        self.cells.get(point)
+    }
+
+    pub fn contains(&self, point: &[i64; N]) -> bool {
+        self.cells.contains_key(point)
+    }
+
+    pub fn insert(&mut self, point: [i64; N], cell: T) -> Option<T> {
+        self.cells.insert(point, cell)
+    }
+
+    pub fn remove(&mut self, point: &[i64; N]) -> Option<T> {
+        self.cells.remove(point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&[i64; N], &T)> {
+        self.cells.iter()
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = &[i64; N]> {
+        self.cells.keys()
+    }
+
+    // The stored cells around the point
+    pub fn neighbours(&self, point: [i64; N]) -> impl Iterator<Item = &T> + '_ {
+        around(point).filter_map(move |neighbour| self.cells.get(&neighbour))
+    }
+
+    // The lowest and the highest coordinates of the stored cells
+    pub fn bounds(&self) -> Option<([i64; N], [i64; N])> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+
+        Some(points.fold((first, first), |(mut low, mut high), point| {
+            for axis in 0..N {
+                low[axis] = low[axis].min(point[axis]);
+                high[axis] = high[axis].max(point[axis]);
+            }
+
+            (low, high)
+        }))
+    }
+}
+
+impl<T: Cell + Default + PartialEq, const N: usize> SparseGrid<T, N> {
+    // A 2D slice at x and y, the other coordinates are 0
+    pub fn parse(input: &str) -> Result<SparseGrid<T, N>, ParseError> {
+        assert!(N >= 2, "a parsed grid has at least two dimensions");
+
+        let background = T::default();
+        let mut grid = SparseGrid::new();
+
+        for (y, row) in parse_grid(input, T::EXPECTED, T::from_char)?
+            .into_iter()
+            .enumerate()
+        {
+            for (x, cell) in row.into_iter().enumerate() {
+                if cell != background {
+                    let mut point = [0; N];
+                    point[0] = x as i64;
+                    point[1] = y as i64;
+                    grid.insert(point, cell);
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+fn axis_name(axis: usize) -> String {
+    match axis {
+        0 => String::from("x"),
+        1 => String::from("y"),
+        2 => String::from("z"),
+        3 => String::from("w"),
+        _ => format!("d{}", axis),
+    }
+}
+
+// The slices of x and y inside the bounds; with more dimensions every slice
+// starts with its coordinates, e.g. "z=-1, w=0"
+impl<T: Cell + Default, const N: usize> fmt::Display for SparseGrid<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (low, high) = match self.bounds() {
+            Some(bounds) if N >= 2 => bounds,
+            _ => return Ok(()),
+        };
+        let background = T::default();
+
+        // The coordinates after x and y, counted like an odometer
+        let mut slice = low;
+        loop {
+            if N > 2 {
+                let header: Vec<String> = (2..N)
+                    .map(|axis| format!("{}={}", axis_name(axis), slice[axis]))
+                    .collect();
+                writeln!(f, "{}", header.join(", "))?;
+            }
+
+            for y in low[1]..=high[1] {
+                for x in low[0]..=high[0] {
+                    let mut point = slice;
+                    point[0] = x;
+                    point[1] = y;
+                    let cell = self.cells.get(&point).unwrap_or(&background);
+                    write!(f, "{}", cell.to_char())?;
+                }
+                writeln!(f)?;
+            }
+
+            match (2..N).find(|&axis| slice[axis] < high[axis]) {
+                Some(axis) => {
+                    slice[axis] += 1;
+                    slice[2..axis].copy_from_slice(&low[2..axis]);
+                    writeln!(f)?;
+                }
+                None => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = ".#.\n..#\n###";
+
+    #[test]
+    fn test_around() {
+        assert_eq!(around([0, 0]).count(), 8);
+        assert_eq!(around([0, 0, 0]).count(), 26);
+        assert_eq!(around([5, 5, 5, 5]).count(), 80);
+        assert!(around([1, 2, 3]).all(|p| p != [1, 2, 3]));
+        assert!(around([1, 2, 3]).any(|p| p == [0, 3, 2]));
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut grid: SparseGrid<bool, 3> = SparseGrid::new();
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert([0, 0, 0], true);
+        grid.insert([1, -2, 3], true);
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(&[1, -2, 3]));
+        assert_eq!(grid.bounds(), Some(([0, -2, 0], [1, 0, 3])));
+
+        grid.remove(&[1, -2, 3]);
+        assert!(!grid.contains(&[1, -2, 3]));
+        assert_eq!(grid.bounds(), Some(([0, 0, 0], [0, 0, 0])));
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid: SparseGrid<bool, 4> = SparseGrid::parse(INPUT).unwrap();
+
+        assert_eq!(grid.len(), 5);
+        assert!(grid.contains(&[1, 0, 0, 0]));
+        assert!(!grid.contains(&[0, 0, 0, 0]));
+        assert_eq!(grid.neighbours([1, 1, 0, 0]).count(), 5);
+        assert_eq!(grid.neighbours([1, 1, 1, 0]).count(), 5);
+        assert_eq!(grid.neighbours([1, 1, 2, 0]).count(), 0);
+
+        assert!(SparseGrid::<bool, 2>::parse(".#\nx.").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let grid: SparseGrid<bool, 2> = SparseGrid::parse(INPUT).unwrap();
+        assert_eq!(grid.to_string(), format!("{}\n", INPUT));
+        assert_eq!(SparseGrid::parse(&grid.to_string()), Ok(grid));
+
+        let mut grid: SparseGrid<bool, 3> = SparseGrid::new();
+        grid.insert([0, 0, -1], true);
+        grid.insert([1, 1, 0], true);
+        assert_eq!(grid.to_string(), "z=-1\n#.\n..\n\nz=0\n..\n.#\n");
+
+        let mut grid: SparseGrid<bool, 4> = SparseGrid::new();
+        grid.insert([0, 0, 0, 0], true);
+        grid.insert([0, 0, 1, 1], true);
+        assert_eq!(
+            grid.to_string(),
+            "z=0, w=0\n#\n\nz=1, w=0\n.\n\nz=0, w=1\n.\n\nz=1, w=1\n#\n"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::error::Error;
+use std::fmt;
+
+// A malformed line of the input, or an input without a solution (then the line is 0)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new<R: Into<String>>(line: usize, column: usize, text: &str, reason: R) -> ParseError {
+        ParseError {
+            line,
+            column,
+            text: String::from(text),
+            reason: reason.into(),
+        }
+    }
+
+    // The token must be a part of the line; the line number is set later by at_line
+    pub fn in_line<R: Into<String>>(line: &str, token: &str, reason: R) -> ParseError {
+        let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        let column = match offset.checked_add(token.len()) {
+            Some(end) if end <= line.len() => offset + 1,
+            _ => 1,
+        };
+
+        ParseError::new(0, column, token, reason)
+    }
+
+    // The line ends before the expected token
+    pub fn end_of_line<R: Into<String>>(line: &str, reason: R) -> ParseError {
+        ParseError::in_line(line, &line[line.len()..], reason)
+    }
+
+    pub fn input<R: Into<String>>(reason: R) -> ParseError {
+        ParseError::new(0, 0, "", reason)
+    }
+
+    pub fn at_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 && self.column == 0 {
+            return f.write_str(self.reason.as_str());
+        }
+
+        write!(
+            f,
+            "line {}, column {}: {}, ",
+            self.line, self.column, self.reason
+        )?;
+        if self.text.is_empty() {
+            f.write_str("found the end of the line")
+        } else {
+            write!(f, "found \"{}\"", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_in_line() {
+        let line = "F10 X7";
+        let err = ParseError::in_line(line, &line[4..], "unknown action").at_line(3);
+
+        assert_eq!(err, ParseError::new(3, 5, "X7", "unknown action"));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 5: unknown action, found \"X7\""
+        );
+    }
+
+    #[test]
+    fn test_parse_error_end_of_line() {
+        let err = ParseError::end_of_line("1-3 a:", "expected a password").at_line(1);
+
+        assert_eq!(err.column, 7);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 7: expected a password, found the end of the line"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_foreign_token() {
+        let token = String::from("xyz");
+        let err = ParseError::in_line("abc", token.as_str(), "unexpected");
+        assert_eq!(err.column, 1);
+        assert_eq!(err.text, "xyz");
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{numbered_lines, ParseError};
+
+// A rectangle of characters, e.g. the lights of 2015 day 18:
+// .#.#.#
+// ...##.
+// The rows are trimmed and the blank lines are skipped, every character must become a cell
+pub fn parse_grid<T, F>(input: &str, expected: &str, mut cell: F) -> Result<Vec<Vec<T>>, ParseError>
+where
+    F: FnMut(char) -> Option<T>,
+{
+    let mut grid: Vec<Vec<T>> = Vec::new();
+
+    for (number, line) in numbered_lines(input) {
+        let row = line.trim();
+        if row.is_empty() {
+            continue;
+        }
+
+        let cells = row
+            .char_indices()
+            .map(|(i, c)| {
+                cell(c).ok_or_else(|| {
+                    let token = &row[i..i + c.len_utf8()];
+                    ParseError::in_line(line, token, format!("expected {}", expected))
+                })
+            })
+            .collect::<Result<Vec<T>, ParseError>>()
+            .map_err(|err| err.at_line(number))?;
+
+        if let Some(width) = grid.first().map(Vec::len) {
+            if cells.len() != width {
+                let reason = format!("expected {} cells in the row", width);
+                return Err(ParseError::in_line(line, row, reason).at_line(number));
+            }
+        }
+
+        grid.push(cells);
+    }
+
+    Ok(grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn light(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        assert_eq!(
+            parse_grid(".#\n\n  #.\n", "# or .", light),
+            Ok(vec![vec![false, true], vec![true, false]])
+        );
+        assert_eq!(parse_grid("", "# or .", light), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_wrong_grid() {
+        assert_eq!(
+            parse_grid(".#\n #x", "# or .", light),
+            Err(ParseError::new(2, 3, "x", "expected # or ."))
+        );
+        assert_eq!(
+            parse_grid(".#\n#..", "# or .", light),
+            Err(ParseError::new(2, 1, "#..", "expected 2 cells in the row"))
+        );
+    }
+}
@@ -0,0 +1,13 @@
+mod error;
+mod grid;
+mod lines;
+mod numbers;
+mod records;
+mod tokens;
+
+pub use error::ParseError;
+pub use grid::parse_grid;
+pub use lines::{numbered_lines, parse_lines};
+pub use numbers::{parse_numbers, parse_separated};
+pub use records::{parse_records, records, Record, Records};
+pub use tokens::{expect_token, next_token, parse_number};
@@ -0,0 +1,42 @@
+use crate::ParseError;
+
+// Every line with its number (starting with 1), the blank lines too
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+}
+
+// Parses every non-blank line; the errors get the line number
+pub fn parse_lines<'a, T, F>(input: &'a str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&'a str) -> Result<T, ParseError>,
+{
+    numbered_lines(input)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| parse(line).map_err(|err| err.at_line(number)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_number;
+
+    #[test]
+    fn test_numbered_lines() {
+        let lines: Vec<(usize, &str)> = numbered_lines("a\n\nb\r\nc").collect();
+        assert_eq!(lines, vec![(1, "a"), (2, ""), (3, "b"), (4, "c")]);
+        assert_eq!(numbered_lines("").count(), 0);
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let numbers = parse_lines("1721\n\n  979\n", |line| parse_number::<u32>(line, line));
+        assert_eq!(numbers, Ok(vec![1721, 979]));
+
+        let err = parse_lines("1721\n\n97x9", |line| parse_number::<u32>(line, line));
+        assert_eq!(err, Err(ParseError::new(3, 1, "97x9", "expected a number")));
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{parse_lines, parse_number, ParseError};
+use std::str::FromStr;
+
+// A number per line, e.g. the expenses of 2020 day 1
+pub fn parse_numbers<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    parse_lines(input, |line| parse_number(line, line.trim()))
+}
+
+// The numbers of one line, e.g. "0,3,6" separated by ','
+pub fn parse_separated<T: FromStr>(line: &str, separator: char) -> Result<Vec<T>, ParseError> {
+    line.split(separator)
+        .map(|token| parse_number(line, token.trim()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(
+            parse_numbers::<u32>("1721\n 979\n\n366\n"),
+            Ok(vec![1721, 979, 366])
+        );
+        assert_eq!(parse_numbers::<i8>(""), Ok(vec![]));
+        assert_eq!(
+            parse_numbers::<u8>("12\n 300"),
+            Err(ParseError::new(2, 2, "300", "expected a number"))
+        );
+    }
+
+    #[test]
+    fn test_parse_separated() {
+        assert_eq!(parse_separated::<u8>("0,3,6", ','), Ok(vec![0, 3, 6]));
+        assert_eq!(parse_separated::<u8>("7, 13", ','), Ok(vec![7, 13]));
+        assert_eq!(
+            parse_separated::<u8>("7,,13", ','),
+            Err(ParseError::new(0, 3, "", "expected a number"))
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use crate::ParseError;
+use std::iter::Enumerate;
+use std::str::Lines;
+
+// The lines between the blank lines, e.g. a passport of 2020 day 4:
+// ecl:gry pid:860033327
+// hcl:#fffffd
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Record<'a> {
+    pub line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Record<'a> {
+    // The lines with their numbers in the whole input
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> {
+        let first = self.line;
+        self.text
+            .lines()
+            .enumerate()
+            .map(move |(index, line)| (first + index, line))
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &'a str> {
+        self.text.split_whitespace()
+    }
+
+    // The token must be a part of the record, so the error points to its line
+    pub fn error<R: Into<String>>(&self, token: &str, reason: R) -> ParseError {
+        let (number, line) = self
+            .lines()
+            .find(|(_, line)| contains(line, token))
+            .unwrap_or((self.line, self.text));
+
+        ParseError::in_line(line, token, reason).at_line(number)
+    }
+}
+
+fn contains(line: &str, token: &str) -> bool {
+    let start = line.as_ptr() as usize;
+    let token = token.as_ptr() as usize;
+
+    (start..=start + line.len()).contains(&token)
+}
+
+pub struct Records<'a> {
+    input: &'a str,
+    lines: Enumerate<Lines<'a>>,
+}
+
+pub fn records(input: &str) -> Records<'_> {
+    Records {
+        input,
+        lines: input.lines().enumerate(),
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Record<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, first) = self.lines.find(|(_, line)| !line.trim().is_empty())?;
+
+        let mut last = first;
+        for (_, line) in self.lines.by_ref() {
+            if line.trim().is_empty() {
+                break;
+            }
+            last = line;
+        }
+
+        let start = first.as_ptr() as usize - self.input.as_ptr() as usize;
+        let end = last.as_ptr() as usize - self.input.as_ptr() as usize + last.len();
+
+        Some(Record {
+            line: index + 1,
+            text: &self.input[start..end],
+        })
+    }
+}
+
+pub fn parse_records<'a, T, F>(input: &'a str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(Record<'a>) -> Result<T, ParseError>,
+{
+    records(input).map(parse).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\n\na\nb\nc\n\n\nab\r\nac\n";
+
+    #[test]
+    fn test_records() {
+        let records: Vec<Record> = records(INPUT).collect();
+
+        assert_eq!(
+            records,
+            vec![
+                Record {
+                    line: 1,
+                    text: "abc"
+                },
+                Record {
+                    line: 3,
+                    text: "a\nb\nc"
+                },
+                Record {
+                    line: 8,
+                    text: "ab\r\nac"
+                },
+            ]
+        );
+        assert_eq!(records[1].lines().last(), Some((5, "c")));
+        assert_eq!(records[2].words().collect::<Vec<&str>>(), vec!["ab", "ac"]);
+        assert_eq!(super::records("\n \n").count(), 0);
+    }
+
+    #[test]
+    fn test_record_error() {
+        let record = records(INPUT).nth(1).unwrap();
+        let token = record.words().nth(1).unwrap();
+
+        assert_eq!(
+            record.error(token, "unexpected"),
+            ParseError::new(4, 1, "b", "unexpected")
+        );
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let counts = parse_records(INPUT, |record| Ok(record.words().count()));
+        assert_eq!(counts, Ok(vec![1, 3, 2]));
+
+        let err = parse_records(INPUT, |record| match record.words().find(|&w| w == "ac") {
+            Some(word) => Err(record.error(word, "unexpected")),
+            None => Ok(()),
+        });
+        assert_eq!(err, Err(ParseError::new(9, 1, "ac", "unexpected")));
+    }
+}
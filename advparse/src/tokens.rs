@@ -0,0 +1,84 @@
+use crate::ParseError;
+use std::str::FromStr;
+
+// The token must be a part of the line, so the error points to it
+pub fn parse_number<T: FromStr>(line: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .trim()
+        .parse::<T>()
+        .map_err(|_| ParseError::in_line(line, token, "expected a number"))
+}
+
+// The next token of the line, e.g. from split_whitespace
+pub fn next_token<'a, I>(line: &str, tokens: &mut I, expected: &str) -> Result<&'a str, ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    tokens
+        .next()
+        .ok_or_else(|| ParseError::end_of_line(line, format!("expected {}", expected)))
+}
+
+// The next token must be exactly the word, e.g. "to" in "London to Dublin = 464"
+pub fn expect_token<'a, I>(line: &str, tokens: &mut I, word: &str) -> Result<(), ParseError>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let expected = format!("\"{}\"", word);
+    match next_token(line, tokens, expected.as_str())? {
+        token if token == word => Ok(()),
+        token => Err(ParseError::in_line(
+            line,
+            token,
+            format!("expected {}", expected),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number() {
+        let line = "move 12 steps";
+        assert_eq!(parse_number::<u8>(line, &line[5..7]), Ok(12));
+        assert_eq!(
+            parse_number::<u8>(line, &line[8..]),
+            Err(ParseError::new(0, 9, "steps", "expected a number"))
+        );
+    }
+
+    #[test]
+    fn test_next_token() {
+        let line = "turn on";
+        let mut tokens = line.split_whitespace();
+
+        assert_eq!(next_token(line, &mut tokens, "a command"), Ok("turn"));
+        assert_eq!(next_token(line, &mut tokens, "a switch"), Ok("on"));
+        assert_eq!(
+            next_token(line, &mut tokens, "a coordinate"),
+            Err(ParseError::new(0, 8, "", "expected a coordinate"))
+        );
+    }
+
+    #[test]
+    fn test_expect_token() {
+        let line = "London to Dublin";
+        let mut tokens = line.split_whitespace();
+
+        assert_eq!(
+            expect_token(line, &mut tokens, "to"),
+            Err(ParseError::new(0, 1, "London", "expected \"to\""))
+        );
+        assert_eq!(expect_token(line, &mut tokens, "to"), Ok(()));
+        assert_eq!(
+            expect_token(line, &mut tokens, "="),
+            Err(ParseError::new(0, 11, "Dublin", "expected \"=\""))
+        );
+        assert_eq!(
+            expect_token(line, &mut tokens, "="),
+            Err(ParseError::new(0, 17, "", "expected \"=\""))
+        );
+    }
+}
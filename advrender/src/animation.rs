@@ -0,0 +1,133 @@
+use crate::Frame;
+use std::borrow::Cow;
+
+// The frames of a puzzle, e.g. the generations of 2015 day 18;
+// the name is the file name without the extension
+pub struct Animation {
+    name: String,
+    frames: Vec<Frame>,
+    delay: u16,
+}
+
+// A tenth of a second between the frames
+const DEFAULT_DELAY: u16 = 10;
+
+impl Animation {
+    pub fn new(name: &str) -> Animation {
+        Animation {
+            name: String::from(name),
+            frames: Vec::new(),
+            delay: DEFAULT_DELAY,
+        }
+    }
+
+    // A single picture, e.g. the lights of 2015 day 6
+    pub fn still(name: &str, frame: Frame) -> Animation {
+        let mut animation = Animation::new(name);
+        animation.push(frame);
+
+        animation
+    }
+
+    // In hundredths of a second, like in GIF
+    pub fn with_delay(self, delay: u16) -> Animation {
+        Animation { delay, ..self }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        if let Some(first) = self.frames.first() {
+            assert!(
+                first.width() == frame.width() && first.height() == frame.height(),
+                "the frames of an animation must have the same size"
+            );
+        }
+
+        self.frames.push(frame);
+    }
+
+    // Loops forever with a gray palette
+    pub fn to_gif(&self) -> Result<Vec<u8>, gif::EncodingError> {
+        let (width, height) = self
+            .frames
+            .first()
+            .map_or((0, 0), |frame| (frame.width(), frame.height()));
+        let palette: Vec<u8> = (0..=255u8).flat_map(|gray| [gray; 3]).collect();
+        let mut bytes = Vec::new();
+
+        {
+            let mut encoder = gif::Encoder::new(&mut bytes, width as u16, height as u16, &palette)?;
+            encoder.set_repeat(gif::Repeat::Infinite)?;
+
+            for frame in &self.frames {
+                let gif_frame = gif::Frame {
+                    width: width as u16,
+                    height: height as u16,
+                    delay: self.delay,
+                    buffer: Cow::Borrowed(frame.pixels()),
+                    ..gif::Frame::default()
+                };
+                encoder.write_frame(&gif_frame)?;
+            }
+        }
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(pixels: [u8; 4]) -> Frame {
+        let grid = advgrid::Grid::from_rows(vec![pixels[..2].to_vec(), pixels[2..].to_vec()]);
+        Frame::from_grid(&grid, |&pixel| pixel)
+    }
+
+    #[test]
+    fn test_push() {
+        let mut animation = Animation::new("blink");
+        animation.push(frame([0, 255, 255, 0]));
+        animation.push(frame([255, 0, 0, 255]));
+
+        assert_eq!(animation.name(), "blink");
+        assert_eq!(animation.frames().len(), 2);
+        assert_eq!(Animation::still("one", Frame::new(1, 1)).frames().len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_push_other_size() {
+        let mut animation = Animation::still("one", Frame::new(1, 1));
+        animation.push(Frame::new(2, 1));
+    }
+
+    #[test]
+    fn test_gif() {
+        let mut animation = Animation::new("blink").with_delay(50);
+        animation.push(frame([0, 255, 255, 0]));
+        animation.push(frame([255, 0, 0, 255]));
+        let gif = animation.to_gif().unwrap();
+
+        assert!(gif.starts_with(b"GIF89a"));
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.delay, frame.buffer.to_vec()));
+        }
+        assert_eq!(
+            frames,
+            vec![(50, vec![0, 255, 255, 0]), (50, vec![255, 0, 0, 255])]
+        );
+    }
+}
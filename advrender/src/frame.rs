@@ -0,0 +1,143 @@
+use advgrid::Grid;
+
+// A grayscale picture, 0 is black and 255 is white
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Frame {
+        Frame {
+            width,
+            height,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    // A pixel for every cell, e.g. a light which is on is white
+    pub fn from_grid<T, F: Fn(&T) -> u8>(grid: &Grid<T>, shade: F) -> Frame {
+        Frame {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.cells().map(shade).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    // Every pixel becomes a square, so a small grid is still visible
+    pub fn scaled(&self, factor: usize) -> Frame {
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let pixels = (0..width * height)
+            .map(|i| self.pixels[(i / width / factor) * self.width + (i % width) / factor])
+            .collect();
+
+        Frame {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    // The binary P6 format, gray is written as three equal colours
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flat_map(|&pixel| [pixel; 3]));
+
+        bytes
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let mut bytes = Vec::new();
+
+        let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker() -> Frame {
+        let grid: Grid<bool> = Grid::parse("#.\n.#").unwrap();
+        Frame::from_grid(&grid, |&light| if light { 255 } else { 0 })
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let frame = checker();
+
+        assert_eq!(frame.width(), 2);
+        assert_eq!(frame.height(), 2);
+        assert_eq!(frame.pixels(), &[255, 0, 0, 255]);
+        assert_eq!(frame.get(1, 1), Some(255));
+        assert_eq!(frame.get(2, 1), None);
+        assert_eq!(Frame::new(3, 1).pixels(), &[0, 0, 0]);
+    }
+
+    #[test]
+    fn test_scaled() {
+        let frame = checker().scaled(2);
+
+        assert_eq!(frame.width(), 4);
+        assert_eq!(frame.height(), 4);
+        assert_eq!(
+            frame.pixels(),
+            &[255, 255, 0, 0, 255, 255, 0, 0, 0, 0, 255, 255, 0, 0, 255, 255]
+        );
+        assert_eq!(checker().scaled(1), checker());
+    }
+
+    #[test]
+    fn test_ppm() {
+        let ppm = checker().to_ppm();
+
+        assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 2 * 2 * 3);
+        assert_eq!(&ppm[11..17], &[255, 255, 255, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_png() {
+        let png = checker().to_png().unwrap();
+
+        assert!(png.starts_with(&[0x89, b'P', b'N', b'G']));
+
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (2, 2));
+        assert_eq!(&pixels[..info.buffer_size()], checker().pixels());
+    }
+}
@@ -0,0 +1,21 @@
+mod animation;
+mod frame;
+mod output;
+mod scene;
+
+pub use animation::Animation;
+pub use frame::Frame;
+pub use output::{write_animation, Format, RenderError};
+pub use scene::Scene;
+
+use advcore::Params;
+use advparse::ParseError;
+
+// The pictures of a day, which are drawn from the input
+pub type Renderer = fn(&str, &Params) -> Result<Vec<Animation>, ParseError>;
+
+// The terminal animations of a day, see --animate
+pub type Animator = fn(&str, &Params) -> Result<Vec<Scene>, ParseError>;
+
+// The text of a day, which shows how the answer is found, see --log
+pub type Logger = fn(&str, &Params) -> Result<String, ParseError>;
@@ -0,0 +1,160 @@
+use crate::Animation;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+// The format of the single frames
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Png,
+    Ppm,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Png => "png",
+            Format::Ppm => "ppm",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "png" => Ok(Format::Png),
+            "ppm" => Ok(Format::Ppm),
+            _ => Err(format!("unknown image format: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RenderError {
+    Io(PathBuf, io::Error),
+    Png(PathBuf, png::EncodingError),
+    Gif(PathBuf, gif::EncodingError),
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderError::Io(path, err) => {
+                write!(f, "Couldn't write {}: {}", path.display(), err)
+            }
+            RenderError::Png(path, err) => {
+                write!(f, "Couldn't encode {}: {}", path.display(), err)
+            }
+            RenderError::Gif(path, err) => {
+                write!(f, "Couldn't encode {}: {}", path.display(), err)
+            }
+        }
+    }
+}
+
+// A single frame is <name>.png, more frames are <name>-000.png, <name>-001.png
+// and so on, and also <name>.gif; returns the written files. The numbers are
+// as wide as the frame count, so the files are sorted by their names too
+pub fn write_animation(
+    dir: &Path,
+    animation: &Animation,
+    format: Format,
+) -> Result<Vec<PathBuf>, RenderError> {
+    fs::create_dir_all(dir).map_err(|err| RenderError::Io(dir.to_path_buf(), err))?;
+
+    let frames = animation.frames();
+    let width = frames.len().to_string().len().max(3);
+    let mut written = Vec::with_capacity(frames.len() + 1);
+
+    for (index, frame) in frames.iter().enumerate() {
+        let name = match frames.len() {
+            1 => format!("{}.{}", animation.name(), format.extension()),
+            _ => format!(
+                "{}-{:0width$}.{}",
+                animation.name(),
+                index,
+                format.extension(),
+                width = width
+            ),
+        };
+        let path = dir.join(name);
+        let bytes = match format {
+            Format::Png => frame
+                .to_png()
+                .map_err(|err| RenderError::Png(path.clone(), err))?,
+            Format::Ppm => frame.to_ppm(),
+        };
+
+        write(&path, &bytes)?;
+        written.push(path);
+    }
+
+    if frames.len() > 1 {
+        let path = dir.join(format!("{}.gif", animation.name()));
+        let bytes = animation
+            .to_gif()
+            .map_err(|err| RenderError::Gif(path.clone(), err))?;
+
+        write(&path, &bytes)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+fn write(path: &Path, bytes: &[u8]) -> Result<(), RenderError> {
+    fs::write(path, bytes).map_err(|err| RenderError::Io(path.to_path_buf(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Frame;
+    use std::env;
+
+    #[test]
+    fn test_format() {
+        assert_eq!("png".parse::<Format>(), Ok(Format::Png));
+        assert_eq!("ppm".parse::<Format>(), Ok(Format::Ppm));
+        assert!("jpg".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_write_animation() {
+        let dir = env::temp_dir().join(format!("advrender-{}", std::process::id()));
+
+        let still = Animation::still("still", Frame::new(2, 2));
+        let written = write_animation(&dir, &still, Format::Ppm).unwrap();
+        assert_eq!(written, vec![dir.join("still.ppm")]);
+        assert_eq!(fs::read(&written[0]).unwrap(), Frame::new(2, 2).to_ppm());
+
+        let mut moving = Animation::new("moving");
+        moving.push(Frame::new(2, 2));
+        moving.push(Frame::new(2, 2));
+        let written = write_animation(&dir, &moving, Format::Png).unwrap();
+        assert_eq!(
+            written,
+            vec![
+                dir.join("moving-000.png"),
+                dir.join("moving-001.png"),
+                dir.join("moving.gif")
+            ]
+        );
+        assert!(written.iter().all(|path| path.is_file()));
+
+        let mut long = Animation::new("long");
+        for _ in 0..1001 {
+            long.push(Frame::new(1, 1));
+        }
+        let written = write_animation(&dir, &long, Format::Ppm).unwrap();
+        assert_eq!(written[0], dir.join("long-0000.ppm"));
+        assert_eq!(written[1000], dir.join("long-1000.ppm"));
+        assert!(written[..1001].windows(2).all(|pair| pair[0] < pair[1]));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+// A text animation for the terminal, e.g. the seating rounds of 2020 day 11;
+// the frames are drawn only when they are shown, a walk can be long
+pub struct Scene {
+    name: String,
+    frames: Box<dyn Iterator<Item = String>>,
+}
+
+impl Scene {
+    pub fn new<I>(name: &str, frames: I) -> Scene
+    where
+        I: Iterator<Item = String> + 'static,
+    {
+        Scene {
+            name: String::from(name),
+            frames: Box::new(frames),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl Iterator for Scene {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.frames.next()
+    }
+}
+
+impl fmt::Debug for Scene {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scene").field("name", &self.name).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scene() {
+        let mut count = 0;
+        let scene = Scene::new(
+            "count",
+            std::iter::from_fn(move || {
+                count += 1;
+                (count <= 3).then(|| count.to_string())
+            }),
+        );
+
+        assert_eq!(scene.name(), "count");
+        assert_eq!(format!("{:?}", scene), "Scene { name: \"count\" }");
+        assert_eq!(scene.collect::<Vec<_>>(), vec!["1", "2", "3"]);
+    }
+}